@@ -1,2 +1,4 @@
+mod rfc2136;
 mod rfc4035;
+mod rfc8945;
 mod scenarios;
@@ -0,0 +1,202 @@
+use std::net::Ipv4Addr;
+
+use dns_test::client::{Client, DigSettings, DigStatus, Prerequisite, Update};
+use dns_test::name_server::{NameServer, Running, SignSettings, UpdatePolicy};
+use dns_test::record::{Record, RecordType};
+use dns_test::tsig::{TsigAlgorithm, TsigKey};
+use dns_test::{Network, Result, FQDN};
+
+#[test]
+#[ignore]
+fn added_record_is_served() -> Result<()> {
+    let network = Network::new()?;
+    let ns = updatable_name_server(&network, UpdatePolicy::Anyone)?;
+    let needle = Record::a(needle_fqdn()?, Ipv4Addr::new(1, 2, 3, 4));
+
+    let client = Client::new(&network)?;
+    let output = client.nsupdate(ns.ipv4_addr(), Update::new(FQDN::COM).add(needle.clone()))?;
+    assert_eq!(DigStatus::NOERROR, output.status);
+
+    let ans = client.dig(
+        DigSettings::default(),
+        ns.ipv4_addr(),
+        RecordType::A,
+        &needle_fqdn()?,
+    )?;
+    assert!(ans.status.is_noerror());
+    let [a] = ans.answer.try_into().unwrap();
+    assert_eq!(needle.to_string(), a.to_string());
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn deleted_rrset_is_not_served() -> Result<()> {
+    let network = Network::new()?;
+    let ns = updatable_name_server(&network, UpdatePolicy::Anyone)?;
+    let needle_fqdn = needle_fqdn()?;
+
+    let client = Client::new(&network)?;
+    let mut update = Update::new(FQDN::COM);
+    update.add(Record::a(needle_fqdn.clone(), Ipv4Addr::new(1, 2, 3, 4)));
+    client.nsupdate(ns.ipv4_addr(), &update)?;
+
+    let output = client.nsupdate(
+        ns.ipv4_addr(),
+        Update::new(FQDN::COM).delete_rrset(&needle_fqdn, RecordType::A),
+    )?;
+    assert_eq!(DigStatus::NOERROR, output.status);
+
+    let ans = client.dig(
+        DigSettings::default(),
+        ns.ipv4_addr(),
+        RecordType::A,
+        &needle_fqdn,
+    )?;
+    assert!(ans.status.is_nxdomain());
+
+    Ok(())
+}
+
+// section 3.2.5
+#[test]
+#[ignore]
+fn unmet_prerequisites_are_reported() -> Result<()> {
+    let network = Network::new()?;
+    let ns = updatable_name_server(&network, UpdatePolicy::Anyone)?;
+    let needle_fqdn = needle_fqdn()?;
+    let client = Client::new(&network)?;
+
+    let cases = [
+        (
+            Prerequisite::NameInUse(needle_fqdn.clone()),
+            DigStatus::NXDOMAIN,
+        ),
+        (Prerequisite::NameNotInUse(FQDN::COM), DigStatus::YXDOMAIN),
+        (
+            Prerequisite::RRsetExists(FQDN::COM, RecordType::A),
+            DigStatus::NXRRSET,
+        ),
+        (
+            Prerequisite::RRsetDoesNotExist(FQDN::COM, RecordType::SOA),
+            DigStatus::YXRRSET,
+        ),
+    ];
+
+    for (prerequisite, expected) in cases {
+        let mut update = Update::new(FQDN::COM);
+        update
+            .prerequisite(prerequisite)
+            .add(Record::a(needle_fqdn.clone(), Ipv4Addr::new(1, 2, 3, 4)));
+        let output = client.nsupdate(ns.ipv4_addr(), &update)?;
+        assert_eq!(expected, output.status);
+    }
+
+    // none of the updates were applied
+    let ans = client.dig(
+        DigSettings::default(),
+        ns.ipv4_addr(),
+        RecordType::A,
+        &needle_fqdn,
+    )?;
+    assert!(ans.status.is_nxdomain());
+
+    Ok(())
+}
+
+// section 3.1.1
+#[test]
+#[ignore]
+fn update_of_a_zone_the_server_is_not_authoritative_for_is_notauth() -> Result<()> {
+    let network = Network::new()?;
+    let ns = updatable_name_server(&network, UpdatePolicy::Anyone)?;
+    let zone = FQDN("org.")?;
+
+    let client = Client::new(&network)?;
+    let output = client.nsupdate(
+        ns.ipv4_addr(),
+        Update::new(zone).add(Record::a(FQDN("example.org.")?, Ipv4Addr::new(1, 2, 3, 4))),
+    )?;
+    assert_eq!(DigStatus::NOTAUTH, output.status);
+
+    Ok(())
+}
+
+// section 3.3
+#[test]
+#[ignore]
+fn unauthorized_update_is_refused() -> Result<()> {
+    let network = Network::new()?;
+    let ns = NameServer::new(&dns_test::SUBJECT, FQDN::COM, &network)?.start()?;
+    let needle = Record::a(needle_fqdn()?, Ipv4Addr::new(1, 2, 3, 4));
+
+    let client = Client::new(&network)?;
+    let output = client.nsupdate(ns.ipv4_addr(), Update::new(FQDN::COM).add(needle))?;
+    assert_eq!(DigStatus::REFUSED, output.status);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn update_signed_with_tsig_key_is_accepted() -> Result<()> {
+    let network = Network::new()?;
+    let key = TsigKey::generate(FQDN("update-key.")?, TsigAlgorithm::HmacSHA256)?;
+    let ns = updatable_name_server(&network, UpdatePolicy::Tsig(key.clone()))?;
+    let needle = Record::a(needle_fqdn()?, Ipv4Addr::new(1, 2, 3, 4));
+
+    let client = Client::new(&network)?;
+    let mut update = Update::new(FQDN::COM);
+    update.add(needle);
+
+    let output = client.nsupdate(ns.ipv4_addr(), &update)?;
+    assert_eq!(DigStatus::REFUSED, output.status);
+
+    let output = client.nsupdate(ns.ipv4_addr(), update.key(key))?;
+    assert_eq!(DigStatus::NOERROR, output.status);
+
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn added_record_is_signed_online() -> Result<()> {
+    let network = Network::new()?;
+    let mut ns = NameServer::new(&dns_test::SUBJECT, FQDN::COM, &network)?;
+    ns.allow_updates(UpdatePolicy::Anyone);
+    let ns = ns.sign(SignSettings::default())?.start()?;
+    let needle_fqdn = needle_fqdn()?;
+
+    let client = Client::new(&network)?;
+    let output = client.nsupdate(
+        ns.ipv4_addr(),
+        Update::new(FQDN::COM).add(Record::a(needle_fqdn.clone(), Ipv4Addr::new(1, 2, 3, 4))),
+    )?;
+    assert_eq!(DigStatus::NOERROR, output.status);
+
+    let ans = client.dig(
+        *DigSettings::default().dnssec(),
+        ns.ipv4_addr(),
+        RecordType::A,
+        &needle_fqdn,
+    )?;
+    assert!(ans.status.is_noerror());
+    let [a, rrsig] = ans.answer.try_into().unwrap();
+    assert!(matches!(a, Record::A(..)));
+    let rrsig = rrsig.try_into_rrsig().unwrap();
+    assert_eq!(RecordType::A, rrsig.type_covered);
+    assert_eq!(FQDN::COM, rrsig.signer_name);
+
+    Ok(())
+}
+
+fn updatable_name_server(network: &Network, policy: UpdatePolicy) -> Result<NameServer<Running>> {
+    let mut ns = NameServer::new(&dns_test::SUBJECT, FQDN::COM, network)?;
+    ns.allow_updates(policy);
+    ns.start()
+}
+
+fn needle_fqdn() -> Result<FQDN> {
+    FQDN("example.com.")
+}
@@ -1,5 +1,5 @@
 use dns_test::client::{Client, DigSettings};
-use dns_test::name_server::NameServer;
+use dns_test::name_server::{NameServer, SignSettings};
 use dns_test::record::{Record, RecordType};
 use dns_test::{Network, Result, FQDN};
 
@@ -9,7 +9,7 @@ fn rrsig_in_answer_section() -> Result<()> {
     let network = Network::new()?;
 
     let ns = NameServer::new(&dns_test::SUBJECT, FQDN::ROOT, &network)?
-        .sign()?
+        .sign(SignSettings::default())?
         .start()?;
 
     let client = Client::new(&network)?;
@@ -22,10 +22,11 @@ fn rrsig_in_answer_section() -> Result<()> {
     )?;
 
     assert!(ans.status.is_noerror());
-    let [a, rrsig] = ans.answer.try_into().unwrap();
+    let [rrset] = ans.answer_rrsets()?.try_into().unwrap();
 
-    assert!(matches!(a, Record::A(..)));
-    let rrsig = rrsig.try_into_rrsig().unwrap();
+    assert_eq!(RecordType::A, rrset.record_type());
+    assert!(matches!(rrset.records.as_slice(), [Record::A(..)]));
+    let [rrsig] = rrset.rrsigs.try_into().unwrap();
     assert_eq!(RecordType::A, rrsig.type_covered);
     assert_eq!(ns_fqdn, &rrsig.fqdn);
 
@@ -38,7 +39,7 @@ fn rrsig_in_authority_section() -> Result<()> {
     let network = Network::new()?;
 
     let ns = NameServer::new(&dns_test::SUBJECT, FQDN::ROOT, &network)?
-        .sign()?
+        .sign(SignSettings::default())?
         .start()?;
 
     let client = Client::new(&network)?;
@@ -50,10 +51,11 @@ fn rrsig_in_authority_section() -> Result<()> {
     )?;
 
     assert!(ans.status.is_noerror());
-    let [ns, rrsig] = ans.authority.try_into().unwrap();
+    let [rrset] = ans.authority_rrsets()?.try_into().unwrap();
 
-    assert!(matches!(ns, Record::NS(..)));
-    let rrsig = rrsig.try_into_rrsig().unwrap();
+    assert_eq!(RecordType::NS, rrset.record_type());
+    assert!(matches!(rrset.records.as_slice(), [Record::NS(..)]));
+    let [rrsig] = rrset.rrsigs.try_into().unwrap();
     assert_eq!(RecordType::NS, rrsig.type_covered);
     assert_eq!(FQDN::ROOT, rrsig.fqdn);
 
@@ -0,0 +1 @@
+mod section_5;
@@ -0,0 +1,129 @@
+use dns_test::client::{Client, DigSettings, DigStatus, TsigError, TsigStatus};
+use dns_test::name_server::{NameServer, Running};
+use dns_test::record::RecordType;
+use dns_test::tsig::{TsigAlgorithm, TsigKey};
+use dns_test::{Clock, Network, Result, FQDN};
+
+#[test]
+#[ignore]
+fn signed_request_gets_signed_response() -> Result<()> {
+    let network = Network::new()?;
+    let key = key()?;
+    let ns = name_server(&network, &key, Clock::Real)?;
+
+    let client = Client::new(&network)?;
+    let output = client.dig(
+        *DigSettings::default().tsig(&key),
+        ns.ipv4_addr(),
+        RecordType::SOA,
+        &FQDN::COM,
+    )?;
+
+    assert!(output.status.is_noerror());
+    assert_eq!(
+        Some(TsigStatus {
+            error: Some(TsigError::NOERROR),
+            verified: true,
+        }),
+        output.tsig
+    );
+
+    Ok(())
+}
+
+// section 5.2.1
+#[test]
+#[ignore]
+fn unknown_key_is_badkey() -> Result<()> {
+    let network = Network::new()?;
+    let ns = name_server(&network, &key()?, Clock::Real)?;
+    let unknown_key = TsigKey::generate(FQDN("unknown-key.")?, TsigAlgorithm::HmacSHA256)?;
+
+    let client = Client::new(&network)?;
+    let output = client.dig(
+        *DigSettings::default().tsig(&unknown_key),
+        ns.ipv4_addr(),
+        RecordType::SOA,
+        &FQDN::COM,
+    )?;
+
+    assert_eq!(DigStatus::NOTAUTH, output.status);
+    assert_eq!(
+        Some(TsigStatus {
+            error: Some(TsigError::BADKEY),
+            verified: false,
+        }),
+        output.tsig
+    );
+
+    Ok(())
+}
+
+// section 5.2.2
+#[test]
+#[ignore]
+fn wrong_secret_is_badsig() -> Result<()> {
+    let network = Network::new()?;
+    let key = key()?;
+    let ns = name_server(&network, &key, Clock::Real)?;
+    let forged_key = TsigKey::generate(key.name.clone(), key.algorithm)?;
+
+    let client = Client::new(&network)?;
+    let output = client.dig(
+        *DigSettings::default().tsig(&forged_key),
+        ns.ipv4_addr(),
+        RecordType::SOA,
+        &FQDN::COM,
+    )?;
+
+    assert_eq!(DigStatus::NOTAUTH, output.status);
+    assert_eq!(
+        Some(TsigStatus {
+            error: Some(TsigError::BADSIG),
+            verified: false,
+        }),
+        output.tsig
+    );
+
+    Ok(())
+}
+
+// section 5.2.3
+#[test]
+#[ignore]
+fn time_skew_beyond_fudge_is_badtime() -> Result<()> {
+    const HOUR: i64 = 60 * 60;
+
+    let network = Network::new()?;
+    let key = key()?;
+    let ns = name_server(&network, &key, Clock::Offset(HOUR))?;
+
+    let client = Client::new(&network)?;
+    let output = client.dig(
+        *DigSettings::default().tsig(&key),
+        ns.ipv4_addr(),
+        RecordType::SOA,
+        &FQDN::COM,
+    )?;
+
+    assert_eq!(DigStatus::NOTAUTH, output.status);
+    assert_eq!(
+        Some(TsigError::BADTIME),
+        output.tsig.and_then(|tsig| tsig.error)
+    );
+
+    Ok(())
+}
+
+fn key() -> Result<TsigKey> {
+    TsigKey::generate(FQDN("transfer-key.")?, TsigAlgorithm::HmacSHA256)
+}
+
+/// Name servers only know the TSIG keys they are configured with for some purpose, like zone
+/// transfers
+fn name_server(network: &Network, key: &TsigKey, clock: Clock) -> Result<NameServer<Running>> {
+    let mut ns = NameServer::new(&dns_test::SUBJECT, FQDN::COM, network)?;
+    ns.transfer_key(key.clone());
+    ns.set_clock(clock)?;
+    ns.start()
+}
@@ -1,6 +1,8 @@
+use std::net::Ipv4Addr;
+
 use dns_test::client::{Client, DigSettings};
 use dns_test::name_server::NameServer;
-use dns_test::record::RecordType;
+use dns_test::record::{Record, RecordType};
 use dns_test::{Network, Result, FQDN};
 
 #[test]
@@ -21,3 +23,27 @@ fn authoritative_answer() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn zone_transfer() -> Result<()> {
+    let network = &Network::new()?;
+    let mut ns = NameServer::new(&dns_test::SUBJECT, FQDN::COM, network)?;
+    let needle = Record::a(FQDN("example.com.")?, Ipv4Addr::new(1, 2, 3, 4));
+    ns.add(needle.clone());
+    let ns = ns.start()?;
+
+    let client = Client::new(network)?;
+    let zone_file = client.axfr(ns.ipv4_addr(), &FQDN::COM)?;
+
+    assert_eq!(
+        ns.zone_file().soa.settings.serial,
+        zone_file.soa.settings.serial
+    );
+    assert_eq!(ns.zone_file().records.len(), zone_file.records.len());
+    assert!(zone_file
+        .records
+        .iter()
+        .any(|record| record.to_string() == needle.to_string()));
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use std::net::Ipv4Addr;
+use std::time::Duration;
+use std::{mem, thread};
 
 use dns_test::client::{Client, DigSettings};
-use dns_test::name_server::{Graph, NameServer, Sign};
-use dns_test::record::{Record, RecordType};
+use dns_test::name_server::{Graph, NameServer, Replication, Sign, ZoneSpec};
+use dns_test::record::{Record, RecordType, A};
 use dns_test::{Network, Resolver, Result, FQDN};
 
 #[test]
@@ -40,6 +42,196 @@ fn can_resolve() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn can_resolve_when_a_name_server_is_down() -> Result<()> {
+    let expected_ipv4_addr = Ipv4Addr::new(1, 2, 3, 4);
+    let leaf_zone = FQDN("example.org.")?;
+    let needle_fqdn = FQDN("www.example.org.")?;
+
+    let network = Network::new()?;
+
+    let mut leaf_ns = NameServer::new(&dns_test::PEER, leaf_zone.clone(), &network)?;
+    leaf_ns.add(Record::a(needle_fqdn.clone(), expected_ipv4_addr));
+    leaf_ns.add_secondary(&dns_test::PEER, Replication::Copy)?;
+
+    let Graph {
+        mut nameservers,
+        root,
+        ..
+    } = Graph::build(leaf_ns, Sign::No)?;
+
+    // take down the primary name server of the leaf zone but keep its secondary running
+    let mut primary = nameservers.remove(&leaf_zone).unwrap();
+    let _secondaries = mem::take(primary.secondaries_mut());
+    drop(primary);
+
+    let resolver = Resolver::new(&network, root).start(&dns_test::SUBJECT)?;
+    let resolver_ip_addr = resolver.ipv4_addr();
+
+    let client = Client::new(&network)?;
+
+    let settings = *DigSettings::default().recurse();
+    let output = client.dig(settings, resolver_ip_addr, RecordType::A, &needle_fqdn)?;
+
+    assert!(output.status.is_noerror());
+
+    let [answer] = output.answer.try_into().unwrap();
+    let a = answer.try_into_a().unwrap();
+
+    assert_eq!(expected_ipv4_addr, a.ipv4_addr);
+
+    Ok(())
+}
+
+#[test]
+fn cached_record_is_served_until_its_ttl_expires() -> Result<()> {
+    const TTL: u32 = 2;
+
+    let old_ipv4_addr = Ipv4Addr::new(1, 2, 3, 4);
+    let new_ipv4_addr = Ipv4Addr::new(5, 6, 7, 8);
+    let leaf_zone = FQDN("example.org.")?;
+    let needle_fqdn = FQDN("www.example.org.")?;
+
+    let network = Network::new()?;
+
+    let mut leaf_ns = NameServer::new(&dns_test::PEER, leaf_zone.clone(), &network)?;
+    leaf_ns.add(A {
+        fqdn: needle_fqdn.clone(),
+        ttl: TTL,
+        ipv4_addr: old_ipv4_addr,
+    });
+
+    let Graph {
+        mut nameservers,
+        root,
+        ..
+    } = Graph::build(leaf_ns, Sign::No)?;
+
+    let resolver = Resolver::new(&network, root).start(&dns_test::SUBJECT)?;
+    let resolver_ip_addr = resolver.ipv4_addr();
+
+    let client = Client::new(&network)?;
+    let settings = *DigSettings::default().recurse();
+    let resolve = || -> Result<Ipv4Addr> {
+        let output = client.dig(settings, resolver_ip_addr, RecordType::A, &needle_fqdn)?;
+        assert!(output.status.is_noerror());
+
+        let [answer] = output.answer.try_into().unwrap();
+        Ok(answer.try_into_a().unwrap().ipv4_addr)
+    };
+
+    assert_eq!(old_ipv4_addr, resolve()?);
+
+    let leaf_ns = nameservers.get_mut(&leaf_zone).unwrap();
+    leaf_ns.update(|zone_file| {
+        for record in &mut zone_file.records {
+            if let Record::A(a) = record {
+                if a.fqdn == needle_fqdn {
+                    a.ipv4_addr = new_ipv4_addr;
+                }
+            }
+        }
+    })?;
+
+    assert_eq!(old_ipv4_addr, resolve()?);
+
+    thread::sleep(Duration::from_secs(u64::from(TTL) + 1));
+
+    assert_eq!(new_ipv4_addr, resolve()?);
+
+    Ok(())
+}
+
+#[test]
+fn can_resolve_in_deeply_nested_zone() -> Result<()> {
+    let expected_ipv4_addr = Ipv4Addr::new(1, 2, 3, 4);
+    let leaf_zone = FQDN("a.b.example.org.")?;
+    let needle_fqdn = FQDN("example.a.b.example.org.")?;
+
+    let network = Network::new()?;
+
+    let mut leaf_ns = NameServer::new(&dns_test::PEER, leaf_zone, &network)?;
+    leaf_ns.add(Record::a(needle_fqdn.clone(), expected_ipv4_addr));
+
+    let Graph {
+        nameservers: _nameservers,
+        root,
+        ..
+    } = Graph::build(leaf_ns, Sign::No)?;
+
+    let resolver = Resolver::new(&network, root).start(&dns_test::SUBJECT)?;
+    let resolver_ip_addr = resolver.ipv4_addr();
+
+    let client = Client::new(&network)?;
+
+    let settings = *DigSettings::default().recurse();
+    let output = client.dig(settings, resolver_ip_addr, RecordType::A, &needle_fqdn)?;
+
+    assert!(output.status.is_noerror());
+
+    let [answer] = output.answer.try_into().unwrap();
+    let a = answer.try_into_a().unwrap();
+
+    assert_eq!(needle_fqdn, a.fqdn);
+    assert_eq!(expected_ipv4_addr, a.ipv4_addr);
+
+    Ok(())
+}
+
+#[test]
+fn can_resolve_in_sibling_zones() -> Result<()> {
+    let needles = [
+        (FQDN("a.example.com.")?, Ipv4Addr::new(1, 2, 3, 4)),
+        (FQDN("b.example.org.")?, Ipv4Addr::new(5, 6, 7, 8)),
+        (FQDN("c.example2.org.")?, Ipv4Addr::new(9, 10, 11, 12)),
+    ];
+
+    let network = Network::new()?;
+
+    let mut root = ZoneSpec::new(FQDN::ROOT);
+    let mut com = ZoneSpec::new(FQDN::COM);
+    let mut org = ZoneSpec::new(FQDN("org.")?);
+    for (needle_fqdn, ipv4_addr) in &needles {
+        let zone = needle_fqdn.parent().unwrap();
+        let tld = if zone.parent() == Some(FQDN::COM) {
+            &mut com
+        } else {
+            &mut org
+        };
+
+        let mut leaf = ZoneSpec::new(zone);
+        leaf.add(Record::a(needle_fqdn.clone(), *ipv4_addr));
+        tld.child(leaf);
+    }
+    root.child(com).child(org);
+
+    let Graph {
+        nameservers: _nameservers,
+        root,
+        ..
+    } = Graph::from_spec(root, &dns_test::PEER, &network, Sign::No)?;
+
+    let resolver = Resolver::new(&network, root).start(&dns_test::SUBJECT)?;
+    let resolver_ip_addr = resolver.ipv4_addr();
+
+    let client = Client::new(&network)?;
+
+    let settings = *DigSettings::default().recurse();
+    for (needle_fqdn, expected_ipv4_addr) in needles {
+        let output = client.dig(settings, resolver_ip_addr, RecordType::A, &needle_fqdn)?;
+
+        assert!(output.status.is_noerror());
+
+        let [answer] = output.answer.try_into().unwrap();
+        let a = answer.try_into_a().unwrap();
+
+        assert_eq!(needle_fqdn, a.fqdn);
+        assert_eq!(expected_ipv4_addr, a.ipv4_addr);
+    }
+
+    Ok(())
+}
+
 #[ignore]
 #[test]
 fn nxdomain() -> Result<()> {
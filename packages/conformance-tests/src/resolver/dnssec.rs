@@ -2,4 +2,8 @@
 
 mod fixtures;
 mod rfc4035;
+mod rfc5011;
+mod rfc6781;
+mod rfc8624;
+mod rfc9276;
 mod scenarios;
@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
 use std::net::Ipv4Addr;
 
 use base64::prelude::*;
 use dns_test::{
-    name_server::{Graph, NameServer, Running, Sign},
-    record::Record,
+    client::{Client, DigOutput, DigSettings},
+    name_server::{Graph, NameServer, Running, Sign, SignSettings},
+    record::{Record, RecordType},
+    validator::{Reason, Validator, Verdict},
     Network, Resolver, Result, TrustAnchor, FQDN,
 };
 
@@ -20,28 +23,46 @@ pub fn bad_signature_in_leaf_nameserver(
 
     let graph = Graph::build(
         leaf_ns,
-        Sign::AndAmend(&|zone, records| {
-            if zone == &FQDN::NAMESERVERS {
-                let mut modified = 0;
-                for record in records {
-                    if let Record::RRSIG(rrsig) = record {
-                        if rrsig.fqdn == *leaf_fqdn {
-                            let mut signature = BASE64_STANDARD.decode(&rrsig.signature).unwrap();
-                            let last = signature.last_mut().expect("empty signature");
-                            *last = !*last;
-
-                            rrsig.signature = BASE64_STANDARD.encode(&signature);
-                            modified += 1;
+        Sign::AndAmend {
+            settings: SignSettings::default(),
+            mutate: &|zone, records| {
+                if zone == &FQDN::NAMESERVERS {
+                    let mut modified = 0;
+                    for record in records {
+                        if let Record::RRSIG(rrsig) = record {
+                            if rrsig.fqdn == *leaf_fqdn {
+                                let mut signature =
+                                    BASE64_STANDARD.decode(&rrsig.signature).unwrap();
+                                let last = signature.last_mut().expect("empty signature");
+                                *last = !*last;
+
+                                rrsig.signature = BASE64_STANDARD.encode(&signature);
+                                modified += 1;
+                            }
                         }
                     }
-                }
 
-                assert_eq!(modified, 1, "sanity check");
-            }
-        }),
+                    assert_eq!(modified, 1, "sanity check");
+                }
+            },
+        },
     )?;
 
     let trust_anchor = graph.trust_anchor.as_ref().unwrap();
+
+    // sanity check: the chain of trust is intact up to the leaf zone's A RRset
+    let mut validator = Validator::new(trust_anchor);
+    for nameserver in graph.nameservers.values() {
+        validator.add_zone_file(nameserver.signed_zone_file().expect("unsigned zone"));
+    }
+    assert!(
+        matches!(
+            validator.validate_rrset(leaf_fqdn, RecordType::A),
+            Verdict::Bogus(Reason::BadSignature { .. })
+        ),
+        "sanity check"
+    );
+
     let resolver = Resolver::new(&network, graph.root.clone())
         .trust_anchor(trust_anchor)
         .start(&dns_test::SUBJECT)?;
@@ -52,7 +73,7 @@ pub fn bad_signature_in_leaf_nameserver(
 pub fn minimally_secure(
     leaf_fqdn: FQDN,
     leaf_ipv4_addr: Ipv4Addr,
-) -> Result<(Resolver, Vec<NameServer<Running>>, TrustAnchor)> {
+) -> Result<(Resolver, BTreeMap<FQDN, NameServer<Running>>, TrustAnchor)> {
     assert_eq!(Some(FQDN::NAMESERVERS), leaf_fqdn.parent());
 
     let network = Network::new()?;
@@ -64,7 +85,12 @@ pub fn minimally_secure(
         nameservers,
         root,
         trust_anchor,
-    } = Graph::build(leaf_ns, Sign::Yes)?;
+    } = Graph::build(
+        leaf_ns,
+        Sign::Yes {
+            settings: SignSettings::default(),
+        },
+    )?;
 
     let trust_anchor = trust_anchor.unwrap();
     let resolver = Resolver::new(&network, root)
@@ -73,3 +99,48 @@ pub fn minimally_secure(
 
     Ok((resolver, nameservers, trust_anchor))
 }
+
+pub const NEEDLE_IPV4_ADDR: Ipv4Addr = Ipv4Addr::new(1, 2, 3, 4);
+
+pub fn needle_fqdn() -> FQDN {
+    FQDN("example.nameservers.com.").unwrap()
+}
+
+// a minimal DNS graph, with all its zones signed with `settings`, whose leaf zone contains the
+// "needle" A record
+pub fn needle_graph(network: &Network, settings: SignSettings) -> Result<Graph> {
+    let mut leaf_ns = NameServer::new(&dns_test::PEER, FQDN::NAMESERVERS, network)?;
+    leaf_ns.add(Record::a(needle_fqdn(), NEEDLE_IPV4_ADDR));
+
+    Graph::build(leaf_ns, Sign::Yes { settings })
+}
+
+// queries the "needle" A record, with the AD bit set, through `resolver`
+pub fn dig_needle(resolver: &Resolver) -> Result<DigOutput> {
+    let client = Client::new(resolver.network())?;
+    let settings = *DigSettings::default().recurse().authentic_data();
+    client.dig(
+        settings,
+        resolver.ipv4_addr(),
+        RecordType::A,
+        &needle_fqdn(),
+    )
+}
+
+// starts a resolver that has nothing cached and checks that it authenticates the "needle" record
+pub fn assert_validates(network: &Network, graph: &Graph) -> Result<()> {
+    let resolver = Resolver::new(network, graph.root.clone())
+        .trust_anchor(graph.trust_anchor.as_ref().unwrap())
+        .start(&dns_test::SUBJECT)?;
+    let output = dig_needle(&resolver)?;
+
+    assert!(output.status.is_noerror());
+    assert!(output.flags.authenticated_data);
+
+    let [a] = output.answer.try_into().unwrap();
+    let a = a.try_into_a().unwrap();
+    assert_eq!(needle_fqdn(), a.fqdn);
+    assert_eq!(NEEDLE_IPV4_ADDR, a.ipv4_addr);
+
+    Ok(())
+}
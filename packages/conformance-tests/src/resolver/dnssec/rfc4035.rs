@@ -1,2 +1,3 @@
 mod section_3;
 mod section_4;
+mod section_5;
@@ -1,6 +1,6 @@
 use dns_test::{
     client::{Client, DigSettings},
-    name_server::{Graph, NameServer, Sign},
+    name_server::{Graph, NameServer, Sign, SignSettings},
     record::RecordType,
     tshark::{Capture, Direction},
     Network, Resolver, Result, FQDN,
@@ -16,15 +16,17 @@ fn on_clients_ds_query_it_queries_the_parent_zone() -> Result<()> {
         nameservers,
         root,
         trust_anchor,
-    } = Graph::build(leaf_ns, Sign::Yes)?;
+    } = Graph::build(
+        leaf_ns,
+        Sign::Yes {
+            settings: SignSettings::default(),
+        },
+    )?;
 
-    let mut com_ns_addr = None;
-    for nameserver in &nameservers {
-        if nameserver.zone() == &FQDN::COM {
-            com_ns_addr = Some(nameserver.ipv4_addr());
-        }
-    }
-    let com_ns_addr = com_ns_addr.expect("com. NS not found");
+    let com_ns_addr = nameservers
+        .get(&FQDN::COM)
+        .expect("com. NS not found")
+        .ipv4_addr();
 
     let trust_anchor = &trust_anchor.unwrap();
     let resolver = Resolver::new(&network, root)
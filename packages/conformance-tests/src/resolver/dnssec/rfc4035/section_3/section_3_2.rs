@@ -2,7 +2,7 @@ mod section_3_2_2;
 
 use dns_test::{
     client::{Client, DigSettings},
-    name_server::NameServer,
+    name_server::{NameServer, SignSettings},
     record::{Record, RecordType},
     tshark::{Capture, Direction},
     Network, Resolver, Result, FQDN,
@@ -12,7 +12,7 @@ use dns_test::{
 fn do_bit_not_set_in_request() -> Result<()> {
     let network = &Network::new()?;
     let ns = NameServer::new(&dns_test::PEER, FQDN::ROOT, network)?
-        .sign()?
+        .sign(SignSettings::default())?
         .start()?;
     let resolver = Resolver::new(network, ns.root_hint()).start(&dns_test::SUBJECT)?;
 
@@ -56,7 +56,7 @@ fn do_bit_not_set_in_request() -> Result<()> {
 fn if_do_bit_not_set_in_request_then_requested_dnssec_record_is_not_stripped() -> Result<()> {
     let network = &Network::new()?;
     let ns = NameServer::new(&dns_test::PEER, FQDN::ROOT, network)?
-        .sign()?
+        .sign(SignSettings::default())?
         .start()?;
     let resolver = Resolver::new(network, ns.root_hint()).start(&dns_test::SUBJECT)?;
 
@@ -81,7 +81,7 @@ fn if_do_bit_not_set_in_request_then_requested_dnssec_record_is_not_stripped() -
 fn do_bit_set_in_request() -> Result<()> {
     let network = &Network::new()?;
     let ns = NameServer::new(&dns_test::PEER, FQDN::ROOT, network)?
-        .sign()?
+        .sign(SignSettings::default())?
         .start()?;
     let resolver = Resolver::new(network, ns.root_hint()).start(&dns_test::SUBJECT)?;
 
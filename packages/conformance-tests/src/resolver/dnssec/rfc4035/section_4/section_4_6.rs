@@ -32,7 +32,7 @@ fn clears_ad_bit_in_outgoing_queries() -> Result<()> {
     let mut ns_checks_count = 0;
     let mut client_checks_count = 0;
     let ns_addrs = nameservers
-        .iter()
+        .values()
         .map(|ns| ns.ipv4_addr())
         .collect::<Vec<_>>();
     for Capture { message, direction } in captures {
@@ -0,0 +1,87 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dns_test::client::DigOutput;
+use dns_test::name_server::SignSettings;
+use dns_test::{Clock, Network, Resolver, Result};
+
+use crate::resolver::dnssec::fixtures;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+
+// section 5.3.1: "The validator's notion of the current time MUST be less than or equal to the
+// time listed in the RRSIG RR's Expiration field"
+#[ignore]
+#[test]
+fn expired_signatures_are_bogus() -> Result<()> {
+    let now = now()?;
+    let output = fixture(now - 2 * DAY, now - DAY, Clock::Real)?;
+
+    assert!(output.status.is_servfail());
+    assert!(!output.flags.authenticated_data);
+
+    Ok(())
+}
+
+// section 5.3.1: "The validator's notion of the current time MUST be greater than or equal to
+// the time listed in the RRSIG RR's Inception field"
+#[ignore]
+#[test]
+fn signatures_that_are_not_valid_yet_are_bogus() -> Result<()> {
+    let now = now()?;
+    let output = fixture(now + DAY, now + 2 * DAY, Clock::Real)?;
+
+    assert!(output.status.is_servfail());
+    assert!(!output.flags.authenticated_data);
+
+    Ok(())
+}
+
+#[ignore]
+#[test]
+fn signatures_close_to_expiration_are_valid() -> Result<()> {
+    let now = now()?;
+    let output = fixture(now - DAY, now + 2 * HOUR, Clock::Offset(HOUR as i64))?;
+
+    assert!(output.status.is_noerror());
+    assert!(output.flags.authenticated_data);
+
+    Ok(())
+}
+
+// the signatures are valid according to the host's clock but not according to the resolver's
+#[ignore]
+#[test]
+fn validity_is_checked_against_the_resolvers_clock() -> Result<()> {
+    let now = now()?;
+    let output = fixture(now - DAY, now + DAY, Clock::Offset(2 * DAY as i64))?;
+
+    assert!(output.status.is_servfail());
+    assert!(!output.flags.authenticated_data);
+
+    Ok(())
+}
+
+// all the zones in the graph are signed with the given validity period
+fn fixture(inception: u64, expiration: u64, resolver_clock: Clock) -> Result<DigOutput> {
+    let network = Network::new()?;
+    let graph = fixtures::needle_graph(
+        &network,
+        SignSettings {
+            inception: Some(inception),
+            expiration: Some(expiration),
+            ..SignSettings::default()
+        },
+    )?;
+
+    let resolver = Resolver::new(&network, graph.root.clone())
+        .trust_anchor(graph.trust_anchor.as_ref().unwrap())
+        .clock(resolver_clock)
+        .start(&dns_test::SUBJECT)?;
+
+    fixtures::dig_needle(&resolver)
+}
+
+fn now() -> Result<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
+}
@@ -0,0 +1,140 @@
+use std::thread;
+use std::time::Duration;
+
+use dns_test::client::{Client, DigSettings};
+use dns_test::name_server::{KeyRole, KeyState, NameServer, SignSettings};
+use dns_test::record::{Record, RecordType};
+use dns_test::{Network, Resolver, Result, TrustAnchor, FQDN};
+
+// RFC5011 hold-down time used by the resolvers, in seconds
+const HOLD_DOWN: u32 = 10;
+// TTL of the DNSKEY RRset; resolvers probe the RRset of a managed trust anchor every half TTL
+const DNSKEY_TTL: u32 = 4;
+
+// section 2.1: "Revocation"
+// the only trust anchor has been revoked and the key that replaces it has not gone through the add
+// hold-down period so the resolver has no trust anchor left to validate the root zone
+#[ignore]
+#[test]
+fn revoked_key_is_not_used_as_trust_anchor() -> Result<()> {
+    let network = Network::new()?;
+
+    let mut ns = NameServer::new(&dns_test::PEER, FQDN::ROOT, &network)?;
+    ns.add(ns.a());
+    let settings = SignSettings::default();
+    let algorithm = settings.algorithm;
+    let mut ns = ns.sign(settings)?;
+
+    let old_ksk = ns.key_signing_key().clone();
+    ns.add_key(KeyRole::KeySigning, algorithm, KeyState::Active)?;
+    let revoked_ksk = ns.revoke_key(old_ksk.key_tag()?)?;
+    assert!(revoked_ksk.is_revoked());
+
+    let ns = ns.start()?;
+
+    let trust_anchor = TrustAnchor::from_iter([old_ksk]);
+    let resolver = Resolver::new(&network, ns.root_hint())
+        .trust_anchor(&trust_anchor)
+        .managed_trust_anchor()
+        .start(&dns_test::SUBJECT)?;
+
+    let client = Client::new(&network)?;
+    let settings = *DigSettings::default().recurse().authentic_data();
+    let output = client.dig(settings, resolver.ipv4_addr(), RecordType::SOA, &FQDN::ROOT)?;
+
+    assert!(!output.flags.authenticated_data);
+
+    Ok(())
+}
+
+// the same zone validates when the old key is still a trust anchor and has not been revoked
+#[ignore]
+#[test]
+fn unrevoked_key_is_used_as_trust_anchor() -> Result<()> {
+    let network = Network::new()?;
+
+    let mut ns = NameServer::new(&dns_test::PEER, FQDN::ROOT, &network)?;
+    ns.add(ns.a());
+    let settings = SignSettings::default();
+    let algorithm = settings.algorithm;
+    let mut ns = ns.sign(settings)?;
+
+    let old_ksk = ns.key_signing_key().clone();
+    ns.add_key(KeyRole::KeySigning, algorithm, KeyState::Active)?;
+
+    let ns = ns.start()?;
+
+    let trust_anchor = TrustAnchor::from_iter([old_ksk]);
+    let resolver = Resolver::new(&network, ns.root_hint())
+        .trust_anchor(&trust_anchor)
+        .managed_trust_anchor()
+        .start(&dns_test::SUBJECT)?;
+
+    let client = Client::new(&network)?;
+    let settings = *DigSettings::default().recurse().authentic_data();
+    let output = client.dig(settings, resolver.ipv4_addr(), RecordType::SOA, &FQDN::ROOT)?;
+
+    assert!(output.status.is_noerror());
+    assert!(output.flags.authenticated_data);
+
+    Ok(())
+}
+
+// section 2.4.1: "Add Hold-Down"
+// a KSK published next to the trust anchor becomes a trust anchor only once the resolver has seen
+// it for the whole hold-down time; from then on the old trust anchor can be revoked
+#[ignore]
+#[test]
+fn new_key_becomes_trust_anchor_after_hold_down() -> Result<()> {
+    let network = Network::new()?;
+
+    let mut ns = NameServer::new(&dns_test::PEER, FQDN::ROOT, &network)?;
+    ns.add(ns.a());
+    let settings = SignSettings::default();
+    let algorithm = settings.algorithm;
+    let mut ns = ns.sign(settings)?;
+
+    let old_ksk = ns.key_signing_key().clone();
+    ns.add_key(KeyRole::KeySigning, algorithm, KeyState::Active)?;
+    // a TTL lower than the original TTL of the RRSIGs doesn't invalidate them
+    for record in &mut ns.signed_zone_file_mut().records {
+        if let Record::DNSKEY(dnskey) = record {
+            dnskey.ttl = DNSKEY_TTL;
+        }
+    }
+
+    let mut ns = ns.start()?;
+
+    let trust_anchor = TrustAnchor::from_iter([old_ksk.clone()]);
+    let mut resolver_settings = Resolver::new(&network, ns.root_hint());
+    resolver_settings
+        .trust_anchor(&trust_anchor)
+        .managed_trust_anchor()
+        .hold_down(HOLD_DOWN);
+
+    // the new key's hold-down time starts when this resolver first sees it
+    let resolver = resolver_settings.start(&dns_test::SUBJECT)?;
+    let client = Client::new(&network)?;
+    assert!(is_authenticated(&client, &resolver)?);
+
+    thread::sleep(Duration::from_secs(u64::from(HOLD_DOWN + 4 * DNSKEY_TTL)));
+
+    ns.revoke_key(old_ksk.key_tag()?)?;
+    // a resolver that has not seen the new key for the whole hold-down time is left without a
+    // trust anchor
+    let new_resolver = resolver_settings.start(&dns_test::SUBJECT)?;
+    // let the cached DNSKEY RRset expire so that the first resolver sees the revoked key
+    thread::sleep(Duration::from_secs(u64::from(2 * DNSKEY_TTL)));
+
+    assert!(is_authenticated(&client, &resolver)?);
+    assert!(!is_authenticated(&client, &new_resolver)?);
+
+    Ok(())
+}
+
+fn is_authenticated(client: &Client, resolver: &Resolver) -> Result<bool> {
+    let settings = *DigSettings::default().recurse().authentic_data();
+    let output = client.dig(settings, resolver.ipv4_addr(), RecordType::SOA, &FQDN::ROOT)?;
+
+    Ok(output.status.is_noerror() && output.flags.authenticated_data)
+}
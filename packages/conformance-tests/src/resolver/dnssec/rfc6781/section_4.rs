@@ -0,0 +1,134 @@
+use dns_test::name_server::{Graph, KeyRole, KeyState, NameServer, Running, SignSettings};
+use dns_test::record::Algorithm;
+use dns_test::{Network, Result, FQDN};
+
+use crate::resolver::dnssec::fixtures;
+
+// every stage is checked with a resolver that has nothing cached. with a long-lived resolver the
+// zone operator would need to wait for the cached DNSKEY RRset to expire between stages
+
+// section 4.1.1.1: "Pre-Publish Zone Signing Key Rollover"
+#[ignore]
+#[test]
+fn zsk_pre_publish_rollover() -> Result<()> {
+    let (network, mut graph) = fixture()?;
+    fixtures::assert_validates(&network, &graph)?;
+
+    let leaf_ns = leaf_nameserver(&mut graph);
+    let old_zsk = key_tag(leaf_ns, KeyRole::ZoneSigning)?;
+
+    // new DNSKEY
+    let new_zsk = leaf_ns.add_key(
+        KeyRole::ZoneSigning,
+        Algorithm::RSASHA1_NSEC3_SHA1,
+        KeyState::Published,
+    )?;
+    let new_zsk = new_zsk.key_tag()?;
+    fixtures::assert_validates(&network, &graph)?;
+
+    // new RRSIGs
+    let leaf_ns = leaf_nameserver(&mut graph);
+    leaf_ns.set_key_state(new_zsk, KeyState::Active)?;
+    leaf_ns.set_key_state(old_zsk, KeyState::Retired)?;
+    fixtures::assert_validates(&network, &graph)?;
+
+    // DNSKEY removal
+    leaf_nameserver(&mut graph).remove_key(old_zsk)?;
+    fixtures::assert_validates(&network, &graph)?;
+
+    Ok(())
+}
+
+// section 4.1.2: "Key Signing Key Rollovers"; double-signature scheme
+#[ignore]
+#[test]
+fn ksk_double_signature_rollover() -> Result<()> {
+    let (network, mut graph) = fixture()?;
+    fixtures::assert_validates(&network, &graph)?;
+
+    let leaf_ns = leaf_nameserver(&mut graph);
+    let old_ksk = key_tag(leaf_ns, KeyRole::KeySigning)?;
+
+    // new DNSKEY
+    let new_ksk = leaf_ns.add_key(
+        KeyRole::KeySigning,
+        Algorithm::RSASHA1_NSEC3_SHA1,
+        KeyState::Active,
+    )?;
+    fixtures::assert_validates(&network, &graph)?;
+
+    // DS change
+    let ds = new_ksk.ds(SignSettings::default().ds_digest)?;
+    graph.replace_ds(&FQDN::NAMESERVERS, vec![ds])?;
+    fixtures::assert_validates(&network, &graph)?;
+
+    // DNSKEY removal
+    leaf_nameserver(&mut graph).remove_key(old_ksk)?;
+    fixtures::assert_validates(&network, &graph)?;
+
+    Ok(())
+}
+
+// section 4.1.4: "Algorithm Rollovers"; the new RRSIGs and DNSKEYs are added in a single step and
+// so are the old DNSKEYs and RRSIGs removed
+#[ignore]
+#[test]
+fn algorithm_rollover() -> Result<()> {
+    let (network, mut graph) = fixture()?;
+    fixtures::assert_validates(&network, &graph)?;
+
+    let leaf_ns = leaf_nameserver(&mut graph);
+    let old_keys = [
+        key_tag(leaf_ns, KeyRole::KeySigning)?,
+        key_tag(leaf_ns, KeyRole::ZoneSigning)?,
+    ];
+
+    // new RRSIGs and new DNSKEY
+    let new_ksk = leaf_ns.add_key(
+        KeyRole::KeySigning,
+        Algorithm::ECDSAP256SHA256,
+        KeyState::Active,
+    )?;
+    leaf_ns.add_key(
+        KeyRole::ZoneSigning,
+        Algorithm::ECDSAP256SHA256,
+        KeyState::Active,
+    )?;
+    fixtures::assert_validates(&network, &graph)?;
+
+    // new DS
+    let ds = new_ksk.ds(SignSettings::default().ds_digest)?;
+    graph.replace_ds(&FQDN::NAMESERVERS, vec![ds])?;
+    fixtures::assert_validates(&network, &graph)?;
+
+    // DNSKEY and RRSIGs removal
+    let leaf_ns = leaf_nameserver(&mut graph);
+    for key_tag in old_keys {
+        leaf_ns.remove_key(key_tag)?;
+    }
+    fixtures::assert_validates(&network, &graph)?;
+
+    Ok(())
+}
+
+fn fixture() -> Result<(Network, Graph)> {
+    let network = Network::new()?;
+    let graph = fixtures::needle_graph(&network, SignSettings::default())?;
+
+    Ok((network, graph))
+}
+
+fn leaf_nameserver(graph: &mut Graph) -> &mut NameServer<Running> {
+    graph.nameservers.get_mut(&FQDN::NAMESERVERS).unwrap()
+}
+
+fn key_tag(nameserver: &NameServer<Running>, role: KeyRole) -> Result<u16> {
+    let is_ksk = role == KeyRole::KeySigning;
+    let key = nameserver
+        .keys()
+        .iter()
+        .find(|key| key.dnskey().is_key_signing_key() == is_ksk)
+        .unwrap();
+
+    key.dnskey().key_tag()
+}
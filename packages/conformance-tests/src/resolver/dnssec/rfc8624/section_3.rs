@@ -0,0 +1,110 @@
+use dns_test::name_server::SignSettings;
+use dns_test::record::{Algorithm, DigestType};
+use dns_test::{Network, Resolver, Result};
+
+use crate::resolver::dnssec::fixtures;
+
+// section 3.1: "DNSSEC Validation" column of the DNSKEY algorithms table
+
+#[ignore]
+#[test]
+fn validates_rsasha1_nsec3_sha1() -> Result<()> {
+    assert_validates(algorithm(Algorithm::RSASHA1_NSEC3_SHA1))
+}
+
+#[ignore]
+#[test]
+fn validates_rsasha256() -> Result<()> {
+    assert_validates(algorithm(Algorithm::RSASHA256))
+}
+
+#[ignore]
+#[test]
+fn validates_rsasha512() -> Result<()> {
+    assert_validates(algorithm(Algorithm::RSASHA512))
+}
+
+#[ignore]
+#[test]
+fn validates_ecdsap256sha256() -> Result<()> {
+    assert_validates(algorithm(Algorithm::ECDSAP256SHA256))
+}
+
+#[ignore]
+#[test]
+fn validates_ecdsap384sha384() -> Result<()> {
+    assert_validates(algorithm(Algorithm::ECDSAP384SHA384))
+}
+
+#[ignore]
+#[test]
+fn validates_ed25519() -> Result<()> {
+    assert_validates(algorithm(Algorithm::ED25519))
+}
+
+#[ignore]
+#[test]
+fn ed448_is_never_bogus() -> Result<()> {
+    assert_not_bogus(algorithm(Algorithm::ED448))
+}
+
+// section 3.3: "DNSSEC Validation" column of the DS digest types table
+
+#[ignore]
+#[test]
+fn validates_sha1_ds_digest() -> Result<()> {
+    assert_validates(ds_digest(DigestType::SHA1))
+}
+
+#[ignore]
+#[test]
+fn validates_sha256_ds_digest() -> Result<()> {
+    assert_validates(ds_digest(DigestType::SHA256))
+}
+
+#[ignore]
+#[test]
+fn sha384_ds_digest_is_never_bogus() -> Result<()> {
+    assert_not_bogus(ds_digest(DigestType::SHA384))
+}
+
+fn algorithm(algorithm: Algorithm) -> SignSettings {
+    SignSettings {
+        algorithm,
+        ..SignSettings::default()
+    }
+}
+
+fn ds_digest(ds_digest: DigestType) -> SignSettings {
+    SignSettings {
+        ds_digest,
+        ..SignSettings::default()
+    }
+}
+
+fn assert_validates(settings: SignSettings) -> Result<()> {
+    let network = Network::new()?;
+    let graph = fixtures::needle_graph(&network, settings)?;
+
+    fixtures::assert_validates(&network, &graph)
+}
+
+// the algorithm is optional ("MAY") for validators: a validator that does not implement it
+// treats the zone as insecure but must never consider it bogus
+fn assert_not_bogus(settings: SignSettings) -> Result<()> {
+    let network = Network::new()?;
+    let graph = fixtures::needle_graph(&network, settings)?;
+
+    let resolver = Resolver::new(&network, graph.root.clone())
+        .trust_anchor(graph.trust_anchor.as_ref().unwrap())
+        .start(&dns_test::SUBJECT)?;
+    let output = fixtures::dig_needle(&resolver)?;
+
+    assert!(output.status.is_noerror());
+
+    let [a] = output.answer.try_into().unwrap();
+    let a = a.try_into_a().unwrap();
+    assert_eq!(fixtures::NEEDLE_IPV4_ADDR, a.ipv4_addr);
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use std::net::Ipv4Addr;
+
+use dns_test::client::{Client, DigOutput, DigSettings, ExtendedDnsError};
+use dns_test::name_server::{Graph, NameServer, Nsec, Sign, SignSettings};
+use dns_test::record::{Record, RecordType};
+use dns_test::{Network, Resolver, Result, FQDN};
+
+// section 3.1: "Best-practice for Zone Publishers"
+
+#[ignore]
+#[test]
+fn validates_nxdomain_with_zero_iterations_and_empty_salt() -> Result<()> {
+    let output = nxdomain_fixture(0, None)?;
+
+    assert!(output.status.is_nxdomain());
+    assert!(output.flags.authenticated_data);
+
+    Ok(())
+}
+
+// publishers SHOULD NOT use a salt but validators still need to handle one
+#[ignore]
+#[test]
+fn validates_nxdomain_with_salt() -> Result<()> {
+    let output = nxdomain_fixture(0, Some("53BCBC5805D2B761"))?;
+
+    assert!(output.status.is_nxdomain());
+    assert!(output.flags.authenticated_data);
+
+    Ok(())
+}
+
+// section 3.2: "Recommendation for Validating Resolvers"
+
+// resolvers MAY return an insecure response or SERVFAIL when the iteration count is above the
+// limit they support. either way the response must not be marked as authenticated
+#[ignore]
+#[test]
+fn excessive_iterations_are_not_authenticated() -> Result<()> {
+    let output = nxdomain_fixture(500, None)?;
+
+    assert!(output.status.is_nxdomain() || output.status.is_servfail());
+    assert!(!output.flags.authenticated_data);
+
+    if dns_test::SUBJECT.supports_ede() {
+        assert_eq!(
+            Some(ExtendedDnsError::UnsupportedNsec3IterationsValue),
+            output.ede
+        );
+    }
+
+    Ok(())
+}
+
+// signs all the zones of a minimal DNS graph using NSEC3 records, without opt-out, and the given
+// NSEC3 parameters. then queries a name that does not exist in the leaf zone
+fn nxdomain_fixture(iterations: u16, salt: Option<&str>) -> Result<DigOutput> {
+    let network = Network::new()?;
+
+    let mut leaf_ns = NameServer::new(&dns_test::PEER, FQDN::NAMESERVERS, &network)?;
+    leaf_ns.add(Record::a(
+        FQDN("example.nameservers.com.")?,
+        Ipv4Addr::new(1, 2, 3, 4),
+    ));
+
+    let settings = SignSettings {
+        nsec: Nsec::_3 {
+            iterations,
+            opt_out: false,
+            salt: salt.map(String::from),
+        },
+        ..SignSettings::default()
+    };
+    let Graph {
+        nameservers: _nameservers,
+        root,
+        trust_anchor,
+    } = Graph::build(leaf_ns, Sign::Yes { settings })?;
+
+    let subject = &dns_test::SUBJECT;
+    let mut resolver = Resolver::new(&network, root);
+    if subject.supports_ede() {
+        resolver.extended_dns_errors();
+    }
+
+    let trust_anchor = &trust_anchor.unwrap();
+    let resolver = resolver.trust_anchor(trust_anchor).start(subject)?;
+
+    let client = Client::new(&network)?;
+    let settings = *DigSettings::default().recurse().authentic_data();
+    let needle_fqdn = FQDN("unicorn.nameservers.com.")?;
+    client.dig(settings, resolver.ipv4_addr(), RecordType::A, &needle_fqdn)
+}
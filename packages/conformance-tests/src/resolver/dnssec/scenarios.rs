@@ -1,3 +1,4 @@
 mod bogus;
 mod ede;
+mod insecure;
 mod secure;
@@ -1,7 +1,8 @@
 use std::net::Ipv4Addr;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use dns_test::client::{Client, DigSettings, ExtendedDnsError};
-use dns_test::name_server::{Graph, NameServer, Sign};
+use dns_test::name_server::{Graph, NameServer, Sign, SignSettings};
 use dns_test::record::{Record, RecordType};
 use dns_test::{Network, Resolver, Result, FQDN};
 
@@ -114,6 +115,38 @@ fn dnssec_bogus() -> Result<()> {
     )
 }
 
+#[ignore]
+#[test]
+fn signature_expired() -> Result<()> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    fixture_with_settings(
+        ExtendedDnsError::SignatureExpired,
+        SignSettings {
+            inception: Some(now - 2 * DAY),
+            expiration: Some(now - DAY),
+            ..SignSettings::default()
+        },
+        |_needle_fqdn, _zone, _records| {},
+    )
+}
+
+#[ignore]
+#[test]
+fn signature_not_yet_valid() -> Result<()> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    fixture_with_settings(
+        ExtendedDnsError::SignatureNotYetValid,
+        SignSettings {
+            inception: Some(now + DAY),
+            expiration: Some(now + 2 * DAY),
+            ..SignSettings::default()
+        },
+        |_needle_fqdn, _zone, _records| {},
+    )
+}
+
+const DAY: u64 = 24 * 60 * 60;
+
 // Sets up a minimal, DNSSEC-enabled DNS graph where the leaf zone contains a "needle" A record
 // that we'll search for
 //
@@ -124,6 +157,15 @@ fn dnssec_bogus() -> Result<()> {
 fn fixture(
     expected: ExtendedDnsError,
     amend: fn(needle_fqdn: &FQDN, zone: &FQDN, records: &mut Vec<Record>),
+) -> Result<()> {
+    fixture_with_settings(expected, SignSettings::default(), amend)
+}
+
+// like `fixture` but all zones are signed with the given `settings`
+fn fixture_with_settings(
+    expected: ExtendedDnsError,
+    settings: SignSettings,
+    amend: fn(needle_fqdn: &FQDN, zone: &FQDN, records: &mut Vec<Record>),
 ) -> Result<()> {
     let subject = &dns_test::SUBJECT;
     let supports_ede = subject.supports_ede();
@@ -141,9 +183,12 @@ fn fixture(
         trust_anchor,
     } = Graph::build(
         leaf_ns,
-        Sign::AndAmend(&|zone, records| {
-            amend(&needle_fqdn, zone, records);
-        }),
+        Sign::AndAmend {
+            settings,
+            mutate: &|zone, records| {
+                amend(&needle_fqdn, zone, records);
+            },
+        },
     )?;
 
     let mut resolver = Resolver::new(&network, root);
@@ -0,0 +1,92 @@
+use std::net::Ipv4Addr;
+
+use dns_test::client::{Client, DigOutput, DigSettings};
+use dns_test::name_server::{Graph, Sign, SignSettings, Signing, ZoneSpec};
+use dns_test::record::{Record, RecordType};
+use dns_test::{Network, Resolver, Result, FQDN};
+
+// RFC4035 section 5.2: the parent proves that there's no DS record for the child zone
+#[test]
+fn unsigned_child_under_signed_parent() -> Result<()> {
+    let output = fixture(Signing::Signed, Signing::Unsigned)?;
+
+    assert!(output.status.is_noerror());
+    assert!(!output.flags.authenticated_data);
+
+    let [a] = output.answer.try_into().unwrap();
+    assert!(matches!(a, Record::A(..)));
+
+    Ok(())
+}
+
+// no DS record in the parent so the child's signatures cannot be authenticated
+#[test]
+fn signed_child_without_ds_under_signed_parent() -> Result<()> {
+    let output = fixture(Signing::Signed, Signing::SignedWithoutDs)?;
+
+    assert!(output.status.is_noerror());
+    assert!(!output.flags.authenticated_data);
+
+    let [a] = output.answer.try_into().unwrap();
+    assert!(matches!(a, Record::A(..)));
+
+    Ok(())
+}
+
+// the child zone is an "island of security"; its keys are in the resolver's trust anchor
+#[ignore]
+#[test]
+fn signed_child_under_unsigned_parent() -> Result<()> {
+    let output = fixture(Signing::Unsigned, Signing::Signed)?;
+
+    assert!(output.status.is_noerror());
+    assert!(output.flags.authenticated_data);
+
+    let [a] = output.answer.try_into().unwrap();
+    assert!(matches!(a, Record::A(..)));
+
+    Ok(())
+}
+
+// Sets up a DNS graph where the `com.` zone is signed according to `parent` and the
+// `example.com.` zone is signed according to `child`. all other zones are signed
+//
+// returns the output of querying a validating resolver for a "needle" A record in the child zone
+fn fixture(parent: Signing, child: Signing) -> Result<DigOutput> {
+    let expected_ipv4_addr = Ipv4Addr::new(1, 2, 3, 4);
+    let needle_fqdn = FQDN("needle.example.com.")?;
+
+    let network = Network::new()?;
+
+    let mut leaf = ZoneSpec::new(needle_fqdn.parent().unwrap());
+    leaf.add(Record::a(needle_fqdn.clone(), expected_ipv4_addr))
+        .signing(child);
+    let mut com = ZoneSpec::new(FQDN::COM);
+    com.child(leaf).signing(parent);
+    let mut root = ZoneSpec::new(FQDN::ROOT);
+    root.child(com);
+
+    let Graph {
+        nameservers: _nameservers,
+        root,
+        trust_anchor,
+    } = Graph::from_spec(
+        root,
+        &dns_test::PEER,
+        &network,
+        Sign::Yes {
+            settings: SignSettings::default(),
+        },
+    )?;
+
+    let trust_anchor = trust_anchor.unwrap();
+    let resolver = Resolver::new(&network, root)
+        .trust_anchor(&trust_anchor)
+        .start(&dns_test::SUBJECT)?;
+
+    let client = Client::new(&network)?;
+    let settings = *DigSettings::default().recurse().authentic_data();
+    let output = client.dig(settings, resolver.ipv4_addr(), RecordType::A, &needle_fqdn)?;
+
+    Ok(output)
+}
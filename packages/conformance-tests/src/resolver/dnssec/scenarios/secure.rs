@@ -1,9 +1,11 @@
 use std::net::Ipv4Addr;
 
-use dns_test::client::{Client, DigSettings};
-use dns_test::name_server::NameServer;
-use dns_test::record::RecordType;
-use dns_test::{Network, Resolver, Result, TrustAnchor, FQDN};
+use dns_test::client::{Client, DigOutput, DigSettings};
+use dns_test::name_server::{
+    Graph, KeyScheme, NameServer, Nsec, Sign, SignSettings, SigningTool, ZoneSpec,
+};
+use dns_test::record::{Algorithm, Record, RecordType};
+use dns_test::{Implementation, Network, Resolver, Result, TrustAnchor, FQDN};
 
 use crate::resolver::dnssec::fixtures;
 
@@ -14,7 +16,7 @@ fn can_validate_without_delegation() -> Result<()> {
     let network = Network::new()?;
     let mut ns = NameServer::new(&dns_test::PEER, FQDN::ROOT, &network)?;
     ns.add(ns.a());
-    let ns = ns.sign()?;
+    let ns = ns.sign(SignSettings::default())?;
 
     let root_ksk = ns.key_signing_key().clone();
     let root_zsk = ns.zone_signing_key().clone();
@@ -75,5 +77,187 @@ fn can_validate_with_delegation() -> Result<()> {
     Ok(())
 }
 
-// TODO nxdomain with NSEC records
-// TODO nxdomain with NSEC3 records
+// zones are signed by the in-process signer instead of `ldns-signzone`
+#[ignore]
+#[test]
+fn can_validate_zones_signed_in_process() -> Result<()> {
+    let network = Network::new()?;
+    let graph = fixtures::needle_graph(
+        &network,
+        SignSettings {
+            algorithm: Algorithm::ECDSAP256SHA256,
+            tool: SigningTool::InProcess,
+            ..SignSettings::default()
+        },
+    )?;
+
+    fixtures::assert_validates(&network, &graph)
+}
+
+// every zone is signed with a single combined signing key (CSK)
+#[ignore]
+#[test]
+fn can_validate_zones_signed_with_combined_key() -> Result<()> {
+    for tool in [SigningTool::Ldns, SigningTool::InProcess] {
+        let network = Network::new()?;
+        let graph = fixtures::needle_graph(
+            &network,
+            SignSettings {
+                algorithm: Algorithm::ECDSAP256SHA256,
+                tool,
+                key_scheme: KeyScheme::Combined,
+                ..SignSettings::default()
+            },
+        )?;
+
+        fixtures::assert_validates(&network, &graph)?;
+    }
+
+    Ok(())
+}
+
+// each zone is served by a different name server implementation
+#[ignore]
+#[test]
+fn can_validate_with_mixed_name_server_implementations() -> Result<()> {
+    let network = Network::new()?;
+
+    let mut nameservers = ZoneSpec::new(FQDN::NAMESERVERS);
+    nameservers
+        .add(Record::a(
+            fixtures::needle_fqdn(),
+            fixtures::NEEDLE_IPV4_ADDR,
+        ))
+        .implementation(dns_test::PEER.clone());
+    let mut com = ZoneSpec::new(FQDN::COM);
+    com.child(nameservers).implementation(Implementation::Bind);
+    let mut root = ZoneSpec::new(FQDN::ROOT);
+    root.child(com).implementation(Implementation::Unbound);
+
+    let graph = Graph::from_spec(
+        root,
+        &dns_test::PEER,
+        &network,
+        Sign::Yes {
+            settings: SignSettings::default(),
+        },
+    )?;
+
+    fixtures::assert_validates(&network, &graph)
+}
+
+#[ignore]
+#[test]
+fn nxdomain_with_nsec() -> Result<()> {
+    let output = denial_of_existence_fixture(Nsec::_1, RecordType::A, "unicorn")?;
+
+    assert!(output.status.is_nxdomain());
+    assert!(output.flags.authenticated_data);
+    assert!(output.answer.is_empty());
+    assert!(output
+        .authority
+        .iter()
+        .any(|record| matches!(record, Record::NSEC(..))));
+
+    Ok(())
+}
+
+#[ignore]
+#[test]
+fn nxdomain_with_nsec3() -> Result<()> {
+    let output = denial_of_existence_fixture(nsec3(), RecordType::A, "unicorn")?;
+
+    assert!(output.status.is_nxdomain());
+    assert!(output.flags.authenticated_data);
+    assert!(output.answer.is_empty());
+    assert!(output
+        .authority
+        .iter()
+        .any(|record| matches!(record, Record::NSEC3(..))));
+
+    Ok(())
+}
+
+#[ignore]
+#[test]
+fn nodata_with_nsec() -> Result<()> {
+    let output = denial_of_existence_fixture(Nsec::_1, RecordType::MX, "example")?;
+
+    assert!(output.status.is_noerror());
+    assert!(output.flags.authenticated_data);
+    assert!(output.answer.is_empty());
+    assert!(output
+        .authority
+        .iter()
+        .any(|record| matches!(record, Record::NSEC(..))));
+
+    Ok(())
+}
+
+#[ignore]
+#[test]
+fn nodata_with_nsec3() -> Result<()> {
+    let output = denial_of_existence_fixture(nsec3(), RecordType::MX, "example")?;
+
+    assert!(output.status.is_noerror());
+    assert!(output.flags.authenticated_data);
+    assert!(output.answer.is_empty());
+    assert!(output
+        .authority
+        .iter()
+        .any(|record| matches!(record, Record::NSEC3(..))));
+
+    Ok(())
+}
+
+// no opt-out: with opt-out set the NXDOMAIN proof cannot rule out an unsigned delegation and
+// validators would treat the response as insecure
+fn nsec3() -> Nsec {
+    Nsec::_3 {
+        iterations: 0,
+        opt_out: false,
+        salt: None,
+    }
+}
+
+// the leaf zone, `nameservers.com.`, only contains an A record for `example.nameservers.com.`
+//
+// queries the `record_type` of `{label}.nameservers.com.` with the DO bit set and returns the
+// response of the resolver
+fn denial_of_existence_fixture(
+    nsec: Nsec,
+    record_type: RecordType,
+    label: &str,
+) -> Result<DigOutput> {
+    let network = Network::new()?;
+
+    let mut leaf_ns = NameServer::new(&dns_test::PEER, FQDN::NAMESERVERS, &network)?;
+    leaf_ns.add(Record::a(
+        FQDN("example.nameservers.com.")?,
+        Ipv4Addr::new(1, 2, 3, 4),
+    ));
+
+    let Graph {
+        nameservers: _nameservers,
+        root,
+        trust_anchor,
+    } = Graph::build(
+        leaf_ns,
+        Sign::Yes {
+            settings: SignSettings {
+                nsec,
+                ..SignSettings::default()
+            },
+        },
+    )?;
+
+    let trust_anchor = trust_anchor.unwrap();
+    let resolver = Resolver::new(&network, root)
+        .trust_anchor(&trust_anchor)
+        .start(&dns_test::SUBJECT)?;
+
+    let client = Client::new(&network)?;
+    let settings = *DigSettings::default().recurse().authentic_data().dnssec();
+    let fqdn = FQDN(format!("{label}.nameservers.com."))?;
+    client.dig(settings, resolver.ipv4_addr(), record_type, &fqdn)
+}
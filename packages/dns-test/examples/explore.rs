@@ -3,9 +3,9 @@ use std::net::Ipv4Addr;
 use std::sync::mpsc;
 
 use dns_test::client::Client;
-use dns_test::name_server::{Graph, NameServer, Sign};
+use dns_test::name_server::{Graph, NameServer, Sign, SignSettings};
 use dns_test::record::RecordType;
-use dns_test::{Network, Resolver, Result, FQDN};
+use dns_test::{Error, Network, Resolver, Result, FQDN};
 
 fn main() -> Result<()> {
     let args = Args::from_env()?;
@@ -18,7 +18,13 @@ fn main() -> Result<()> {
     println!("DONE");
 
     println!("setting up name servers...");
-    let sign = if args.dnssec { Sign::Yes } else { Sign::No };
+    let sign = if args.dnssec {
+        Sign::Yes {
+            settings: SignSettings::default(),
+        }
+    } else {
+        Sign::No
+    };
     let Graph {
         root,
         trust_anchor,
@@ -49,9 +55,10 @@ fn main() -> Result<()> {
 
     let (tx, rx) = mpsc::channel();
 
-    ctrlc::set_handler(move || tx.send(()).expect("could not forward signal"))?;
+    ctrlc::set_handler(move || tx.send(()).expect("could not forward signal"))
+        .map_err(|e| Error::Other(e.to_string()))?;
 
-    for ns in &nameservers {
+    for ns in nameservers.values() {
         println!("{} name server's IP address: {}", ns.zone(), ns.ipv4_addr());
         println!(
             "attach to this container with: `docker exec -it {} bash`\n",
@@ -89,7 +96,7 @@ the resolver container before performing queries:\n"
 
     println!("press Ctrl+C to take down the network");
 
-    rx.recv()?;
+    rx.recv().map_err(|e| Error::Other(e.to_string()))?;
 
     println!("\ntaking down network...");
 
@@ -128,5 +135,5 @@ Options:
   --dnssec      sign zone files to enable DNSSEC"
     );
 
-    Err("CLI error".into())
+    Err(Error::InvalidInput("CLI error".to_string()))
 }
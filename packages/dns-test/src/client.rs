@@ -1,9 +1,12 @@
 use core::str::FromStr;
 use std::net::Ipv4Addr;
 
-use crate::container::{Container, Image, Network};
-use crate::record::{Record, RecordType};
+use crate::container::{Clock, Container, Image, Network};
+use crate::record::{Record, RecordType, SOA};
+use crate::rrset::RRset;
 use crate::trust_anchor::TrustAnchor;
+use crate::tsig::TsigKey;
+use crate::zone_file::ZoneFile;
 use crate::{Error, Result, FQDN};
 
 pub struct Client {
@@ -25,6 +28,11 @@ impl Client {
         self.inner.ipv4_addr()
     }
 
+    /// Fakes the clock `delv` uses to validate signatures
+    pub fn set_clock(&self, clock: Clock) -> Result<()> {
+        self.inner.set_clock(clock)
+    }
+
     pub fn delv(
         &self,
         server: Ipv4Addr,
@@ -47,7 +55,7 @@ impl Client {
             "-a",
             TRUST_ANCHOR_PATH,
             fqdn.as_str(),
-            record_type.as_str(),
+            &record_type.to_string(),
         ])
     }
 
@@ -58,30 +66,347 @@ impl Client {
         record_type: RecordType,
         fqdn: &FQDN,
     ) -> Result<DigOutput> {
-        let output = self.inner.stdout(&[
+        let server = format!("@{server}");
+        let record_type = record_type.to_string();
+        let tsig = settings.tsig_arg();
+        let mut args = vec![
             "dig",
             settings.rdflag(),
             settings.do_bit(),
             settings.adflag(),
             settings.cdflag(),
-            &format!("@{server}"),
-            record_type.as_str(),
-            fqdn.as_str(),
-        ])?;
+            settings.unknownformat(),
+        ];
+        if let Some(tsig) = &tsig {
+            args.extend(["-y", tsig]);
+        }
+        args.extend([server.as_str(), record_type.as_str(), fqdn.as_str()]);
+
+        let output = self.inner.stdout(&args)?;
 
         output.parse()
     }
+
+    /// Transfers the whole `zone` from `server` (AXFR)
+    pub fn axfr(&self, server: Ipv4Addr, zone: &FQDN) -> Result<ZoneFile> {
+        let output = self.xfr(&["dig", &format!("@{server}"), "AXFR", zone.as_str()])?;
+
+        full_transfer(xfr_records(&output)?)
+    }
+
+    /// Transfers the changes made to `zone` since the version with the given SOA `serial`
+    /// (IXFR); `server` may respond with the whole zone instead
+    pub fn ixfr(&self, server: Ipv4Addr, zone: &FQDN, serial: u32) -> Result<IxfrOutput> {
+        self.xfr(&[
+            "dig",
+            &format!("@{server}"),
+            &format!("IXFR={serial}"),
+            zone.as_str(),
+        ])?
+        .parse()
+    }
+
+    // `dig` exits with a zero status when the transfer fails
+    fn xfr(&self, command_and_args: &[&str]) -> Result<String> {
+        let stdout = self.inner.stdout(command_and_args)?;
+
+        if stdout.lines().any(is_transfer_failure) {
+            return Err(Error::ZoneTransferFailed {
+                container: self.inner.name().to_string(),
+                command: command_and_args.iter().map(|arg| arg.to_string()).collect(),
+                stdout,
+            });
+        }
+
+        Ok(stdout)
+    }
+
+    /// Sends a dynamic `update` (RFC2136) to `server` using `nsupdate`
+    ///
+    /// A response with an error status is not an `Err`; it's reported in `UpdateOutput`
+    pub fn nsupdate(&self, server: Ipv4Addr, update: &Update) -> Result<UpdateOutput> {
+        const SCRIPT_PATH: &str = "/tmp/nsupdate.txt";
+
+        let command = ["nsupdate", SCRIPT_PATH];
+        self.inner.cp(SCRIPT_PATH, &update.script(server))?;
+        let output = self.inner.output(&command)?;
+
+        if output.status.success() {
+            return Ok(UpdateOutput {
+                status: DigStatus::NOERROR,
+            });
+        }
+
+        // `docker exec -t` may merge stderr into stdout
+        let status = output
+            .stdout
+            .lines()
+            .chain(output.stderr.lines())
+            .find_map(|line| line.strip_prefix("update failed: "))
+            .map(|status| status.trim().parse());
+
+        match status {
+            Some(status) => Ok(UpdateOutput { status: status? }),
+            None => Err(self.inner.command_failed(&command, output)),
+        }
+    }
+}
+
+/// A dynamic update (RFC2136) of a zone
+///
+/// The update is applied only if all its prerequisites hold
+#[derive(Clone, Debug)]
+pub struct Update {
+    zone: FQDN,
+    key: Option<TsigKey>,
+    prerequisites: Vec<String>,
+    updates: Vec<String>,
+}
+
+impl Update {
+    pub fn new(zone: FQDN) -> Self {
+        Self {
+            zone,
+            key: None,
+            prerequisites: vec![],
+            updates: vec![],
+        }
+    }
+
+    /// Signs the update message with `key` (TSIG)
+    pub fn key(&mut self, key: TsigKey) -> &mut Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn prerequisite(&mut self, prerequisite: Prerequisite) -> &mut Self {
+        let command = match prerequisite {
+            Prerequisite::NameInUse(fqdn) => format!("yxdomain {fqdn}"),
+            Prerequisite::NameNotInUse(fqdn) => format!("nxdomain {fqdn}"),
+            Prerequisite::RRsetExists(fqdn, record_type) => format!("yxrrset {fqdn} {record_type}"),
+            Prerequisite::RRsetDoesNotExist(fqdn, record_type) => {
+                format!("nxrrset {fqdn} {record_type}")
+            }
+            Prerequisite::RRsetEquals(record) => format!("yxrrset {record}"),
+        };
+        self.prerequisites.push(command);
+        self
+    }
+
+    /// Adds `record` to its RRset
+    pub fn add(&mut self, record: impl Into<Record>) -> &mut Self {
+        self.updates.push(format!("add {}", record.into()));
+        self
+    }
+
+    /// Deletes `record` from its RRset
+    pub fn delete(&mut self, record: impl Into<Record>) -> &mut Self {
+        self.updates.push(format!("delete {}", record.into()));
+        self
+    }
+
+    /// Deletes the RRset of type `record_type` owned by `fqdn`
+    pub fn delete_rrset(&mut self, fqdn: &FQDN, record_type: RecordType) -> &mut Self {
+        self.updates.push(format!("delete {fqdn} {record_type}"));
+        self
+    }
+
+    /// Deletes all the RRsets owned by `fqdn`
+    pub fn delete_name(&mut self, fqdn: &FQDN) -> &mut Self {
+        self.updates.push(format!("delete {fqdn}"));
+        self
+    }
+
+    /// Input for `nsupdate`
+    fn script(&self, server: Ipv4Addr) -> String {
+        let mut script = format!("server {server}\nzone {}\n", self.zone);
+        if let Some(key) = &self.key {
+            script.push_str(&format!(
+                "key {}:{} {}\n",
+                key.algorithm,
+                key.name,
+                key.secret()
+            ));
+        }
+        for prerequisite in &self.prerequisites {
+            script.push_str(&format!("prereq {prerequisite}\n"));
+        }
+        for update in &self.updates {
+            script.push_str(&format!("update {update}\n"));
+        }
+        script.push_str("send\n");
+        script
+    }
+}
+
+/// Prerequisite of a dynamic update (section 2.4 of RFC2136)
+#[derive(Clone, Debug)]
+pub enum Prerequisite {
+    NameInUse(FQDN),
+    NameNotInUse(FQDN),
+    RRsetExists(FQDN, RecordType),
+    RRsetDoesNotExist(FQDN, RecordType),
+    /// The RRset that contains this record exists and contains exactly the records given in the
+    /// update's `RRsetEquals` prerequisites
+    RRsetEquals(Record),
+}
+
+/// Response to a dynamic update
+#[derive(Debug)]
+pub struct UpdateOutput {
+    pub status: DigStatus,
+}
+
+/// Response to an IXFR query
+#[derive(Debug)]
+pub enum IxfrOutput {
+    /// The whole zone, as in a response to an AXFR query
+    Full(ZoneFile),
+    /// The differences between the version of the zone the client has and the current one; it's
+    /// empty if the client's version is the current one
+    Incremental(Vec<ZoneDiff>),
+}
+
+/// The changes between two consecutive versions of a zone (section 4 of RFC1995)
+#[derive(Debug)]
+pub struct ZoneDiff {
+    pub old_soa: SOA,
+    pub deleted: Vec<Record>,
+    pub new_soa: SOA,
+    pub added: Vec<Record>,
+}
+
+impl FromStr for IxfrOutput {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let records = xfr_records(input)?;
+
+        // an AXFR-style response starts with the SOA record followed by the rest of the zone's
+        // records; an incremental one starts with the current SOA record followed by the old one
+        let current = match records.as_slice() {
+            [Record::SOA(_)] => return Ok(Self::Incremental(vec![])),
+            [Record::SOA(current), Record::SOA(old), ..]
+                if old.settings.serial != current.settings.serial =>
+            {
+                current
+            }
+            _ => return Ok(Self::Full(full_transfer(records)?)),
+        };
+
+        // SOA(old) deleted.. SOA(new) added.. repeated, then SOA(current) once more
+        let mut diffs = vec![];
+        let mut records = records[1..].iter().peekable();
+        loop {
+            let old_soa = match records.next() {
+                Some(Record::SOA(soa)) if soa.settings.serial == current.settings.serial => {
+                    if records.next().is_some() {
+                        return Err(Error::parse(
+                            "unexpected records after the closing SOA record",
+                        ));
+                    }
+                    break;
+                }
+                Some(Record::SOA(soa)) => soa.clone(),
+                _ => {
+                    return Err(Error::parse(
+                        "IXFR response is missing its closing SOA record",
+                    ))
+                }
+            };
+
+            let mut deleted = vec![];
+            while let Some(record) = records.next_if(|record| !record.is_soa()) {
+                deleted.push(record.clone());
+            }
+
+            let Some(Record::SOA(new_soa)) = records.next() else {
+                return Err(Error::parse(
+                    "IXFR difference sequence is missing its new SOA record",
+                ));
+            };
+
+            let mut added = vec![];
+            while let Some(record) = records.next_if(|record| !record.is_soa()) {
+                added.push(record.clone());
+            }
+
+            diffs.push(ZoneDiff {
+                old_soa,
+                deleted,
+                new_soa: new_soa.clone(),
+                added,
+            });
+        }
+
+        Ok(Self::Incremental(diffs))
+    }
+}
+
+fn is_transfer_failure(line: &str) -> bool {
+    line.starts_with("; Transfer failed")
+}
+
+/// The records in the output of a `dig` zone transfer query
+fn xfr_records(input: &str) -> Result<Vec<Record>> {
+    let mut records = vec![];
+    for (index, line) in input.lines().enumerate() {
+        if is_transfer_failure(line) {
+            return Err(Error::parse("the zone transfer failed").at_line(index + 1, line));
+        }
+
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+
+        records.push(
+            line.parse()
+                .map_err(|e: Error| e.at_line(index + 1, line))?,
+        );
+    }
+
+    Ok(records)
+}
+
+/// The zone file in an AXFR-style transfer: the records of the zone between two copies of its SOA
+/// record
+fn full_transfer(mut records: Vec<Record>) -> Result<ZoneFile> {
+    let Some(Record::SOA(closing)) = records.pop() else {
+        return Err(Error::parse("zone transfer does not end with a SOA record"));
+    };
+
+    let mut records = records.into_iter();
+    let Some(Record::SOA(soa)) = records.next() else {
+        return Err(Error::parse(
+            "zone transfer does not start with a SOA record",
+        ));
+    };
+
+    if soa.settings.serial != closing.settings.serial {
+        return Err(Error::parse(
+            "the SOA records at the start and end of the zone transfer differ",
+        ));
+    }
+
+    let mut zone_file = ZoneFile::new(soa);
+    for record in records {
+        zone_file.add(record);
+    }
+
+    Ok(zone_file)
 }
 
 #[derive(Clone, Copy, Default)]
-pub struct DigSettings {
+pub struct DigSettings<'a> {
     adflag: bool,
     cdflag: bool,
     dnssec: bool,
     recurse: bool,
+    tsig: Option<&'a TsigKey>,
+    unknownformat: bool,
 }
 
-impl DigSettings {
+impl<'a> DigSettings<'a> {
     /// Sets the AD bit in the query
     pub fn authentic_data(&mut self) -> &mut Self {
         self.adflag = true;
@@ -137,6 +462,32 @@ impl DigSettings {
             "+norecurse"
         }
     }
+
+    /// Signs the query with `key` (TSIG) and verifies the signature of the response
+    pub fn tsig(&mut self, key: &'a TsigKey) -> &mut Self {
+        self.tsig = Some(key);
+        self
+    }
+
+    fn tsig_arg(&self) -> Option<String> {
+        self.tsig
+            .map(|key| format!("{}:{}:{}", key.algorithm, key.name, key.secret()))
+    }
+
+    /// Prints the RDATA of all records in the generic format of RFC3597; they'll be parsed as
+    /// `Record::Unknown`
+    pub fn unknown_format(&mut self) -> &mut Self {
+        self.unknownformat = true;
+        self
+    }
+
+    fn unknownformat(&self) -> &'static str {
+        if self.unknownformat {
+            "+unknownformat"
+        } else {
+            "+nounknownformat"
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -146,9 +497,24 @@ pub struct DigOutput {
     pub status: DigStatus,
     pub answer: Vec<Record>,
     pub authority: Vec<Record>,
+    /// The TSIG processing of the response; `None` if the query was not signed and the response
+    /// carried no TSIG record
+    pub tsig: Option<TsigStatus>,
     // TODO(if needed) other sections
 }
 
+impl DigOutput {
+    /// The answer section grouped into RRsets, in canonical order
+    pub fn answer_rrsets(&self) -> Result<Vec<RRset>> {
+        RRset::group(self.answer.iter().cloned())
+    }
+
+    /// The authority section grouped into RRsets, in canonical order
+    pub fn authority_rrsets(&self) -> Result<Vec<RRset>> {
+        RRset::group(self.authority.iter().cloned())
+    }
+}
+
 impl FromStr for DigOutput {
     type Err = Error;
 
@@ -158,17 +524,19 @@ impl FromStr for DigOutput {
         const EDE_PREFIX: &str = "; EDE: ";
         const ANSWER_HEADER: &str = ";; ANSWER SECTION:";
         const AUTHORITY_HEADER: &str = ";; AUTHORITY SECTION:";
+        const TSIG_HEADER: &str = ";; TSIG PSEUDOSECTION:";
+        const TSIG_FAILURE_PREFIX: &str = ";; Couldn't verify signature: ";
 
-        fn not_found(prefix: &str) -> String {
-            format!("`{prefix}` line was not found")
+        fn not_found(prefix: &str) -> Error {
+            Error::parse(format!("`{prefix}` line was not found"))
         }
 
-        fn more_than_once(prefix: &str) -> String {
-            format!("`{prefix}` line was found more than once")
+        fn more_than_once(prefix: &str) -> Error {
+            Error::parse(format!("`{prefix}` line was found more than once"))
         }
 
-        fn missing(prefix: &str, delimiter: &str) -> String {
-            format!("`{prefix}` line is missing a {delimiter}")
+        fn missing(prefix: &str, delimiter: &str) -> Error {
+            Error::parse(format!("`{prefix}` line is missing a {delimiter}"))
         }
 
         let mut flags = None;
@@ -176,16 +544,18 @@ impl FromStr for DigOutput {
         let mut answer = None;
         let mut authority = None;
         let mut ede = None;
+        let mut tsig_error = None;
+        let mut tsig_failure = false;
 
-        let mut lines = input.lines();
-        while let Some(line) = lines.next() {
+        let mut lines = input.lines().enumerate();
+        while let Some((_, line)) = lines.next() {
             if let Some(unprefixed) = line.strip_prefix(FLAGS_PREFIX) {
                 let (flags_text, _rest) = unprefixed
                     .split_once(';')
                     .ok_or_else(|| missing(FLAGS_PREFIX, "semicolon (;)"))?;
 
                 if flags.is_some() {
-                    return Err(more_than_once(FLAGS_PREFIX).into());
+                    return Err(more_than_once(FLAGS_PREFIX));
                 }
 
                 flags = Some(flags_text.parse()?);
@@ -195,7 +565,7 @@ impl FromStr for DigOutput {
                     .ok_or_else(|| missing(STATUS_PREFIX, "comma (,)"))?;
 
                 if status.is_some() {
-                    return Err(more_than_once(STATUS_PREFIX).into());
+                    return Err(more_than_once(STATUS_PREFIX));
                 }
 
                 status = Some(status_text.parse()?);
@@ -206,59 +576,155 @@ impl FromStr for DigOutput {
                     .unwrap_or(unprefixed);
 
                 if ede.is_some() {
-                    return Err(more_than_once(EDE_PREFIX).into());
+                    return Err(more_than_once(EDE_PREFIX));
                 }
 
                 ede = Some(code.parse()?);
             } else if line.starts_with(ANSWER_HEADER) {
                 if answer.is_some() {
-                    return Err(more_than_once(ANSWER_HEADER).into());
+                    return Err(more_than_once(ANSWER_HEADER));
                 }
 
                 let mut records = vec![];
-                for line in lines.by_ref() {
+                for (index, line) in lines.by_ref() {
                     if line.is_empty() {
                         break;
                     }
 
-                    records.push(line.parse()?);
+                    records.push(
+                        line.parse()
+                            .map_err(|e: Error| e.at_line(index + 1, line))?,
+                    );
                 }
 
                 answer = Some(records);
             } else if line.starts_with(AUTHORITY_HEADER) {
                 if authority.is_some() {
-                    return Err(more_than_once(AUTHORITY_HEADER).into());
+                    return Err(more_than_once(AUTHORITY_HEADER));
                 }
 
                 let mut records = vec![];
-                for line in lines.by_ref() {
+                for (index, line) in lines.by_ref() {
                     if line.is_empty() {
                         break;
                     }
 
-                    records.push(line.parse()?);
+                    records.push(
+                        line.parse()
+                            .map_err(|e: Error| e.at_line(index + 1, line))?,
+                    );
                 }
 
                 authority = Some(records);
+            } else if line.starts_with(TSIG_HEADER) {
+                if tsig_error.is_some() {
+                    return Err(more_than_once(TSIG_HEADER));
+                }
+
+                let (_, record) = lines.next().ok_or_else(|| {
+                    Error::parse(format!("`{TSIG_HEADER}` is not followed by a record"))
+                })?;
+                tsig_error = Some(tsig_record_error(record)?);
+            } else if line.starts_with(TSIG_FAILURE_PREFIX) {
+                tsig_failure = true;
             }
         }
 
+        let tsig = if tsig_error.is_some() || tsig_failure {
+            Some(TsigStatus {
+                error: tsig_error,
+                verified: !tsig_failure,
+            })
+        } else {
+            None
+        };
+
         Ok(Self {
             answer: answer.unwrap_or_default(),
             authority: authority.unwrap_or_default(),
             ede,
             flags: flags.ok_or_else(|| not_found(FLAGS_PREFIX))?,
             status: status.ok_or_else(|| not_found(STATUS_PREFIX))?,
+            tsig,
         })
     }
 }
 
+/// The TSIG processing of a response (RFC8945)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TsigStatus {
+    /// The error field of the response's TSIG record; `None` if the response was not signed
+    pub error: Option<TsigError>,
+    /// Whether `dig` verified the signature of the response
+    pub verified: bool,
+}
+
+/// The error field of a TSIG record (section 3 of RFC8945)
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TsigError {
+    NOERROR,
+    BADSIG,
+    BADKEY,
+    BADTIME,
+    BADTRUNC,
+}
+
+impl FromStr for TsigError {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let error = match input {
+            "NOERROR" => Self::NOERROR,
+            "BADSIG" => Self::BADSIG,
+            "BADKEY" => Self::BADKEY,
+            "BADTIME" => Self::BADTIME,
+            "BADTRUNC" => Self::BADTRUNC,
+            _ => return Err(Error::parse(format!("unknown TSIG error: {input}"))),
+        };
+
+        Ok(error)
+    }
+}
+
+/// Extracts the error field of a TSIG record in `dig`'s presentation format
+///
+/// The MAC field is omitted from the output when the MAC is empty
+fn tsig_record_error(input: &str) -> Result<TsigError> {
+    let mut columns = input.split_whitespace();
+    let [Some(_name), Some(_ttl), Some(_class), Some("TSIG"), Some(_algorithm), Some(_time), Some(_fudge), Some(mac_size)] = [
+        columns.next(),
+        columns.next(),
+        columns.next(),
+        columns.next(),
+        columns.next(),
+        columns.next(),
+        columns.next(),
+        columns.next(),
+    ] else {
+        return Err(Error::parse(format!("invalid TSIG record: {input}")));
+    };
+
+    if mac_size != "0" {
+        let _mac = columns.next();
+    }
+    let _original_id = columns.next();
+
+    columns
+        .next()
+        .ok_or_else(|| Error::parse(format!("TSIG record is missing the error field: {input}")))?
+        .parse()
+}
+
 #[derive(Debug, PartialEq)]
 pub enum ExtendedDnsError {
     DnskeyMissing,
     DnssecBogus,
     RrsigsMissing,
+    SignatureExpired,
+    SignatureNotYetValid,
     UnsupportedDnskeyAlgorithm,
+    UnsupportedNsec3IterationsValue,
 }
 
 impl FromStr for ExtendedDnsError {
@@ -270,8 +736,11 @@ impl FromStr for ExtendedDnsError {
         let code = match code {
             1 => Self::UnsupportedDnskeyAlgorithm,
             6 => Self::DnssecBogus,
+            7 => Self::SignatureExpired,
+            8 => Self::SignatureNotYetValid,
             9 => Self::DnskeyMissing,
             10 => Self::RrsigsMissing,
+            27 => Self::UnsupportedNsec3IterationsValue,
             _ => todo!("EDE {code} has not yet been implemented"),
         };
 
@@ -308,7 +777,7 @@ impl FromStr for DigFlags {
                 "aa" => authoritative_answer = true,
                 "ad" => authenticated_data = true,
                 "cd" => checking_disabled = true,
-                _ => return Err(format!("unknown flag: {flag}").into()),
+                _ => return Err(Error::parse(format!("unknown flag: {flag}"))),
             }
         }
 
@@ -326,10 +795,17 @@ impl FromStr for DigFlags {
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DigStatus {
+    FORMERR,
     NOERROR,
+    NOTAUTH,
+    NOTIMP,
+    NOTZONE,
     NXDOMAIN,
+    NXRRSET,
     REFUSED,
     SERVFAIL,
+    YXDOMAIN,
+    YXRRSET,
 }
 
 impl DigStatus {
@@ -354,11 +830,18 @@ impl FromStr for DigStatus {
 
     fn from_str(input: &str) -> Result<Self> {
         let status = match input {
-            "NXDOMAIN" => Self::NXDOMAIN,
+            "FORMERR" => Self::FORMERR,
             "NOERROR" => Self::NOERROR,
+            "NOTAUTH" => Self::NOTAUTH,
+            "NOTIMP" => Self::NOTIMP,
+            "NOTZONE" => Self::NOTZONE,
+            "NXDOMAIN" => Self::NXDOMAIN,
+            "NXRRSET" => Self::NXRRSET,
             "REFUSED" => Self::REFUSED,
             "SERVFAIL" => Self::SERVFAIL,
-            _ => return Err(format!("unknown status: {input}").into()),
+            "YXDOMAIN" => Self::YXDOMAIN,
+            "YXRRSET" => Self::YXRRSET,
+            _ => return Err(Error::parse(format!("unknown status: {input}"))),
         };
 
         Ok(status)
@@ -368,6 +851,137 @@ impl FromStr for DigStatus {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::record::UnknownRdata;
+
+    #[test]
+    fn update_script() -> Result<()> {
+        let zone = FQDN("nameservers.com.")?;
+        let fqdn = FQDN("example.nameservers.com.")?;
+        let mut update = Update::new(zone);
+        update
+            .prerequisite(Prerequisite::NameNotInUse(fqdn.clone()))
+            .add(Record::a(fqdn.clone(), Ipv4Addr::new(1, 2, 3, 4)))
+            .delete_rrset(&fqdn, RecordType::TXT);
+
+        let script = update.script(Ipv4Addr::new(172, 18, 0, 2));
+        let lines = script.lines().collect::<Vec<_>>();
+        assert_eq!(
+            [
+                "server 172.18.0.2",
+                "zone nameservers.com.",
+                "prereq nxdomain example.nameservers.com.",
+            ],
+            lines[..3]
+        );
+        assert!(lines[3].starts_with("update add example.nameservers.com."));
+        assert!(lines[3].ends_with("1.2.3.4"));
+        assert_eq!(
+            ["update delete example.nameservers.com. TXT", "send"],
+            lines[4..]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn axfr() -> Result<()> {
+        // $ dig @172.18.0.2 AXFR nameservers.com.
+        let input = "
+; <<>> DiG 9.18.24-1-Debian <<>> @172.18.0.2 AXFR nameservers.com.
+; (1 server found)
+;; global options: +cmd
+nameservers.com.	86400	IN	SOA	primary0.nameservers.com. admin0.nameservers.com. 2024010101 1800 900 604800 86400
+nameservers.com.	86400	IN	NS	primary0.nameservers.com.
+primary0.nameservers.com. 86400	IN	A	172.18.0.2
+nameservers.com.	86400	IN	SOA	primary0.nameservers.com. admin0.nameservers.com. 2024010101 1800 900 604800 86400
+;; Query time: 0 msec
+;; SERVER: 172.18.0.2#53(172.18.0.2) (TCP)
+;; WHEN: Mon Mar 04 10:00:00 UTC 2024
+;; XFR size: 4 records (messages 1, bytes 214)
+";
+
+        let zone_file = full_transfer(xfr_records(input)?)?;
+
+        assert_eq!(FQDN("nameservers.com.")?, zone_file.soa.zone);
+        assert_eq!(2024010101, zone_file.soa.settings.serial);
+        assert_eq!(2, zone_file.records.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn failed_transfer() {
+        let input = "
+; <<>> DiG 9.18.24-1-Debian <<>> @172.18.0.2 AXFR nameservers.com.
+; (1 server found)
+;; global options: +cmd
+; Transfer failed.
+";
+
+        assert!(xfr_records(input).is_err());
+    }
+
+    #[test]
+    fn ixfr() -> Result<()> {
+        // example from section 5 of RFC1995
+        let input = "
+; <<>> DiG 9.18.24-1-Debian <<>> @172.18.0.2 IXFR=1 jain.ad.jp.
+jain.ad.jp.	600	IN	SOA	ns.jain.ad.jp. mail.jain.ad.jp. 3 600 600 3600000 604800
+jain.ad.jp.	600	IN	SOA	ns.jain.ad.jp. mail.jain.ad.jp. 1 600 600 3600000 604800
+nezu.jain.ad.jp. 600	IN	A	133.69.136.5
+jain.ad.jp.	600	IN	SOA	ns.jain.ad.jp. mail.jain.ad.jp. 2 600 600 3600000 604800
+jain-bb.jain.ad.jp. 600	IN	A	133.69.136.4
+jain-bb.jain.ad.jp. 600	IN	A	192.41.197.2
+jain.ad.jp.	600	IN	SOA	ns.jain.ad.jp. mail.jain.ad.jp. 2 600 600 3600000 604800
+jain-bb.jain.ad.jp. 600	IN	A	133.69.136.4
+jain.ad.jp.	600	IN	SOA	ns.jain.ad.jp. mail.jain.ad.jp. 3 600 600 3600000 604800
+jain-bb.jain.ad.jp. 600	IN	A	133.69.136.3
+jain.ad.jp.	600	IN	SOA	ns.jain.ad.jp. mail.jain.ad.jp. 3 600 600 3600000 604800
+";
+
+        let IxfrOutput::Incremental(diffs) = input.parse()? else {
+            panic!("expected an incremental transfer");
+        };
+
+        let [first, second] = diffs.try_into().expect("two difference sequences");
+        assert_eq!(1, first.old_soa.settings.serial);
+        assert_eq!(1, first.deleted.len());
+        assert_eq!(2, first.new_soa.settings.serial);
+        assert_eq!(2, first.added.len());
+        assert_eq!(2, second.old_soa.settings.serial);
+        assert_eq!(1, second.deleted.len());
+        assert_eq!(3, second.new_soa.settings.serial);
+        assert_eq!(1, second.added.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn ixfr_up_to_date() -> Result<()> {
+        let input = "
+jain.ad.jp.	600	IN	SOA	ns.jain.ad.jp. mail.jain.ad.jp. 3 600 600 3600000 604800
+";
+
+        let output: IxfrOutput = input.parse()?;
+        assert!(matches!(output, IxfrOutput::Incremental(diffs) if diffs.is_empty()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn ixfr_fallback_to_full_transfer() -> Result<()> {
+        let input = "
+jain.ad.jp.	600	IN	SOA	ns.jain.ad.jp. mail.jain.ad.jp. 3 600 600 3600000 604800
+jain.ad.jp.	600	IN	NS	ns.jain.ad.jp.
+ns.jain.ad.jp.	600	IN	A	133.69.136.1
+jain.ad.jp.	600	IN	SOA	ns.jain.ad.jp. mail.jain.ad.jp. 3 600 600 3600000 604800
+";
+
+        let output: IxfrOutput = input.parse()?;
+        assert!(matches!(output, IxfrOutput::Full(zone_file) if zone_file.records.len() == 2));
+
+        Ok(())
+    }
 
     #[test]
     fn dig_nxdomain() -> Result<()> {
@@ -440,6 +1054,119 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn unknown_record_type() -> Result<()> {
+        // $ dig TYPE65280 private.example.
+        let input = "
+; <<>> DiG 9.18.24-1-Debian <<>> TYPE65280 private.example.
+;; global options: +cmd
+;; Got answer:
+;; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 1734
+;; flags: qr aa rd; QUERY: 1, ANSWER: 2, AUTHORITY: 0, ADDITIONAL: 1
+
+;; OPT PSEUDOSECTION:
+; EDNS: version: 0, flags:; udp: 1232
+;; QUESTION SECTION:
+;private.example.		IN	TYPE65280
+
+;; ANSWER SECTION:
+private.example.	86400	IN	CNAME	target.example.
+target.example.		86400	IN	TYPE65280 \\# 3 ABCDEF
+
+;; Query time: 0 msec
+;; SERVER: 172.18.0.2#53(172.18.0.2) (UDP)
+;; WHEN: Tue Mar 05 17:45:29 UTC 2024
+;; MSG SIZE  rcvd: 91
+";
+
+        let output: DigOutput = input.parse()?;
+
+        let [cname, unknown] = output.answer.try_into().expect("exactly two records");
+        assert!(matches!(cname, Record::CNAME(..)));
+        assert_eq!(RecordType::Unknown(65280), unknown.record_type());
+        let unknown = unknown.try_into_unknown().expect("not an unknown record");
+        assert_eq!(UnknownRdata::Generic(vec![0xAB, 0xCD, 0xEF]), unknown.rdata);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unmodeled_record_type() -> Result<()> {
+        // $ dig HINFO host.example.
+        let input = "
+; <<>> DiG 9.18.24-1-Debian <<>> HINFO host.example.
+;; global options: +cmd
+;; Got answer:
+;; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 40281
+;; flags: qr aa rd; QUERY: 1, ANSWER: 1, AUTHORITY: 0, ADDITIONAL: 1
+
+;; OPT PSEUDOSECTION:
+; EDNS: version: 0, flags:; udp: 1232
+;; QUESTION SECTION:
+;host.example.			IN	HINFO
+
+;; ANSWER SECTION:
+host.example.		86400	IN	HINFO	\"ARM64\" \"Linux 6.1\"
+
+;; Query time: 0 msec
+;; SERVER: 172.18.0.2#53(172.18.0.2) (UDP)
+;; WHEN: Tue Mar 05 17:45:29 UTC 2024
+;; MSG SIZE  rcvd: 75
+";
+
+        let output: DigOutput = input.parse()?;
+
+        let [hinfo] = output.answer.try_into().expect("exactly one record");
+        assert_eq!(RecordType::Unknown(13), hinfo.record_type());
+        let hinfo = hinfo.try_into_unknown().expect("not an unknown record");
+        assert_eq!(
+            UnknownRdata::Presentation("\"ARM64\" \"Linux 6.1\"".to_string()),
+            hinfo.rdata
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn answer_rrsets() -> Result<()> {
+        // $ dig +dnssec A primary.nameservers.com.
+        let input = "
+; <<>> DiG 9.18.24-1-Debian <<>> +dnssec A primary.nameservers.com.
+;; global options: +cmd
+;; Got answer:
+;; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 40237
+;; flags: qr aa rd; QUERY: 1, ANSWER: 3, AUTHORITY: 0, ADDITIONAL: 1
+
+;; OPT PSEUDOSECTION:
+; EDNS: version: 0, flags: do; udp: 1232
+;; QUESTION SECTION:
+;primary.nameservers.com.	IN	A
+
+;; ANSWER SECTION:
+primary.nameservers.com. 86400	IN	RRSIG	A 8 3 86400 20240404090000 20240305090000 1546 nameservers.com. wXpRU4elJPGYm2kgVVsIwGf1IkYJcQ3UE4mwmItWdxj0XWSWY07MO4Ll
+primary.nameservers.com. 86400	IN	A	192.168.1.3
+primary.nameservers.com. 86400	IN	A	192.168.1.2
+
+;; Query time: 0 msec
+;; SERVER: 192.168.1.2#53(192.168.1.2) (UDP)
+;; WHEN: Tue Mar 05 09:00:00 UTC 2024
+;; MSG SIZE  rcvd: 223
+";
+
+        let output: DigOutput = input.parse()?;
+
+        let [rrset] = output
+            .answer_rrsets()?
+            .try_into()
+            .expect("exactly one RRset");
+        assert_eq!("primary.nameservers.com.", rrset.fqdn().as_str());
+        assert_eq!(RecordType::A, rrset.record_type());
+        assert_eq!(2, rrset.records.len());
+        assert!(rrset.is_signed());
+
+        Ok(())
+    }
+
     #[test]
     fn ede() -> Result<()> {
         let input = "; <<>> DiG 9.18.24-1-Debian <<>> +recurse +nodnssec +adflag +nocdflag @192.168.176.5 A example.nameservers.com.
@@ -467,4 +1194,83 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn tsig_verified() -> Result<()> {
+        // $ dig -y hmac-sha256:update-key.:$SECRET @172.18.0.2 SOA com.
+        let input = "
+; <<>> DiG 9.18.24-1-Debian <<>> -y hmac-sha256:update-key.:$SECRET @172.18.0.2 SOA com.
+; (1 server found)
+;; global options: +cmd
+;; Got answer:
+;; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 23162
+;; flags: qr aa; QUERY: 1, ANSWER: 1, AUTHORITY: 0, ADDITIONAL: 2
+
+;; OPT PSEUDOSECTION:
+; EDNS: version: 0, flags:; udp: 1232
+;; QUESTION SECTION:
+;com.				IN	SOA
+
+;; ANSWER SECTION:
+com.			86400	IN	SOA	primary0.nameservers.com. admin0.nameservers.com. 2024010101 1800 900 604800 86400
+
+;; TSIG PSEUDOSECTION:
+update-key.		0	ANY	TSIG	hmac-sha256. 1710000000 300 32 MZ/L2O6pb+W8iVfZ0ScUz0NvUZDB7uH4yLJ3atk6b7Q= 23162 NOERROR 0 
+
+;; Query time: 0 msec
+;; SERVER: 172.18.0.2#53(172.18.0.2) (UDP)
+;; WHEN: Sat Mar 09 16:00:00 UTC 2024
+;; MSG SIZE  rcvd: 196
+";
+
+        let output: DigOutput = input.parse()?;
+
+        assert_eq!(
+            Some(TsigStatus {
+                error: Some(TsigError::NOERROR),
+                verified: true,
+            }),
+            output.tsig
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn tsig_bad_key() -> Result<()> {
+        // $ dig -y hmac-sha256:unknown-key.:$SECRET @172.18.0.2 SOA com.
+        let input = "
+; <<>> DiG 9.18.24-1-Debian <<>> -y hmac-sha256:unknown-key.:$SECRET @172.18.0.2 SOA com.
+; (1 server found)
+;; global options: +cmd
+;; Couldn't verify signature: tsig indicates error
+;; Got answer:
+;; ->>HEADER<<- opcode: QUERY, status: NOTAUTH, id: 4242
+;; flags: qr; QUERY: 1, ANSWER: 0, AUTHORITY: 0, ADDITIONAL: 1
+
+;; QUESTION SECTION:
+;com.				IN	SOA
+
+;; TSIG PSEUDOSECTION:
+unknown-key.		0	ANY	TSIG	hmac-sha256. 1710000000 300 0  4242 BADKEY 0 
+
+;; Query time: 0 msec
+;; SERVER: 172.18.0.2#53(172.18.0.2) (UDP)
+;; WHEN: Sat Mar 09 16:00:00 UTC 2024
+;; MSG SIZE  rcvd: 83
+";
+
+        let output: DigOutput = input.parse()?;
+
+        assert_eq!(DigStatus::NOTAUTH, output.status);
+        assert_eq!(
+            Some(TsigStatus {
+                error: Some(TsigError::BADKEY),
+                verified: false,
+            }),
+            output.tsig
+        );
+
+        Ok(())
+    }
 }
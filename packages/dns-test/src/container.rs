@@ -11,7 +11,7 @@ use std::{env, fs};
 use tempfile::{NamedTempFile, TempDir};
 
 pub use crate::container::network::Network;
-use crate::{Error, Implementation, Repository, Result};
+use crate::{error, wire, Error, Implementation, Repository, Result};
 
 #[derive(Clone)]
 pub struct Container {
@@ -189,46 +189,35 @@ impl Container {
             .args(["exec", "-t", &self.inner.id])
             .args(command_and_args);
 
-        command.output()?.try_into()
+        command.output().map_err(Error::docker)?.try_into()
     }
 
     /// Similar to `Self::output` but checks `command_and_args` ran successfully and only
     /// returns the stdout
     pub fn stdout(&self, command_and_args: &[&str]) -> Result<String> {
-        let Output {
-            status,
-            stderr,
-            stdout,
-        } = self.output(command_and_args)?;
+        let output = self.output(command_and_args)?;
 
-        if status.success() {
-            Ok(stdout)
+        if output.status.success() {
+            Ok(output.stdout)
         } else {
-            eprintln!("STDOUT:\n{stdout}\nSTDERR:\n{stderr}");
-
-            Err(format!("[{}] `{command_and_args:?}` failed", self.inner.name).into())
+            Err(self.command_failed(command_and_args, output))
         }
     }
 
-    /// Similar to `std::process::Command::status` but runs `command_and_args` in the container
-    pub fn status(&self, command_and_args: &[&str]) -> Result<ExitStatus> {
-        let mut command = Command::new("docker");
-        command
-            .args(["exec", "-t", &self.inner.id])
-            .args(command_and_args);
-
-        Ok(command.status()?)
-    }
-
-    /// Like `Self::status` but checks that `command_and_args` executed successfully
+    /// Checks that `command_and_args` executed successfully in the container
+    ///
+    /// The output of `command_and_args` is captured and reported in the error
     pub fn status_ok(&self, command_and_args: &[&str]) -> Result<()> {
-        let status = self.status(command_and_args)?;
+        self.stdout(command_and_args).map(drop)
+    }
 
-        if status.success() {
-            Ok(())
-        } else {
-            Err(format!("[{}] `{command_and_args:?}` failed", self.inner.name).into())
-        }
+    /// The error to report when `command_and_args` produced the unsuccessful `output`
+    pub(crate) fn command_failed(&self, command_and_args: &[&str], output: Output) -> Error {
+        Error::command_failed(
+            Some(&self.inner.name),
+            command_and_args.iter().map(|arg| arg.to_string()).collect(),
+            output,
+        )
     }
 
     pub fn spawn(&self, cmd: &[&str]) -> Result<Child> {
@@ -236,13 +225,24 @@ impl Container {
         command.stdout(Stdio::piped()).stderr(Stdio::piped());
         command.args(["exec", "-t", &self.inner.id]).args(cmd);
 
-        let inner = command.spawn()?;
+        let inner = command.spawn().map_err(Error::docker)?;
         Ok(Child {
             inner: Some(inner),
             _container: self.inner.clone(),
         })
     }
 
+    /// Changes the clock observed by the processes started in the container from now on
+    ///
+    /// Processes that are already running may pick up the change after a few seconds; restart
+    /// them to get a deterministic behavior
+    pub fn set_clock(&self, clock: Clock) -> Result<()> {
+        match clock.faketimerc() {
+            Some(faketimerc) => self.cp(FAKETIMERC_PATH, &faketimerc),
+            None => self.status_ok(&["rm", "-f", FAKETIMERC_PATH]),
+        }
+    }
+
     pub fn ipv4_addr(&self) -> Ipv4Addr {
         self.inner.ipv4_addr
     }
@@ -251,11 +251,54 @@ impl Container {
         &self.inner.id
     }
 
+    pub(crate) fn name(&self) -> &str {
+        &self.inner.name
+    }
+
     pub(crate) fn network(&self) -> &Network {
         &self.inner.network
     }
 }
 
+// all images preload libfaketime, which reads its configuration from this file when the
+// `FAKETIME` environment variable is not set
+const FAKETIMERC_PATH: &str = "/etc/faketimerc";
+
+/// Clock of the processes running in a container
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Clock {
+    /// The host's clock
+    #[default]
+    Real,
+    /// The host's clock shifted by this many seconds; negative values move the clock to the past
+    Offset(i64),
+    /// A clock that starts at this time, in seconds since the UNIX epoch, and advances from there
+    StartAt(u64),
+}
+
+impl Clock {
+    // see the "Changing the 'specification' dynamically" section of libfaketime's README
+    fn faketimerc(&self) -> Option<String> {
+        match self {
+            Self::Real => None,
+            Self::Offset(seconds) => Some(format!("{seconds:+}")),
+            Self::StartAt(unix) => {
+                let text = wire::text_timestamp(*unix);
+                let (date, time) = (text / 1_000_000, text % 1_000_000);
+                Some(format!(
+                    "@{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+                    date / 10_000,
+                    date / 100 % 100,
+                    date % 100,
+                    time / 10_000,
+                    time / 100 % 100,
+                    time % 100,
+                ))
+            }
+        }
+    }
+}
+
 fn verbose_docker_build() -> bool {
     env::var("DNS_TEST_VERBOSE_DOCKER_BUILD").as_deref().is_ok()
 }
@@ -304,11 +347,10 @@ impl Child {
     ///
     /// This method will succeed at most once
     pub fn stdout(&mut self) -> Result<ChildStdout> {
-        Ok(self
-            .inner
+        self.inner
             .as_mut()
             .and_then(|child| child.stdout.take())
-            .ok_or("could not retrieve child's stdout")?)
+            .ok_or_else(|| Error::Other("could not retrieve child's stdout".to_string()))
     }
 
     pub fn wait(mut self) -> Result<Output> {
@@ -337,12 +379,12 @@ impl TryFrom<process::Output> for Output {
 
     fn try_from(output: process::Output) -> Result<Self> {
         let mut stderr = String::from_utf8(output.stderr)?;
-        while stderr.ends_with(|c| matches!(c, '\n' | '\r')) {
+        while stderr.ends_with(['\n', '\r']) {
             stderr.pop();
         }
 
         let mut stdout = String::from_utf8(output.stdout)?;
-        while stdout.ends_with(|c| matches!(c, '\n' | '\r')) {
+        while stdout.ends_with(['\n', '\r']) {
             stdout.pop();
         }
 
@@ -354,12 +396,16 @@ impl TryFrom<process::Output> for Output {
     }
 }
 
-fn checked_output(command: &mut Command) -> Result<process::Output> {
-    let output = command.output()?;
+pub(crate) fn checked_output(command: &mut Command) -> Result<process::Output> {
+    let output = command.output().map_err(Error::docker)?;
     if output.status.success() {
         Ok(output)
     } else {
-        Err(format!("`{command:?}` failed").into())
+        Err(Error::command_failed(
+            None,
+            error::command_line(command),
+            output.try_into()?,
+        ))
     }
 }
 
@@ -373,10 +419,7 @@ fn get_ipv4_addr(container_id: &str) -> Result<Ipv4Addr> {
         ])
         .arg(container_id);
 
-    let output = command.output()?;
-    if !output.status.success() {
-        return Err(format!("`{command:?}` failed").into());
-    }
+    let output = checked_output(&mut command)?;
 
     let ipv4_addr = str::from_utf8(&output.stdout)?.trim().to_string();
 
@@ -440,4 +483,31 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn set_clock_works() -> Result<()> {
+        let network = Network::new()?;
+        let container = Container::run(&Image::Client, &network)?;
+
+        container.set_clock(Clock::StartAt(1709251200))?;
+        let output = container.stdout(&["date", "-u", "+%Y-%m-%d"])?;
+        assert_eq!("2024-03-01", output);
+
+        container.set_clock(Clock::Real)?;
+        let output = container.stdout(&["date", "-u", "+%Y"])?;
+        assert_ne!("2024", output);
+
+        Ok(())
+    }
+
+    #[test]
+    fn faketimerc() {
+        assert_eq!(None, Clock::Real.faketimerc());
+        assert_eq!(Some("+3600"), Clock::Offset(3600).faketimerc().as_deref());
+        assert_eq!(Some("-60"), Clock::Offset(-60).faketimerc().as_deref());
+        assert_eq!(
+            Some("@2024-03-06 13:27:01"),
+            Clock::StartAt(1709731621).faketimerc().as_deref()
+        );
+    }
 }
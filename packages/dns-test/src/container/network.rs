@@ -6,6 +6,7 @@ use std::{
     },
 };
 
+use crate::container::checked_output;
 use crate::Result;
 
 /// Represents a network in which to put containers into.
@@ -60,13 +61,7 @@ impl NetworkInner {
             .arg(&network_name);
 
         // create network
-        let output = command.output()?;
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-
-        if !output.status.success() {
-            return Err(format!("--- STDOUT ---\n{stdout}\n--- STDERR ---\n{stderr}").into());
-        }
+        checked_output(&mut command)?;
 
         // inspect & parse network details
         let config = get_network_config(&network_name)?;
@@ -96,10 +91,7 @@ fn get_network_config(network_name: &str) -> Result<NetworkConfig> {
         ])
         .arg(network_name);
 
-    let output = command.output()?;
-    if !output.status.success() {
-        return Err(format!("{command:?} failed").into());
-    }
+    let output = checked_output(&mut command)?;
 
     let subnet = std::str::from_utf8(&output.stdout)?.trim().to_string();
     Ok(NetworkConfig { subnet })
@@ -0,0 +1,363 @@
+//! The error type of this crate
+
+use core::fmt;
+use std::io;
+use std::process::Command;
+
+use crate::container::Output;
+use crate::FQDN;
+
+/// An error produced by this crate
+///
+/// The variants carry enough context -- the command that was run, its output, the container it
+/// ran in or the text that could not be parsed -- to tell apart, and report, the different ways
+/// a test can fail
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The `docker` CLI could not be executed; docker is probably not installed
+    DockerUnavailable(io::Error),
+
+    /// A command exited with a non-zero status
+    CommandFailed {
+        /// Name of the container the command ran in; `None` if it ran on the host
+        container: Option<String>,
+        command: Vec<String>,
+        /// `None` if the command was terminated by a signal
+        exit_code: Option<i32>,
+        stdout: String,
+        stderr: String,
+    },
+
+    /// A command could not be executed because its container is not running
+    ContainerExited {
+        container: String,
+        command: Vec<String>,
+    },
+
+    /// A command gave up waiting for a response from a server, e.g. `dig` or `nsupdate`
+    Timeout {
+        container: Option<String>,
+        command: Vec<String>,
+        stdout: String,
+        stderr: String,
+    },
+
+    /// The output of a tool, or a user provided string, could not be parsed
+    Parse {
+        message: String,
+        /// The line that could not be parsed, if known
+        line: Option<String>,
+        /// The 1-based position of `line` in the parsed text, if known
+        line_number: Option<usize>,
+    },
+
+    /// A value passed to this crate is not valid, e.g. a `Graph` spec that doesn't start at the
+    /// root zone or a record that lies outside of the zone being signed
+    InvalidInput(String),
+
+    /// There are not enough keys to sign a zone: an active KSK and an active ZSK, or an active
+    /// CSK, are required
+    MissingKeys {
+        /// `None` if there are no keys at all
+        zone: Option<FQDN>,
+    },
+
+    /// None of the keys of a zone has this key tag
+    UnknownKeyTag {
+        key_tag: u16,
+    },
+
+    /// A key operation was requested on a zone that has not been signed
+    ZoneNotSigned {
+        zone: FQDN,
+    },
+
+    /// The name server refused, or could not complete, a zone transfer (AXFR / IXFR)
+    ZoneTransferFailed {
+        /// Name of the container the transfer was requested from
+        container: String,
+        command: Vec<String>,
+        stdout: String,
+    },
+
+    Io(io::Error),
+
+    /// Any other failure
+    Other(String),
+}
+
+impl Error {
+    /// Classifies the failure of `command`, which ran in `container`
+    pub(crate) fn command_failed(
+        container: Option<&str>,
+        command: Vec<String>,
+        output: Output,
+    ) -> Self {
+        let Output {
+            status,
+            stdout,
+            stderr,
+        } = output;
+
+        if let Some(container) = container {
+            // the error is reported by the docker daemon, not by `command`
+            if stderr.contains("is not running") {
+                return Self::ContainerExited {
+                    container: container.to_string(),
+                    command,
+                };
+            }
+        }
+
+        let container = container.map(str::to_string);
+        if stdout.contains("timed out") || stderr.contains("timed out") {
+            Self::Timeout {
+                container,
+                command,
+                stdout,
+                stderr,
+            }
+        } else {
+            Self::CommandFailed {
+                container,
+                command,
+                exit_code: status.code(),
+                stdout,
+                stderr,
+            }
+        }
+    }
+
+    /// Like `Error::from(io::Error)` but reports a missing `docker` binary as
+    /// `Error::DockerUnavailable`
+    pub(crate) fn docker(error: io::Error) -> Self {
+        if error.kind() == io::ErrorKind::NotFound {
+            Self::DockerUnavailable(error)
+        } else {
+            Self::Io(error)
+        }
+    }
+
+    pub(crate) fn parse(message: impl Into<String>) -> Self {
+        Self::Parse {
+            message: message.into(),
+            line: None,
+            line_number: None,
+        }
+    }
+
+    /// Turns this error into a `Parse` error that points at `line`, the `line_number`-th line of
+    /// the text being parsed
+    pub(crate) fn at_line(self, line_number: usize, line: &str) -> Self {
+        let message = match self {
+            Self::Parse { message, .. } => message,
+            other => other.to_string(),
+        };
+
+        Self::Parse {
+            message,
+            line: Some(line.to_string()),
+            line_number: Some(line_number),
+        }
+    }
+}
+
+/// The program and arguments of `command`, for use in `Error` variants
+pub(crate) fn command_line(command: &Command) -> Vec<String> {
+    let mut command_line = vec![command.get_program().to_string_lossy().into_owned()];
+    command_line.extend(
+        command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned()),
+    );
+    command_line
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DockerUnavailable(error) => write!(f, "could not execute `docker`: {error}"),
+
+            Self::CommandFailed {
+                container,
+                command,
+                exit_code,
+                stdout,
+                stderr,
+            } => {
+                if let Some(container) = container {
+                    write!(f, "[{container}] ")?;
+                }
+                write!(f, "`{command:?}` failed")?;
+                if let Some(exit_code) = exit_code {
+                    write!(f, " with exit code {exit_code}")?;
+                }
+                write!(f, "\n--- STDOUT ---\n{stdout}\n--- STDERR ---\n{stderr}")
+            }
+
+            Self::ContainerExited { container, command } => {
+                write!(
+                    f,
+                    "[{container}] could not run `{command:?}`: the container exited"
+                )
+            }
+
+            Self::Timeout {
+                container,
+                command,
+                stdout,
+                stderr,
+            } => {
+                if let Some(container) = container {
+                    write!(f, "[{container}] ")?;
+                }
+                write!(
+                    f,
+                    "`{command:?}` timed out\n--- STDOUT ---\n{stdout}\n--- STDERR ---\n{stderr}"
+                )
+            }
+
+            Self::Parse {
+                message,
+                line,
+                line_number,
+            } => {
+                f.write_str(message)?;
+                if let Some(line_number) = line_number {
+                    write!(f, " (line {line_number})")?;
+                }
+                if let Some(line) = line {
+                    write!(f, ": {line}")?;
+                }
+                Ok(())
+            }
+
+            Self::InvalidInput(message) => f.write_str(message),
+
+            Self::MissingKeys { zone } => {
+                if let Some(zone) = zone {
+                    write!(f, "zone {zone} needs ")?;
+                } else {
+                    f.write_str("a zone needs ")?;
+                }
+                f.write_str("at least one active KSK and one active ZSK, or an active CSK")
+            }
+
+            Self::UnknownKeyTag { key_tag } => write!(f, "no key with key tag {key_tag}"),
+
+            Self::ZoneNotSigned { zone } => write!(f, "zone {zone} is not signed"),
+
+            Self::ZoneTransferFailed {
+                container,
+                command,
+                stdout,
+            } => write!(
+                f,
+                "[{container}] `{command:?}` failed to transfer the zone\n--- STDOUT ---\n{stdout}"
+            ),
+
+            Self::Io(error) => write!(f, "{error}"),
+
+            Self::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::DockerUnavailable(error) | Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+macro_rules! parse_errors {
+    ($($error:ty),*) => {
+        $(
+            impl From<$error> for Error {
+                fn from(error: $error) -> Self {
+                    Self::parse(error.to_string())
+                }
+            }
+        )*
+    };
+}
+
+parse_errors!(
+    core::num::ParseIntError,
+    core::str::Utf8Error,
+    std::net::AddrParseError,
+    std::string::FromUtf8Error,
+    data_encoding::DecodeError,
+    serde_json::Error
+);
+
+macro_rules! other_errors {
+    ($($error:ty),*) => {
+        $(
+            impl From<$error> for Error {
+                fn from(error: $error) -> Self {
+                    Self::Other(error.to_string())
+                }
+            }
+        )*
+    };
+}
+
+other_errors!(
+    core::num::TryFromIntError,
+    ring::error::KeyRejected,
+    ring::error::Unspecified,
+    std::time::SystemTimeError
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_line() {
+        let error = Error::parse("unknown record type: FOO").at_line(3, "a.\t0\tIN\tFOO\t1");
+
+        let Error::Parse {
+            message,
+            line,
+            line_number,
+        } = &error
+        else {
+            panic!("expected a parse error, got {error:?}");
+        };
+        assert_eq!("unknown record type: FOO", message);
+        assert_eq!(Some("a.\t0\tIN\tFOO\t1"), line.as_deref());
+        assert_eq!(Some(3), *line_number);
+    }
+
+    #[test]
+    fn timeout() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        let output = Output {
+            status: ExitStatus::from_raw(9 << 8),
+            stderr: String::new(),
+            stdout: ";; connection timed out; no servers could be reached".to_string(),
+        };
+        let error = Error::command_failed(Some("client"), vec!["dig".to_string()], output);
+
+        assert!(matches!(error, Error::Timeout { .. }));
+    }
+
+    #[test]
+    fn missing_docker() {
+        let error = Error::docker(io::Error::from(io::ErrorKind::NotFound));
+        assert!(matches!(error, Error::DockerUnavailable(_)));
+    }
+}
@@ -1,24 +1,60 @@
-use core::fmt;
+use core::cmp::Ordering;
+use core::fmt::{self, Write};
+use core::hash::{Hash, Hasher};
 use core::str::FromStr;
 use std::borrow::Cow;
 
 use crate::{Error, Result};
 
-#[derive(Clone, PartialEq)]
+// section 2.3.4 of RFC1035
+const MAX_LABEL_LEN: usize = 63;
+const MAX_NAME_LEN: usize = 255;
+
+/// A fully qualified domain name in presentation format, e.g. `www.example.com.`
+///
+/// Labels may contain escape sequences: `\X` for a character `X`, like a dot, and `\DDD` for
+/// the octet with decimal value `DDD`. Comparisons ignore ASCII case and `Ord` implements the
+/// canonical ordering of section 6.1 of RFC4034
+#[derive(Clone)]
 pub struct FQDN {
     inner: Cow<'static, str>,
 }
 
-// TODO likely needs further validation
 #[allow(non_snake_case)]
 pub fn FQDN(input: impl Into<Cow<'static, str>>) -> Result<FQDN> {
     let input = input.into();
-    if !input.ends_with('.') {
-        return Err("FQDN must end with a `.`".into());
+    // an odd number of backslashes before the final dot escapes it
+    let backslashes = input
+        .strip_suffix('.')
+        .map(|rest| rest.bytes().rev().take_while(|byte| *byte == b'\\').count());
+    if backslashes.is_none_or(|count| count % 2 != 0) {
+        return Err(Error::parse("FQDN must end with an unescaped `.`"));
     }
 
     if input != "." && input.starts_with('.') {
-        return Err("non-root FQDN cannot start with a `.`".into());
+        return Err(Error::parse("non-root FQDN cannot start with a `.`"));
+    }
+
+    let mut len = 1; // the root label
+    for label in Labels::new(&input) {
+        let label_len = unescape_label(label)?.len();
+        if label_len == 0 {
+            return Err(Error::parse(format!("FQDN `{input}` has an empty label")));
+        }
+
+        if label_len > MAX_LABEL_LEN {
+            return Err(Error::parse(format!(
+                "label `{label}` is longer than {MAX_LABEL_LEN} octets"
+            )));
+        }
+
+        len += label_len + 1;
+    }
+
+    if len > MAX_NAME_LEN {
+        return Err(Error::parse(format!(
+            "FQDN `{input}` is longer than {MAX_NAME_LEN} octets"
+        )));
     }
 
     Ok(FQDN { inner: input })
@@ -56,26 +92,215 @@ impl FQDN {
         }
     }
 
+    /// The labels of the domain name, in presentation format, from left to right; the root
+    /// label is not included
+    pub fn labels(&self) -> Labels<'_> {
+        Labels::new(&self.inner)
+    }
+
+    /// The labels of the domain name, as octets, from left to right; the root label is not
+    /// included
+    pub(crate) fn wire_labels(&self) -> Vec<Cow<'_, [u8]>> {
+        self.labels()
+            .map(|label| unescape_label(label).expect("UNREACHABLE: labels were validated"))
+            .collect()
+    }
+
     pub fn parent(&self) -> Option<FQDN> {
-        let (fragment, parent) = self.inner.split_once('.').unwrap();
+        let label = self.labels().next()?;
+        let parent = self.inner.get(label.len() + 1..)?;
 
-        if fragment.is_empty() {
-            None
+        if parent.is_empty() {
+            Some(FQDN::ROOT)
         } else {
-            let parent = if parent.is_empty() {
-                FQDN::ROOT
-            } else {
-                FQDN(parent.to_string()).unwrap()
-            };
-            Some(parent)
+            FQDN(parent.to_string()).ok()
         }
     }
 
     pub fn num_labels(&self) -> usize {
-        self.inner
-            .split('.')
-            .filter(|label| !label.is_empty())
-            .count()
+        self.labels().count()
+    }
+
+    /// Whether this name is `other` or a descendant of `other`
+    pub fn is_subdomain_of(&self, other: &FQDN) -> bool {
+        let labels = self.wire_labels();
+        let other = other.wire_labels();
+
+        labels.len() >= other.len()
+            && labels
+                .iter()
+                .rev()
+                .zip(other.iter().rev())
+                .all(|(label, other)| label.eq_ignore_ascii_case(other))
+    }
+
+    /// Whether the leftmost label is the asterisk label (section 4.3.3 of RFC1034)
+    pub fn is_wildcard(&self) -> bool {
+        self.labels()
+            .next()
+            .is_some_and(|label| unescape_label(label).is_ok_and(|label| *label == *b"*"))
+    }
+
+    /// The domain name formed by the labels of this name followed by the labels of `suffix`,
+    /// e.g. `www.` + `example.com.` = `www.example.com.`
+    pub fn append(&self, suffix: &FQDN) -> Result<FQDN> {
+        if self.is_root() {
+            return Ok(suffix.clone());
+        }
+
+        if suffix.is_root() {
+            return Ok(self.clone());
+        }
+
+        FQDN(format!("{}{}", self.inner, suffix.inner))
+    }
+
+    /// The domain name formed by `label`, in presentation format, followed by the labels of this
+    /// name
+    pub fn prepend_label(&self, label: &str) -> Result<FQDN> {
+        if (Labels { rest: label }).next() != Some(label) || label.ends_with('.') {
+            return Err(Error::parse(format!("`{label}` is not a single label")));
+        }
+
+        let suffix = if self.is_root() { "" } else { self.as_str() };
+        FQDN(format!("{label}.{suffix}"))
+    }
+}
+
+/// Iterator over the labels of a domain name; see `FQDN::labels`
+pub struct Labels<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Labels<'a> {
+    fn new(fqdn: &'a str) -> Self {
+        // the root name has no labels other than the root label
+        let rest = if fqdn == "." { "" } else { fqdn };
+        Labels { rest }
+    }
+}
+
+impl<'a> Iterator for Labels<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let mut escaped = false;
+        let mut end = self.rest.len();
+        for (index, c) in self.rest.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '.' => {
+                    end = index;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let label = &self.rest[..end];
+        self.rest = self.rest.get(end + 1..).unwrap_or_default();
+        Some(label)
+    }
+}
+
+/// Decodes the escape sequences of a label in presentation format
+fn unescape_label(label: &str) -> Result<Cow<'_, [u8]>> {
+    if !label.contains('\\') {
+        return Ok(Cow::Borrowed(label.as_bytes()));
+    }
+
+    let invalid = || Error::parse(format!("invalid escape sequence in label `{label}`"));
+    let mut bytes = vec![];
+    let mut input = label.bytes();
+    while let Some(byte) = input.next() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+
+        let escaped = input.next().ok_or_else(invalid)?;
+        if escaped.is_ascii_digit() {
+            let mut value = u16::from(escaped - b'0');
+            for _ in 0..2 {
+                let digit = input
+                    .next()
+                    .filter(u8::is_ascii_digit)
+                    .ok_or_else(invalid)?;
+                value = value * 10 + u16::from(digit - b'0');
+            }
+            bytes.push(u8::try_from(value).map_err(|_| invalid())?);
+        } else {
+            bytes.push(escaped);
+        }
+    }
+
+    Ok(Cow::Owned(bytes))
+}
+
+/// Appends the presentation format of a label, given as octets, to `text`
+pub(crate) fn escape_label(label: &[u8], text: &mut String) {
+    for byte in label {
+        match byte {
+            b'.' | b'\\' => {
+                text.push('\\');
+                text.push(char::from(*byte));
+            }
+            0x21..=0x7E => text.push(char::from(*byte)),
+            _ => write!(text, "\\{byte:03}").unwrap(),
+        }
+    }
+}
+
+impl PartialEq for FQDN {
+    fn eq(&self, other: &Self) -> bool {
+        let labels = self.wire_labels();
+        let other = other.wire_labels();
+
+        labels.len() == other.len()
+            && labels
+                .iter()
+                .zip(&other)
+                .all(|(label, other)| label.eq_ignore_ascii_case(other))
+    }
+}
+
+impl Eq for FQDN {}
+
+impl Hash for FQDN {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for label in self.wire_labels() {
+            state.write_usize(label.len());
+            for byte in label.iter() {
+                state.write_u8(byte.to_ascii_lowercase());
+            }
+        }
+    }
+}
+
+impl PartialOrd for FQDN {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// section 6.1 of RFC4034: labels are compared from right to left, as lowercased octet strings;
+// a name sorts before its descendants
+impl Ord for FQDN {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let lowercase = |fqdn: &FQDN| {
+            fqdn.wire_labels()
+                .into_iter()
+                .rev()
+                .map(|label| label.to_ascii_lowercase())
+                .collect::<Vec<_>>()
+        };
+
+        lowercase(self).cmp(&lowercase(other))
     }
 }
 
@@ -131,4 +356,144 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn enforces_length_limits() {
+        let label = "a".repeat(MAX_LABEL_LEN);
+        assert!(FQDN(format!("{label}.com.")).is_ok());
+        assert!(FQDN(format!("a{label}.com.")).is_err());
+
+        // 3 labels of 63 octets and 1 of 61 octets + length octets + root label = 255 octets
+        let name = format!("{label}.{label}.{label}.{}.", "a".repeat(61));
+        assert!(FQDN(name.clone()).is_ok());
+        assert!(FQDN(format!("a{name}")).is_err());
+
+        assert!(FQDN("a..com.").is_err());
+        assert!(FQDN("a..").is_err());
+        assert!(FQDN("a.b..").is_err());
+        assert!(FQDN("..").is_err());
+    }
+
+    #[test]
+    fn escaped_labels() -> Result<()> {
+        let fqdn = FQDN(r"a\.b.c\032d.example.")?;
+        assert_eq!(
+            [r"a\.b", r"c\032d", "example"],
+            fqdn.labels().collect::<Vec<_>>().as_slice()
+        );
+        assert_eq!(Some(FQDN("c\\032d.example.")?), fqdn.parent());
+
+        let wire_labels = fqdn.wire_labels();
+        assert_eq!(b"a.b", &*wire_labels[0]);
+        assert_eq!(b"c d", &*wire_labels[1]);
+
+        // an escaped octet counts as one octet towards the label limit
+        assert!(FQDN(format!(r"{}.com.", r"\065".repeat(MAX_LABEL_LEN))).is_ok());
+        assert!(FQDN(r"a\256.com.").is_err());
+        assert!(FQDN(r"a\1.com.").is_err());
+
+        // the only trailing dot is escaped so the name is relative
+        assert!(FQDN(r"a\.").is_err());
+        assert!(FQDN(r"a\\\.").is_err());
+
+        let fqdn = FQDN(r"a\..")?;
+        assert_eq!([r"a\."], fqdn.labels().collect::<Vec<_>>().as_slice());
+        assert_eq!(Some(FQDN::ROOT), fqdn.parent());
+
+        let fqdn = FQDN(r"a\\.")?;
+        assert_eq!([r"a\\"], fqdn.labels().collect::<Vec<_>>().as_slice());
+        assert_eq!(Some(FQDN::ROOT), fqdn.parent());
+
+        Ok(())
+    }
+
+    #[test]
+    fn case_insensitive_equality() -> Result<()> {
+        use std::collections::HashSet;
+
+        let lower = FQDN("www.example.com.")?;
+        let mixed = FQDN("WWW.Example.COM.")?;
+        assert_eq!(lower, mixed);
+        assert_eq!(FQDN(r"\065.com.")?, FQDN("a.com.")?);
+        assert_ne!(FQDN(r"a\.b.com.")?, FQDN("a.b.com.")?);
+
+        let set = HashSet::from([lower, mixed]);
+        assert_eq!(1, set.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn canonical_order() -> Result<()> {
+        // example from section 6.1 of RFC4034
+        let expected = [
+            "example.",
+            "a.example.",
+            "yljkjljk.a.example.",
+            "Z.a.example.",
+            r"zABC.a.EXAMPLE.",
+            "z.example.",
+            r"\001.z.example.",
+            "*.z.example.",
+            r"\200.z.example.",
+        ]
+        .map(|name| FQDN(name).unwrap());
+
+        let mut names = expected.clone();
+        names.reverse();
+        names.sort();
+        assert_eq!(expected, names);
+
+        Ok(())
+    }
+
+    #[test]
+    fn subdomain() -> Result<()> {
+        let fqdn = FQDN("www.Example.com.")?;
+        assert!(fqdn.is_subdomain_of(&FQDN("example.COM.")?));
+        assert!(fqdn.is_subdomain_of(&fqdn));
+        assert!(fqdn.is_subdomain_of(&FQDN::ROOT));
+        assert!(!fqdn.is_subdomain_of(&FQDN("ample.com.")?));
+        assert!(!FQDN::COM.is_subdomain_of(&fqdn));
+
+        Ok(())
+    }
+
+    #[test]
+    fn append_and_prepend_label() -> Result<()> {
+        let www = FQDN("www.")?;
+        let example = FQDN("example.com.")?;
+        assert_eq!("www.example.com.", www.append(&example)?.as_str());
+        assert_eq!("example.com.", FQDN::ROOT.append(&example)?.as_str());
+        assert_eq!("www.", www.append(&FQDN::ROOT)?.as_str());
+
+        let long = FQDN(format!("{}.", "a".repeat(MAX_LABEL_LEN)))?;
+        let mut name = long.clone();
+        for _ in 0..2 {
+            name = name.append(&long)?;
+        }
+        assert!(name.append(&long).is_err());
+
+        assert_eq!("*.example.com.", example.prepend_label("*")?.as_str());
+        assert_eq!(r"a\.b.com.", FQDN::COM.prepend_label(r"a\.b")?.as_str());
+        assert_eq!("com.", FQDN::ROOT.prepend_label("com")?.as_str());
+        assert!(example.prepend_label("a.b").is_err());
+        assert!(example.prepend_label("").is_err());
+        assert!(example
+            .prepend_label(&"a".repeat(MAX_LABEL_LEN + 1))
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn wildcard() -> Result<()> {
+        assert!(FQDN("*.example.com.")?.is_wildcard());
+        assert!(!FQDN("a.*.example.com.")?.is_wildcard());
+        assert!(FQDN(r"\042.example.com.")?.is_wildcard());
+        assert!(!FQDN("**.example.com.")?.is_wildcard());
+        assert!(!FQDN::ROOT.is_wildcard());
+
+        Ok(())
+    }
 }
@@ -1,21 +1,42 @@
 use core::fmt;
 use std::borrow::Cow;
+use std::net::Ipv4Addr;
 use std::path::Path;
 
 use url::Url;
 
+use crate::name_server::UpdatePolicy;
+use crate::tsig::TsigKey;
 use crate::FQDN;
 
 #[derive(Clone, Copy)]
 pub enum Config<'a> {
     NameServer {
         origin: &'a FQDN,
+        /// Address of the primary name server the zone is transferred from; `None` means that
+        /// the zone is loaded from the local zone file
+        primary: Option<Ipv4Addr>,
+        /// Addresses of the secondary name servers that are notified (RFC1996) when the zone
+        /// changes
+        notify: &'a [Ipv4Addr],
+        /// TSIG key that authenticates zone transfers: the ones served, as a primary, and the
+        /// ones requested, as a secondary
+        transfer_key: Option<&'a TsigKey>,
+        /// Dynamic updates (RFC2136) accepted by the name server; `None` means none are
+        update_policy: Option<&'a UpdatePolicy>,
+        /// Whether the name server signs the records changed by dynamic updates, with the keys
+        /// that `ldns-keygen` left in the zones directory
+        resign: bool,
     },
     Resolver {
         use_dnssec: bool,
         netmask: &'a str,
         /// Extended DNS error (RFC8914)
         ede: bool,
+        /// Automated updates of the trust anchor (RFC5011)
+        managed_trust_anchor: bool,
+        /// RFC5011 add and remove hold-down time, in seconds
+        hold_down: Option<u32>,
     },
 }
 
@@ -34,10 +55,11 @@ pub enum Role {
     Resolver,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub enum Implementation {
     Bind,
     Hickory(Repository<'static>),
+    #[default]
     Unbound,
 }
 
@@ -66,7 +88,10 @@ impl Implementation {
                 use_dnssec,
                 netmask,
                 ede,
+                managed_trust_anchor,
+                hold_down,
             } => match self {
+                // the hold-down time is set on the command line; see `ResolverSettings::start`
                 Self::Bind => {
                     assert!(!ede, "the BIND resolver does not support EDE (RFC8914)");
 
@@ -78,6 +103,11 @@ impl Implementation {
                 }
 
                 Self::Hickory(_) => {
+                    assert!(
+                        !managed_trust_anchor,
+                        "the hickory resolver does not support RFC5011 trust anchors"
+                    );
+
                     // TODO enable EDE in Hickory when supported
                     minijinja::render!(
                         include_str!("templates/hickory.resolver.toml.jinja"),
@@ -91,29 +121,89 @@ impl Implementation {
                         use_dnssec => use_dnssec,
                         netmask => netmask,
                         ede => ede,
+                        managed_trust_anchor => managed_trust_anchor,
+                        hold_down => hold_down,
                     )
                 }
             },
 
-            Config::NameServer { origin } => match self {
+            Config::NameServer {
+                origin,
+                primary,
+                notify,
+                transfer_key,
+                update_policy,
+                resign,
+            } => match self {
                 Self::Bind => {
+                    let mut keys = transfer_key.into_iter().collect::<Vec<_>>();
+                    let allow_update = match update_policy {
+                        None => None,
+                        Some(UpdatePolicy::Anyone) => Some("any".to_string()),
+                        Some(UpdatePolicy::Tsig(key)) => {
+                            if keys.iter().all(|other| other.name != key.name) {
+                                keys.push(key);
+                            }
+                            Some(format!("key \"{}\"", key.name))
+                        }
+                    };
+
                     minijinja::render!(
                         include_str!("templates/named.name-server.conf.jinja"),
-                        fqdn => origin.as_str()
+                        fqdn => origin.as_str(),
+                        primary => primary.map(|addr| addr.to_string()),
+                        notify => notify.iter().map(|addr| addr.to_string()).collect::<Vec<_>>(),
+                        tsig_keys => keys.iter().map(|key| key.bind_key_statement()).collect::<Vec<_>>(),
+                        transfer_key => transfer_key.map(|key| key.name.as_str()),
+                        allow_update => allow_update,
+                        resign => resign,
                     )
                 }
 
                 Self::Unbound => {
+                    assert!(
+                        update_policy.is_none(),
+                        "NSD does not support dynamic updates (RFC2136)"
+                    );
+
                     minijinja::render!(
                         include_str!("templates/nsd.conf.jinja"),
-                        fqdn => origin.as_str()
+                        fqdn => origin.as_str(),
+                        primary => primary.map(|addr| addr.to_string()),
+                        notify => notify.iter().map(|addr| addr.to_string()).collect::<Vec<_>>(),
+                        transfer_key => transfer_key.map(|key| minijinja::context! {
+                            name => key.name.as_str(),
+                            algorithm => key.algorithm.as_str(),
+                            secret => key.secret(),
+                        }),
                     )
                 }
 
+                // hickory does not send NOTIFY messages; its secondaries only pick up changes
+                // when they refresh the zone
                 Self::Hickory(_) => {
+                    // hickory can serve zone transfers but it has no client side implementation
+                    // of them
+                    assert!(
+                        primary.is_none(),
+                        "the hickory name server does not support the secondary role"
+                    );
+
+                    assert!(
+                        transfer_key.is_none()
+                            && !matches!(update_policy, Some(UpdatePolicy::Tsig(_))),
+                        "the hickory name server does not support TSIG"
+                    );
+                    // hickory can only sign updates with keys listed in its configuration
+                    assert!(
+                        !resign,
+                        "the hickory name server does not support dynamic updates of signed zones"
+                    );
+
                     minijinja::render!(
                         include_str!("templates/hickory.name-server.toml.jinja"),
-                        fqdn => origin.as_str()
+                        fqdn => origin.as_str(),
+                        allow_update => update_policy.is_some(),
                     )
                 }
             },
@@ -203,9 +293,3 @@ pub fn Repository(input: impl Into<Cow<'static, str>>) -> Repository<'static> {
     );
     Repository { inner: input }
 }
-
-impl Default for Implementation {
-    fn default() -> Self {
-        Self::Unbound
-    }
-}
@@ -4,24 +4,30 @@ use std::env;
 
 use lazy_static::lazy_static;
 
-pub use crate::container::Network;
-pub use crate::fqdn::FQDN;
+pub use crate::container::{Clock, Network};
+pub use crate::error::Error;
+pub use crate::fqdn::{Labels, FQDN};
 pub use crate::implementation::{Implementation, Repository};
 pub use crate::resolver::Resolver;
 pub use crate::trust_anchor::TrustAnchor;
 
 pub mod client;
 mod container;
+mod error;
 mod fqdn;
 mod implementation;
 pub mod name_server;
 pub mod record;
 mod resolver;
+pub mod rrset;
+pub mod signer;
 mod trust_anchor;
 pub mod tshark;
+pub mod tsig;
+pub mod validator;
+pub mod wire;
 pub mod zone_file;
 
-pub type Error = Box<dyn std::error::Error>;
 pub type Result<T> = core::result::Result<T, Error>;
 
 // TODO maybe this should be a TLS variable that each unit test (thread) can override
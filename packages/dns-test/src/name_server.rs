@@ -1,15 +1,22 @@
+use core::cmp::Reverse;
 use core::sync::atomic::{self, AtomicUsize};
+use std::collections::BTreeMap;
+use std::mem;
 use std::net::Ipv4Addr;
 
-use crate::container::{Child, Container, Network};
+use crate::container::{Child, Clock, Container, Network};
 use crate::implementation::{Config, Role};
-use crate::record::{self, Record, SoaSettings, DS, SOA};
+use crate::record::{self, Algorithm, DigestType, Record, SoaSettings, DS, SOA};
+use crate::signer::{Signer, SigningKey};
 use crate::tshark::Tshark;
+use crate::tsig::TsigKey;
 use crate::zone_file::{self, Root, ZoneFile};
-use crate::{Implementation, Result, TrustAnchor, DEFAULT_TTL, FQDN};
+use crate::{Error, Implementation, Result, TrustAnchor, DEFAULT_TTL, FQDN};
 
 pub struct Graph {
-    pub nameservers: Vec<NameServer<Running>>,
+    /// The name servers in the graph, indexed by the zone they have authority over. The
+    /// secondary name servers of a zone are reachable through `NameServer::secondaries`
+    pub nameservers: BTreeMap<FQDN, NameServer<Running>>,
     pub root: Root,
     pub trust_anchor: Option<TrustAnchor>,
 }
@@ -17,124 +24,470 @@ pub struct Graph {
 /// Whether to sign the zone files
 pub enum Sign<'a> {
     No,
-    Yes,
+    Yes {
+        settings: SignSettings,
+    },
     /// Signs the zone files and then modifies the records produced by the signing process
-    AndAmend(&'a dyn Fn(&FQDN, &mut Vec<Record>)),
+    AndAmend {
+        settings: SignSettings,
+        mutate: &'a dyn Fn(&FQDN, &mut Vec<Record>),
+    },
+}
+
+/// Settings used to sign a zone file
+#[derive(Clone, Debug)]
+pub struct SignSettings {
+    pub algorithm: Algorithm,
+    /// Size of the zone signing key, in bits. Only used with RSA algorithms
+    pub zsk_bits: u16,
+    /// Size of the key signing key, in bits. Only used with RSA algorithms
+    pub ksk_bits: u16,
+    /// Digest type of the DS record that's added to the parent zone
+    pub ds_digest: DigestType,
+    /// Authenticated denial of existence mechanism
+    pub nsec: Nsec,
+    /// What produces the keys, signatures and DS record
+    pub tool: SigningTool,
+    /// Whether the zone is signed with separate KSK and ZSK, or with a single CSK
+    pub key_scheme: KeyScheme,
+    /// Start of the signatures' validity period, in seconds since the UNIX epoch. `None` means
+    /// the current time of the signing tool's clock
+    pub inception: Option<u64>,
+    /// End of the signatures' validity period, in seconds since the UNIX epoch. `None` means the
+    /// signing tool's default: 4 weeks (`ldns-signzone`) or 30 days (`Signer`) after the current
+    /// time
+    pub expiration: Option<u64>,
+}
+
+/// Set of keys a zone is initially signed with
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum KeyScheme {
+    /// A key signing key (KSK) and a zone signing key (ZSK)
+    #[default]
+    Split,
+    /// A single combined signing key (CSK); the `ksk_bits` size is used for it
+    Combined,
+}
+
+/// Implementation of the signing process
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SigningTool {
+    /// `ldns-keygen`, `ldns-signzone` and `ldns-key2ds`, executed inside the name server container
+    #[default]
+    Ldns,
+    /// The `signer` module of this crate. RSA keys are not supported
+    InProcess,
+}
+
+/// Records used to prove the non-existence of names and record types
+#[derive(Clone, Debug, PartialEq)]
+pub enum Nsec {
+    /// NSEC records (RFC4034)
+    _1,
+    /// NSEC3 records (RFC5155)
+    _3 {
+        /// Number of additional times the hash function is applied
+        iterations: u16,
+        /// Whether the opt-out flag is set on the NSEC3 records
+        opt_out: bool,
+        /// Hex encoded salt; `None` means an empty salt
+        salt: Option<String>,
+    },
+}
+
+impl Default for SignSettings {
+    fn default() -> Self {
+        Self {
+            algorithm: Algorithm::RSASHA1_NSEC3_SHA1,
+            zsk_bits: 1024,
+            ksk_bits: 2048,
+            ds_digest: DigestType::SHA256,
+            nsec: Nsec::_3 {
+                iterations: 1,
+                opt_out: true,
+                salt: None,
+            },
+            tool: SigningTool::default(),
+            key_scheme: KeyScheme::default(),
+            inception: None,
+            expiration: None,
+        }
+    }
+}
+
+/// How a single zone in a `Graph` is signed
+///
+/// This overrides, for one zone, the choice made by `Sign` for the whole graph
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Signing {
+    /// The zone file is not signed
+    Unsigned,
+    /// The zone file is signed and a DS record with the hash of the zone's key is added to the
+    /// parent's zone file
+    Signed,
+    /// The zone file is signed but no DS record is added to the parent's zone file
+    SignedWithoutDs,
+}
+
+/// How a secondary name server gets the zone it serves
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Replication {
+    /// The primary's zone file is copied into the secondary's container
+    #[default]
+    Copy,
+    /// The secondary pulls the zone from the primary by zone transfer (AXFR / IXFR)
+    ZoneTransfer,
+}
+
+/// Which dynamic updates (RFC2136) a name server accepts
+#[derive(Clone, Debug)]
+pub enum UpdatePolicy {
+    /// Updates from any client
+    Anyone,
+    /// Only updates signed with this key (TSIG)
+    Tsig(TsigKey),
+}
+
+/// Specification of a zone, and its child zones, used to build a `Graph`
+pub struct ZoneSpec {
+    zone: FQDN,
+    records: Vec<Record>,
+    signing: Option<Signing>,
+    implementation: Option<Implementation>,
+    secondaries: Vec<(Option<Implementation>, Replication)>,
+    children: Vec<ZoneSpec>,
+}
+
+impl ZoneSpec {
+    /// Creates the specification of an empty `zone` without child zones
+    pub fn new(zone: FQDN) -> Self {
+        Self {
+            zone,
+            records: Vec::new(),
+            signing: None,
+            implementation: None,
+            secondaries: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds a record to the zone file
+    pub fn add(&mut self, record: impl Into<Record>) -> &mut Self {
+        self.records.push(record.into());
+        self
+    }
+
+    /// Adds a child zone
+    ///
+    /// # Panics
+    ///
+    /// this method panics if `child` is not a zone directly below this zone or if this zone
+    /// already has that child zone
+    pub fn child(&mut self, child: ZoneSpec) -> &mut Self {
+        assert_eq!(
+            Some(&self.zone),
+            child.zone.parent().as_ref(),
+            "{} is not a child zone of {}",
+            child.zone,
+            self.zone,
+        );
+        assert!(
+            self.children.iter().all(|spec| spec.zone != child.zone),
+            "zone {} appears more than once",
+            child.zone,
+        );
+
+        self.children.push(child);
+        self
+    }
+
+    /// Overrides how this zone is signed
+    pub fn signing(&mut self, signing: Signing) -> &mut Self {
+        self.signing = Some(signing);
+        self
+    }
+
+    /// Overrides the `Implementation` of the name server that will have authority over this zone
+    pub fn implementation(&mut self, implementation: Implementation) -> &mut Self {
+        self.implementation = Some(implementation);
+        self
+    }
+
+    /// Adds a secondary name server to the zone; see `NameServer::add_secondary`
+    ///
+    /// `None` means that the secondary uses the same `Implementation` as the zone's primary name
+    /// server
+    pub fn secondary(
+        &mut self,
+        implementation: Option<Implementation>,
+        replication: Replication,
+    ) -> &mut Self {
+        self.secondaries.push((implementation, replication));
+        self
+    }
+
+    pub fn zone(&self) -> &FQDN {
+        &self.zone
+    }
+
+    fn contains(&self, zone: &FQDN) -> bool {
+        self.zone == *zone || self.children.iter().any(|child| child.contains(zone))
+    }
+
+    /// collects this zone and all the zones below it; the collected specs have no children
+    fn flatten(mut self, specs: &mut Vec<ZoneSpec>) {
+        let children = core::mem::take(&mut self.children);
+        specs.push(self);
+        for child in children {
+            child.flatten(specs);
+        }
+    }
 }
 
 impl Graph {
     /// Builds up a minimal DNS graph from `leaf` up to a root name server and returns all the
     /// name servers in the graph
     ///
-    /// All new name servers will share the `Implementation` of `leaf`.
+    /// `leaf` can be any zone, e.g. `a.b.example.org.` or a TLD like `org.`; a name server will be
+    /// created for each of its ancestor zones. The graph also covers the `nameservers.com.` zone
+    /// (and its ancestors) as that's where the FQDNs of the root and `com.` name servers live.
     ///
-    /// The returned name servers are sorted from leaf zone to root zone.
+    /// All new name servers will share the `Implementation` of `leaf`. Use `Graph::from_spec` to
+    /// pick a different `Implementation` for each zone.
     ///
     /// both `Sign::Yes` and `Sign::AndAmend` will add a DS record with the hash of the child's
     /// key to the parent's zone file
     ///
-    /// a non-empty `TrustAnchor` is returned only when `Sign::Yes` or `Sign::AndAmend` is used
+    /// a non-empty `TrustAnchor` is returned only when `Sign::Yes` or `Sign::AndAmend` is used.
+    /// The trust anchor contains the keys of the root zone
     pub fn build(leaf: NameServer<Stopped>, sign: Sign) -> Result<Self> {
-        assert_eq!(2, leaf.zone().num_labels(), "not yet implemented");
-        assert_eq!(Some(FQDN::COM), leaf.zone().parent(), "not yet implemented");
-
-        // first pass: create nameservers for parent zones
-        let mut zone = leaf.zone().clone();
         let network = leaf.container.network().clone();
         let implementation = leaf.implementation.clone();
 
-        let (mut nameservers_ns, leaf) = if leaf.zone() != &FQDN::NAMESERVERS {
-            let nameservers_ns = NameServer::new(&implementation, FQDN::NAMESERVERS, &network)?;
-            (nameservers_ns, Some(leaf))
-        } else {
-            (leaf, None)
-        };
+        let mut zones = vec![];
+        for start in [leaf.zone().clone(), FQDN::NAMESERVERS] {
+            let mut zone = Some(start);
+            while let Some(current) = zone {
+                zone = current.parent();
+                if !zones.contains(&current) {
+                    zones.push(current);
+                }
+            }
+        }
 
-        // the nameserver covering `FQDN::NAMESERVERS` needs A records about all the nameservers in the graph
         let mut nameservers = vec![];
-        while let Some(parent) = zone.parent() {
-            let nameserver = NameServer::new(&implementation, parent.clone(), &network)?;
+        for zone in zones {
+            if &zone != leaf.zone() {
+                nameservers.push(NameServer::new(&implementation, zone, &network)?);
+            }
+        }
+        nameservers.push(leaf);
 
-            nameservers_ns.add(nameserver.a());
-            nameservers.push(nameserver);
+        Self::wire(nameservers, BTreeMap::new(), sign)
+    }
 
-            zone = parent;
+    /// Builds the DNS graph described by `spec`, a tree of zones that starts at the root zone,
+    /// and returns all the name servers in the graph
+    ///
+    /// One name server will be created for each zone in the tree, plus the secondaries requested
+    /// with `ZoneSpec::secondary`. The name server will use the given `implementation` unless the
+    /// zone overrides it with `ZoneSpec::implementation`.
+    /// If `spec` does not include the `com.` and `nameservers.com.` zones, they'll be added to the
+    /// graph as that's where the FQDNs of the root and `com.` name servers live.
+    ///
+    /// Referrals (NS + A record pairs) from parent to child zones, one per name server of the
+    /// child zone, are added to the zone files.
+    /// `sign` works as in `Graph::build` but it can be overridden for each zone with
+    /// `ZoneSpec::signing`.
+    ///
+    /// The returned `TrustAnchor` contains the keys of every signed zone that's either the root
+    /// zone or the child of an unsigned zone, i.e. the keys at the top of each "island of
+    /// security". A zone signed with `Signing::SignedWithoutDs` under a signed parent is not part
+    /// of the trust anchor; validating resolvers should treat it as insecure.
+    pub fn from_spec(
+        mut spec: ZoneSpec,
+        implementation: &Implementation,
+        network: &Network,
+        sign: Sign,
+    ) -> Result<Self> {
+        if spec.zone != FQDN::ROOT {
+            return Err(Error::InvalidInput(format!(
+                "the spec must start at the root zone, not {}",
+                spec.zone
+            )));
         }
-        drop((network, implementation));
 
-        if let Some(leaf) = leaf {
-            nameservers.insert(0, leaf);
+        if !spec.contains(&FQDN::COM) {
+            spec.child(ZoneSpec::new(FQDN::COM));
         }
-        nameservers.insert(0, nameservers_ns);
-
-        // second pass: add referrals from parent to child
-        // the nameservers are sorted leaf-most zone first but siblings may be next to each other
-        // for each child (e.g. `nameservers.com.`), do a linear search for its parent (`com.`)
-        for index in 1..nameservers.len() {
-            let (left, right) = nameservers.split_at_mut(index);
-            let child = left.last_mut().unwrap();
-            for maybe_parent in right {
-                if Some(maybe_parent.zone()) == child.zone().parent().as_ref() {
-                    let parent = maybe_parent;
-                    parent.referral_nameserver(child);
-                    break;
+
+        if !spec.contains(&FQDN::NAMESERVERS) {
+            let com = spec
+                .children
+                .iter_mut()
+                .find(|child| child.zone == FQDN::COM)
+                .expect("unreachable");
+            com.child(ZoneSpec::new(FQDN::NAMESERVERS));
+        }
+
+        let mut specs = vec![];
+        spec.flatten(&mut specs);
+
+        let mut nameservers = vec![];
+        let mut signings = BTreeMap::new();
+        for spec in specs {
+            if let Some(signing) = spec.signing {
+                signings.insert(spec.zone.clone(), signing);
+            }
+
+            let implementation = spec.implementation.as_ref().unwrap_or(implementation);
+            let mut nameserver = NameServer::new(implementation, spec.zone, network)?;
+            for record in spec.records {
+                nameserver.add(record);
+            }
+            for (secondary, replication) in spec.secondaries {
+                let secondary = secondary.as_ref().unwrap_or(implementation);
+                nameserver.add_secondary(secondary, replication)?;
+            }
+            nameservers.push(nameserver);
+        }
+
+        Self::wire(nameservers, signings, sign)
+    }
+
+    /// Replaces the DS RRset of `zone`, in the zone file of its parent, with `ds_rrset` and then
+    /// re-signs and reloads the parent zone; see `NameServer::update`
+    ///
+    /// This is the step of a KSK or algorithm rollover that moves the chain of trust to the new
+    /// keys. An empty `ds_rrset` turns `zone` into an insecure delegation
+    pub fn replace_ds(&mut self, zone: &FQDN, ds_rrset: Vec<DS>) -> Result<()> {
+        let parent = zone
+            .parent()
+            .and_then(|parent| self.nameservers.get_mut(&parent))
+            .ok_or_else(|| {
+                Error::InvalidInput(format!("the parent zone of {zone} is not in the graph"))
+            })?;
+
+        parent.update(|zone_file| {
+            zone_file
+                .records
+                .retain(|record| !matches!(record, Record::DS(ds) if ds.zone == *zone));
+            for ds in ds_rrset {
+                zone_file.add(ds);
+            }
+        })
+    }
+
+    /// adds referrals, glue and DS records to the zone files of `nameservers` and then starts them
+    ///
+    /// `nameservers` must contain a name server for the parent of each of its zones. Each zone is
+    /// signed according to its entry in `signings` or, if it has none, according to `sign`
+    fn wire(
+        mut nameservers: Vec<NameServer<Stopped>>,
+        signings: BTreeMap<FQDN, Signing>,
+        sign: Sign,
+    ) -> Result<Self> {
+        // sort leaf-most zones first; this ensures that children get signed before their parents
+        nameservers.sort_by_key(|nameserver| Reverse(nameserver.zone().num_labels()));
+
+        // add referrals from parent to child
+        for child in 0..nameservers.len() {
+            let Some(parent_zone) = nameservers[child].zone().parent() else {
+                continue;
+            };
+
+            let parent = nameservers
+                .iter()
+                .position(|nameserver| nameserver.zone() == &parent_zone)
+                .expect("unreachable");
+
+            let zone = nameservers[child].zone().clone();
+            for (fqdn, ipv4_addr) in nameservers[child].servers() {
+                nameservers[parent].referral(zone.clone(), fqdn, ipv4_addr);
+            }
+        }
+
+        // the zone that has authority over a name server's FQDN needs its A record. for example,
+        // the nameserver covering `FQDN::NAMESERVERS` needs A records about the root and `com.`
+        // name servers
+        for index in 0..nameservers.len() {
+            for (fqdn, ipv4_addr) in nameservers[index].servers() {
+                let authority = nameservers
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, nameserver)| fqdn.is_subdomain_of(nameserver.zone()))
+                    .max_by_key(|(_, nameserver)| nameserver.zone().num_labels())
+                    .map(|(authority, _)| authority)
+                    .expect("unreachable");
+
+                if authority != index {
+                    nameservers[authority].add(Record::a(fqdn, ipv4_addr));
                 }
             }
         }
 
         let root = nameservers.last().unwrap().root_hint();
 
-        // start name servers
-        let (nameservers, trust_anchor) = match sign {
-            Sign::No => (
-                nameservers
-                    .into_iter()
-                    .map(|nameserver| nameserver.start())
-                    .collect::<Result<_>>()?,
-                None,
-            ),
-
-            _ => {
-                let mut trust_anchor = TrustAnchor::empty();
-                let maybe_mutate = match sign {
-                    Sign::No => unreachable!(),
-                    Sign::Yes => None,
-                    Sign::AndAmend(f) => Some(f),
-                };
-
-                let mut running = vec![];
-                let mut children_ds = vec![];
-                let mut children_num_labels = 0;
-                let len = nameservers.len();
-                for (index, mut nameserver) in nameservers.into_iter().enumerate() {
-                    if !children_ds.is_empty() {
-                        let is_parent = nameserver.zone().num_labels() + 1 == children_num_labels;
-                        if is_parent {
-                            for ds in children_ds.drain(..) {
-                                nameserver.add(ds);
-                            }
-                        }
-                    }
+        let (default_signing, settings, maybe_mutate) = match sign {
+            Sign::No => (Signing::Unsigned, SignSettings::default(), None),
+            Sign::Yes { settings } => (Signing::Signed, settings, None),
+            Sign::AndAmend { settings, mutate } => (Signing::Signed, settings, Some(mutate)),
+        };
+        let signing_of = |zone: &FQDN| signings.get(zone).copied().unwrap_or(default_signing);
+
+        // sign and start name servers
+        let mut trust_anchor = TrustAnchor::empty();
+        let mut running = BTreeMap::new();
+        let mut children_ds: Vec<DS> = vec![];
+        for mut nameserver in nameservers {
+            let mut index = 0;
+            while index < children_ds.len() {
+                if children_ds[index].zone.parent().as_ref() == Some(nameserver.zone()) {
+                    nameserver.add(children_ds.remove(index));
+                } else {
+                    index += 1;
+                }
+            }
 
-                    let mut nameserver = nameserver.sign()?;
+            let zone = nameserver.zone().clone();
+            let signing = signing_of(&zone);
+            let nameserver = if signing == Signing::Unsigned {
+                nameserver.start()?
+            } else {
+                let mut nameserver = nameserver.sign(settings.clone())?;
+                if signing == Signing::Signed {
                     children_ds.push(nameserver.ds().clone());
-                    children_num_labels = nameserver.zone().num_labels();
-                    if let Some(mutate) = maybe_mutate {
-                        let zone = nameserver.zone().clone();
-                        mutate(&zone, &mut nameserver.signed_zone_file_mut().records);
-                    }
+                }
 
-                    if index == len - 1 {
-                        // the last nameserver covers `.`
-                        trust_anchor.add(nameserver.key_signing_key().clone());
-                        trust_anchor.add(nameserver.zone_signing_key().clone());
-                    }
+                if let Some(mutate) = maybe_mutate {
+                    mutate(&zone, &mut nameserver.signed_zone_file_mut().records);
+                }
 
-                    running.push(nameserver.start()?);
+                // top of an island of security
+                let is_anchored = zone
+                    .parent()
+                    .is_none_or(|parent| signing_of(&parent) == Signing::Unsigned);
+                if is_anchored {
+                    let ksk = nameserver.key_signing_key().clone();
+                    let zsk = nameserver.zone_signing_key().clone();
+                    // with a CSK both are the same key
+                    if zsk.public_key != ksk.public_key {
+                        trust_anchor.add(zsk);
+                    }
+                    trust_anchor.add(ksk);
                 }
 
-                (running, Some(trust_anchor))
-            }
+                nameserver.start()?
+            };
+
+            running.insert(zone, nameserver);
+        }
+
+        let nameservers = running;
+        let trust_anchor = if trust_anchor.is_empty() {
+            None
+        } else {
+            Some(trust_anchor)
         };
 
         Ok(Graph {
@@ -147,8 +500,11 @@ impl Graph {
 
 pub struct NameServer<State> {
     container: Container,
+    fqdn: FQDN,
     implementation: Implementation,
     state: State,
+    transfer_key: Option<TsigKey>,
+    update_policy: Option<UpdatePolicy>,
     zone_file: ZoneFile,
 }
 
@@ -164,7 +520,7 @@ impl NameServer<Stopped> {
     ///
     /// - one SOA record, with the primary name server field set to this name server's FQDN
     /// - one NS record, with this name server's FQDN set as the only available name server for
-    /// the zone
+    ///   the zone
     pub fn new(implementation: &Implementation, zone: FQDN, network: &Network) -> Result<Self> {
         let ns_count = ns_count();
         let nameserver = primary_ns(ns_count, &zone);
@@ -186,12 +542,72 @@ impl NameServer<Stopped> {
 
         Ok(Self {
             container,
+            fqdn: nameserver,
             implementation: implementation.clone(),
             zone_file,
-            state: Stopped,
+            transfer_key: None,
+            update_policy: None,
+            state: Stopped {
+                primary: None,
+                secondaries: vec![],
+            },
         })
     }
 
+    /// Spins up a secondary name server for this server's zone and lists it in the zone's NS
+    /// RRset
+    ///
+    /// The FQDN of the secondary server will have the form `secondary{count}.nameservers.com.`.
+    /// The secondary server serves the zone file this server ends up serving, signed or not; it
+    /// gets it as specified by `replication`. The secondary is started, after this server, and
+    /// stopped together with this server
+    ///
+    /// # Panics
+    ///
+    /// hickory does not support `Replication::ZoneTransfer`; `start` panics in that case
+    pub fn add_secondary(
+        &mut self,
+        implementation: &Implementation,
+        replication: Replication,
+    ) -> Result<()> {
+        let zone = self.zone().clone();
+        let nameserver = secondary_ns(ns_count(), &zone);
+        let image = implementation.clone().into();
+        let container = Container::run(&image, self.container.network())?;
+
+        self.zone_file.add(Record::ns(zone, nameserver.clone()));
+        self.zone_file
+            .add(Record::a(nameserver.clone(), container.ipv4_addr()));
+
+        self.state.secondaries.push(NameServer {
+            container,
+            fqdn: nameserver,
+            implementation: implementation.clone(),
+            // replaced with the primary's zone file on `start`
+            zone_file: self.zone_file.clone(),
+            transfer_key: None,
+            update_policy: None,
+            state: Stopped {
+                primary: match replication {
+                    Replication::Copy => None,
+                    Replication::ZoneTransfer => Some(self.ipv4_addr()),
+                },
+                secondaries: vec![],
+            },
+        });
+
+        Ok(())
+    }
+
+    /// The FQDNs and addresses of all the name servers of the zone: this one and its secondaries
+    fn servers(&self) -> Vec<(FQDN, Ipv4Addr)> {
+        let mut servers = vec![(self.fqdn().clone(), self.ipv4_addr())];
+        for secondary in &self.state.secondaries {
+            servers.push((secondary.fqdn().clone(), secondary.ipv4_addr()));
+        }
+        servers
+    }
+
     /// Adds a NS + A record pair to the zone file
     pub fn referral(&mut self, zone: FQDN, nameserver: FQDN, ipv4_addr: Ipv4Addr) -> &mut Self {
         self.zone_file.referral(zone, nameserver, ipv4_addr);
@@ -213,66 +629,99 @@ impl NameServer<Stopped> {
         self
     }
 
-    /// Freezes and signs the name server's zone file
-    pub fn sign(self) -> Result<NameServer<Signed>> {
-        // TODO do we want to make these settings configurable?
-        const ZSK_BITS: usize = 1024;
-        const KSK_BITS: usize = 2048;
-        const ALGORITHM: &str = "RSASHA1-NSEC3-SHA1";
+    /// Makes the name server only serve zone transfers signed with `key` (TSIG). The secondaries
+    /// that use zone transfers sign their requests with `key`
+    ///
+    /// # Panics
+    ///
+    /// hickory does not support TSIG; `start` panics in that case
+    pub fn transfer_key(&mut self, key: TsigKey) -> &mut Self {
+        self.transfer_key = Some(key);
+        self
+    }
+
+    /// Makes the name server accept the dynamic updates (RFC2136) allowed by `policy`
+    ///
+    /// If the zone is signed, the name server signs the updated records with the zone's keys,
+    /// which must be generated with `SigningTool::Ldns`. The changes made through dynamic updates
+    /// are not reflected in `zone_file` and they may be lost if the zone is changed through
+    /// `NameServer<Running>::update`
+    ///
+    /// # Panics
+    ///
+    /// NSD does not support dynamic updates and hickory only supports them in unsigned zones and
+    /// without TSIG; `start` panics in those cases
+    pub fn allow_updates(&mut self, policy: UpdatePolicy) -> &mut Self {
+        self.update_policy = Some(policy);
+        self
+    }
 
+    /// Freezes and signs the name server's zone file
+    pub fn sign(self, settings: SignSettings) -> Result<NameServer<Signed>> {
         let Self {
             container,
+            fqdn,
             zone_file,
             implementation,
-            state: _,
+            state: Stopped {
+                primary: _,
+                secondaries,
+            },
+            transfer_key,
+            update_policy,
         } = self;
 
-        container.status_ok(&["mkdir", "-p", ZONES_DIR])?;
-        let zone_file_path = zone_file_path();
-        container.cp(&zone_file_path, &zone_file.to_string())?;
-
-        let zone = zone_file.origin();
-
-        let zsk_keygen =
-            format!("cd {ZONES_DIR} && ldns-keygen -a {ALGORITHM} -b {ZSK_BITS} {zone}");
-        let zsk_filename = container.stdout(&["sh", "-c", &zsk_keygen])?;
-        let zsk_path = format!("{ZONES_DIR}/{zsk_filename}.key");
-        let zsk: zone_file::DNSKEY = container.stdout(&["cat", &zsk_path])?.parse()?;
-
-        let ksk_keygen =
-            format!("cd {ZONES_DIR} && ldns-keygen -k -a {ALGORITHM} -b {KSK_BITS} {zone}");
-        let ksk_filename = container.stdout(&["sh", "-c", &ksk_keygen])?;
-        let ksk_path = format!("{ZONES_DIR}/{ksk_filename}.key");
-        let ksk: zone_file::DNSKEY = container.stdout(&["cat", &ksk_path])?.parse()?;
-
-        // -n = use NSEC3 instead of NSEC
-        // -p = set the opt-out flag on all nsec3 rrs
-        let signzone = format!(
-            "cd {ZONES_DIR} && ldns-signzone -n -p {ZONE_FILENAME} {zsk_filename} {ksk_filename}"
-        );
-        container.status_ok(&["sh", "-c", &signzone])?;
-
-        // TODO do we want to make the hashing algorithm configurable?
-        // -2 = use SHA256 for the DS hash
-        let key2ds = format!("cd {ZONES_DIR} && ldns-key2ds -n -2 {ZONE_FILENAME}.signed");
-        let ds: DS = container.stdout(&["sh", "-c", &key2ds])?.parse()?;
-
-        let signed: ZoneFile = container
-            .stdout(&["cat", &format!("{zone_file_path}.signed")])?
-            .parse()?;
+        let roles: &[_] = match settings.key_scheme {
+            KeyScheme::Split => &[KeyRole::KeySigning, KeyRole::ZoneSigning],
+            KeyScheme::Combined => &[KeyRole::Combined],
+        };
+        let keys = roles
+            .iter()
+            .map(|role| {
+                generate_key(
+                    &container,
+                    &zone_file,
+                    &settings,
+                    *role,
+                    settings.algorithm,
+                    KeyState::Active,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let signed = sign_zone_file(&container, &zone_file, &settings, &keys)?;
+
+        let ds = match settings.tool {
+            SigningTool::Ldns => {
+                // -1 = use SHA1 for the DS hash
+                // -2 = use SHA256 for the DS hash
+                // -4 = use SHA384 for the DS hash
+                let digest = match settings.ds_digest {
+                    DigestType::SHA1 => "-1",
+                    DigestType::SHA256 => "-2",
+                    DigestType::SHA384 => "-4",
+                };
+                let key2ds =
+                    format!("cd {ZONES_DIR} && ldns-key2ds -n {digest} {ZONE_FILENAME}.signed");
+                container.stdout(&["sh", "-c", &key2ds])?.parse()?
+            }
 
-        let ttl = zone_file.soa.ttl;
+            SigningTool::InProcess => keys[0].dnskey.ds(settings.ds_digest)?,
+        };
 
         Ok(NameServer {
             container,
+            fqdn,
             implementation,
             zone_file,
+            transfer_key,
+            update_policy,
             state: Signed {
                 ds,
+                keys,
+                secondaries,
+                settings,
                 signed,
-                // inherit SOA's TTL value
-                ksk: ksk.with_ttl(ttl),
-                zsk: zsk.with_ttl(ttl),
             },
         })
     }
@@ -281,13 +730,25 @@ impl NameServer<Stopped> {
     pub fn start(self) -> Result<NameServer<Running>> {
         let Self {
             container,
+            fqdn,
             zone_file,
             implementation,
-            state: _,
+            state: Stopped {
+                primary,
+                secondaries,
+            },
+            transfer_key,
+            update_policy,
         } = self;
 
+        let notify = notified_secondaries(&secondaries);
         let config = Config::NameServer {
             origin: zone_file.origin(),
+            primary,
+            notify: &notify,
+            transfer_key: transfer_key.as_ref(),
+            update_policy: update_policy.as_ref(),
+            resign: false,
         };
 
         container.cp(
@@ -296,15 +757,30 @@ impl NameServer<Stopped> {
         )?;
 
         container.status_ok(&["mkdir", "-p", ZONES_DIR])?;
-        container.cp(&zone_file_path(), &zone_file.to_string())?;
+        if primary.is_some() {
+            // the name server writes the transferred zone into this directory; NSD does so after
+            // dropping its privileges
+            container.status_ok(&["chmod", "777", ZONES_DIR])?;
+        } else {
+            container.cp(&zone_file_path(), &zone_file.to_string())?;
+        }
 
         let child = container.spawn(implementation.cmd_args(config.role()))?;
+        let secondaries = start_secondaries(secondaries, &zone_file, transfer_key.as_ref())?;
 
         Ok(NameServer {
             container,
+            fqdn,
             implementation,
             zone_file,
-            state: Running { child },
+            transfer_key,
+            update_policy,
+            state: Running {
+                child,
+                primary,
+                secondaries,
+                signed: None,
+            },
         })
     }
 }
@@ -316,6 +792,205 @@ fn zone_file_path() -> String {
     format!("{ZONES_DIR}/{ZONE_FILENAME}")
 }
 
+// options of `ldns-signzone`; these are passed to a shell so the salt must be validated
+fn ldns_signzone_args(settings: &SignSettings) -> Result<String> {
+    // -n = use NSEC3 instead of NSEC
+    // -t = number of hash iterations
+    // -s = salt, hex encoded
+    // -p = set the opt-out flag on all nsec3 rrs
+    let mut args = match &settings.nsec {
+        Nsec::_1 => String::new(),
+        Nsec::_3 {
+            iterations,
+            opt_out,
+            salt,
+        } => {
+            let mut args = format!("-n -t {iterations}");
+            if let Some(salt) = salt {
+                record::check_salt(salt)?;
+                if !salt.is_empty() && salt != "-" {
+                    args.push_str(&format!(" -s {salt}"));
+                }
+            }
+            if *opt_out {
+                args.push_str(" -p");
+            }
+            args
+        }
+    };
+    // -i = inception date, in seconds since the UNIX epoch
+    // -e = expiration date, in seconds since the UNIX epoch
+    if let Some(inception) = settings.inception {
+        args.push_str(&format!(" -i {inception}"));
+    }
+    if let Some(expiration) = settings.expiration {
+        args.push_str(&format!(" -e {expiration}"));
+    }
+
+    Ok(args)
+}
+
+/// Generates a new key for the zone using the tool selected in `settings`
+fn generate_key(
+    container: &Container,
+    zone_file: &ZoneFile,
+    settings: &SignSettings,
+    role: KeyRole,
+    algorithm: Algorithm,
+    state: KeyState,
+) -> Result<ZoneKey> {
+    let zone = zone_file.origin();
+    // inherit SOA's TTL value
+    let ttl = zone_file.soa.ttl;
+
+    let (dnskey, private_key) = match settings.tool {
+        SigningTool::Ldns => {
+            // -k = generate a key signing key; `ldns-signzone` signs all RRsets with the KSKs
+            //      when it's given no ZSK so a CSK is also generated with this flag
+            // -b = key size in bits; ldns-keygen only accepts it for RSA keys
+            let (ksk, bits) = match role {
+                KeyRole::KeySigning | KeyRole::Combined => ("-k ", settings.ksk_bits),
+                KeyRole::ZoneSigning => ("", settings.zsk_bits),
+            };
+            let bits = if algorithm.is_rsa() {
+                format!(" -b {bits}")
+            } else {
+                String::new()
+            };
+
+            container.status_ok(&["mkdir", "-p", ZONES_DIR])?;
+            let keygen = format!("cd {ZONES_DIR} && ldns-keygen {ksk}-a {algorithm}{bits} {zone}");
+            let filename = container.stdout(&["sh", "-c", &keygen])?;
+            let path = format!("{ZONES_DIR}/{filename}.key");
+            let dnskey: zone_file::DNSKEY = container.stdout(&["cat", &path])?.parse()?;
+
+            (dnskey.with_ttl(ttl), PrivateKey::Ldns(filename))
+        }
+
+        SigningTool::InProcess => {
+            let key = match role {
+                KeyRole::KeySigning => SigningKey::key_signing_key(zone.clone(), algorithm)?,
+                KeyRole::ZoneSigning => SigningKey::zone_signing_key(zone.clone(), algorithm)?,
+                KeyRole::Combined => SigningKey::combined_signing_key(zone.clone(), algorithm)?,
+            };
+
+            let dnskey = record::DNSKEY {
+                ttl,
+                ..key.dnskey().clone()
+            };
+            (dnskey, PrivateKey::InProcess(key))
+        }
+    };
+
+    Ok(ZoneKey {
+        dnskey,
+        role,
+        state,
+        private_key,
+    })
+}
+
+/// Signs `zone_file` with the `Active` keys and publishes all the others
+fn sign_zone_file(
+    container: &Container,
+    zone_file: &ZoneFile,
+    settings: &SignSettings,
+    keys: &[ZoneKey],
+) -> Result<ZoneFile> {
+    let is_active = |key: &&ZoneKey| key.state == KeyState::Active;
+    let has_ksk = keys
+        .iter()
+        .filter(is_active)
+        .any(|key| key.role.signs_keys());
+    let has_zsk = keys
+        .iter()
+        .filter(is_active)
+        .any(|key| key.role.signs_zone());
+    if !has_ksk || !has_zsk {
+        return Err(Error::MissingKeys {
+            zone: Some(zone_file.origin().clone()),
+        });
+    }
+
+    match settings.tool {
+        SigningTool::Ldns => sign_with_ldns(container, zone_file, settings, keys),
+        SigningTool::InProcess => sign_in_process(zone_file, settings, keys),
+    }
+}
+
+fn sign_with_ldns(
+    container: &Container,
+    zone_file: &ZoneFile,
+    settings: &SignSettings,
+    keys: &[ZoneKey],
+) -> Result<ZoneFile> {
+    // keys that don't sign are added to the zone file as plain DNSKEY records
+    let mut unsigned = zone_file.clone();
+    let mut filenames = vec![];
+    for key in keys {
+        let PrivateKey::Ldns(filename) = &key.private_key else {
+            return Err(Error::InvalidInput(
+                "key was not generated by `ldns-keygen`".to_string(),
+            ));
+        };
+
+        if key.state == KeyState::Active {
+            filenames.push(filename.as_str());
+        } else {
+            unsigned.add(key.dnskey.clone());
+        }
+    }
+
+    container.status_ok(&["mkdir", "-p", ZONES_DIR])?;
+    let zone_file_path = zone_file_path();
+    container.cp(&zone_file_path, &unsigned.to_string())?;
+
+    let args = ldns_signzone_args(settings)?;
+    // the DNSKEY RRset is signed with the KSKs; all other RRsets are signed with the ZSKs
+    let filenames = filenames.join(" ");
+    let signzone = format!("cd {ZONES_DIR} && ldns-signzone {args} {ZONE_FILENAME} {filenames}");
+    container.status_ok(&["sh", "-c", &signzone])?;
+
+    container
+        .stdout(&["cat", &format!("{zone_file_path}.signed")])?
+        .parse()
+}
+
+fn sign_in_process(
+    zone_file: &ZoneFile,
+    settings: &SignSettings,
+    keys: &[ZoneKey],
+) -> Result<ZoneFile> {
+    let mut active = vec![];
+    let mut published = vec![];
+    for key in keys {
+        let PrivateKey::InProcess(signing_key) = &key.private_key else {
+            return Err(Error::InvalidInput(
+                "key was not generated by the in-process signer".to_string(),
+            ));
+        };
+
+        if key.state == KeyState::Active {
+            active.push(signing_key.clone());
+        } else {
+            published.push(key.dnskey.clone());
+        }
+    }
+
+    let mut signer = Signer::with_keys(active, settings.nsec.clone())?;
+    for dnskey in published {
+        signer.publish(dnskey);
+    }
+    if let Some(inception) = settings.inception {
+        signer.inception = inception;
+    }
+    if let Some(expiration) = settings.expiration {
+        signer.expiration = expiration;
+    }
+
+    signer.sign_zone(zone_file)
+}
+
 fn ns_count() -> usize {
     static COUNT: AtomicUsize = AtomicUsize::new(0);
     COUNT.fetch_add(1, atomic::Ordering::Relaxed)
@@ -326,36 +1001,106 @@ impl NameServer<Signed> {
     pub fn start(self) -> Result<NameServer<Running>> {
         let Self {
             container,
+            fqdn,
             zone_file,
             implementation,
-            state,
+            mut state,
+            transfer_key,
+            update_policy,
         } = self;
 
+        // the private keys of the in-process signer cannot be handed to the name server
+        assert!(
+            update_policy.is_none() || state.settings.tool == SigningTool::Ldns,
+            "dynamic updates of signed zones require keys generated by `SigningTool::Ldns`"
+        );
+
+        let notify = notified_secondaries(&state.secondaries);
         let config = Config::NameServer {
             origin: zone_file.origin(),
+            primary: None,
+            notify: &notify,
+            transfer_key: transfer_key.as_ref(),
+            update_policy: update_policy.as_ref(),
+            resign: update_policy.is_some(),
         };
         container.cp(
             implementation.conf_file_path(config.role()),
             &implementation.format_config(config),
         )?;
+        container.status_ok(&["mkdir", "-p", ZONES_DIR])?;
         container.cp(&zone_file_path(), &state.signed.to_string())?;
 
         let child = container.spawn(implementation.cmd_args(config.role()))?;
+        let secondaries = start_secondaries(
+            mem::take(&mut state.secondaries),
+            &state.signed,
+            transfer_key.as_ref(),
+        )?;
 
         Ok(NameServer {
             container,
+            fqdn,
             implementation,
             zone_file,
-            state: Running { child },
+            transfer_key,
+            update_policy,
+            state: Running {
+                child,
+                primary: None,
+                secondaries,
+                signed: Some(state),
+            },
         })
     }
 
+    /// The first active KSK; the CSK when the zone is signed with a combined key
     pub fn key_signing_key(&self) -> &record::DNSKEY {
-        &self.state.ksk
+        self.state.active_key(KeyRole::KeySigning)
     }
 
+    /// The first active ZSK; the CSK when the zone is signed with a combined key
     pub fn zone_signing_key(&self) -> &record::DNSKEY {
-        &self.state.zsk
+        self.state.active_key(KeyRole::ZoneSigning)
+    }
+
+    /// All the keys of the zone, including the ones that are not used for signing
+    pub fn keys(&self) -> &[ZoneKey] {
+        &self.state.keys
+    }
+
+    /// Generates a new key and re-signs the zone file
+    ///
+    /// Re-signing discards the changes made through `signed_zone_file_mut`
+    pub fn add_key(
+        &mut self,
+        role: KeyRole,
+        algorithm: Algorithm,
+        state: KeyState,
+    ) -> Result<record::DNSKEY> {
+        self.state
+            .add_key(&self.container, &self.zone_file, role, algorithm, state)
+    }
+
+    /// Changes the state of the key with the given key tag and re-signs the zone file
+    pub fn set_key_state(&mut self, key_tag: u16, state: KeyState) -> Result<()> {
+        self.state
+            .set_key_state(&self.container, &self.zone_file, key_tag, state)
+    }
+
+    /// Sets the REVOKE bit (RFC5011) of the key with the given key tag and re-signs the zone
+    /// file. The key keeps signing the DNSKEY RRset
+    ///
+    /// Returns the revoked key, which has a different key tag
+    pub fn revoke_key(&mut self, key_tag: u16) -> Result<record::DNSKEY> {
+        self.state
+            .revoke_key(&self.container, &self.zone_file, key_tag)
+    }
+
+    /// Removes the key with the given key tag from the zone and re-signs the zone file
+    pub fn remove_key(&mut self, key_tag: u16) -> Result<()> {
+        self.state
+            .remove_key(&self.container, &self.zone_file, key_tag)
     }
 
     pub fn signed_zone_file(&self) -> &ZoneFile {
@@ -372,6 +1117,180 @@ impl NameServer<Signed> {
 }
 
 impl NameServer<Running> {
+    /// The secondary name servers of the zone
+    pub fn secondaries(&self) -> &[NameServer<Running>] {
+        &self.state.secondaries
+    }
+
+    /// The secondary name servers of the zone. Removing a secondary from the returned `Vec`
+    /// stops it
+    pub fn secondaries_mut(&mut self) -> &mut Vec<NameServer<Running>> {
+        &mut self.state.secondaries
+    }
+
+    /// The zone file that is being served, if the name server was started in the signed state
+    pub fn signed_zone_file(&self) -> Option<&ZoneFile> {
+        self.state.signed.as_ref().map(|signed| &signed.signed)
+    }
+
+    /// All the keys of the zone; empty if the name server was not started in the signed state
+    pub fn keys(&self) -> &[ZoneKey] {
+        self.state
+            .signed
+            .as_ref()
+            .map(|signed| signed.keys.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Changes the zone file, bumps its SOA serial, re-signs it, if the zone is signed, and
+    /// reloads the name server
+    ///
+    /// Secondaries that use zone transfers are notified of the change, except by hickory; the
+    /// other secondaries are reloaded with a copy of the new zone file. Re-signing discards the
+    /// changes made to the signed zone file through `signed_zone_file_mut` and `Sign::AndAmend`
+    pub fn update(&mut self, update: impl FnOnce(&mut ZoneFile)) -> Result<()> {
+        let mut zone_file = self.zone_file.clone();
+        update(&mut zone_file);
+        bump_serial(&mut zone_file);
+
+        if let Some(signed) = &mut self.state.signed {
+            signed.signed =
+                sign_zone_file(&self.container, &zone_file, &signed.settings, &signed.keys)?;
+        }
+        self.zone_file = zone_file;
+
+        self.reload()
+    }
+
+    /// Adds a record to the zone; see `update`
+    pub fn add(&mut self, record: impl Into<Record>) -> Result<()> {
+        let record = record.into();
+        self.update(|zone_file| zone_file.add(record))
+    }
+
+    /// Removes the records for which `remove` returns `true` from the zone; see `update`
+    ///
+    /// The SOA record can't be removed
+    pub fn remove(&mut self, mut remove: impl FnMut(&Record) -> bool) -> Result<()> {
+        self.update(|zone_file| zone_file.records.retain(|record| !remove(record)))
+    }
+
+    /// Generates a new key, re-signs the zone file and reloads the name server
+    pub fn add_key(
+        &mut self,
+        role: KeyRole,
+        algorithm: Algorithm,
+        state: KeyState,
+    ) -> Result<record::DNSKEY> {
+        self.change_keys(|container, zone_file, signed| {
+            signed.add_key(container, zone_file, role, algorithm, state)
+        })
+    }
+
+    /// Changes the state of the key with the given key tag, re-signs the zone file and reloads
+    /// the name server
+    pub fn set_key_state(&mut self, key_tag: u16, state: KeyState) -> Result<()> {
+        self.change_keys(|container, zone_file, signed| {
+            signed.set_key_state(container, zone_file, key_tag, state)
+        })
+    }
+
+    /// Sets the REVOKE bit (RFC5011) of the key with the given key tag, re-signs the zone file
+    /// and reloads the name server. The key keeps signing the DNSKEY RRset
+    ///
+    /// Returns the revoked key, which has a different key tag
+    pub fn revoke_key(&mut self, key_tag: u16) -> Result<record::DNSKEY> {
+        self.change_keys(|container, zone_file, signed| {
+            signed.revoke_key(container, zone_file, key_tag)
+        })
+    }
+
+    /// Removes the key with the given key tag from the zone, re-signs the zone file and reloads
+    /// the name server
+    pub fn remove_key(&mut self, key_tag: u16) -> Result<()> {
+        self.change_keys(|container, zone_file, signed| {
+            signed.remove_key(container, zone_file, key_tag)
+        })
+    }
+
+    // runs a key operation, which re-signs the zone file, and then reloads the name server
+    fn change_keys<T>(
+        &mut self,
+        operation: impl FnOnce(&Container, &ZoneFile, &mut Signed) -> Result<T>,
+    ) -> Result<T> {
+        let Some(signed) = &mut self.state.signed else {
+            return Err(Error::ZoneNotSigned {
+                zone: self.zone_file.origin().clone(),
+            });
+        };
+
+        // the SOA serial is bumped so that secondaries pick up the new key set; it's only kept if
+        // the operation succeeds
+        let mut zone_file = self.zone_file.clone();
+        bump_serial(&mut zone_file);
+        let output = operation(&self.container, &zone_file, signed)?;
+        self.zone_file = zone_file;
+
+        self.reload()?;
+        Ok(output)
+    }
+
+    /// Makes the name server serve the current (signed) zone file
+    ///
+    /// BIND and NSD re-read their zone files on SIGHUP, as with `rndc reload` and `nsd-control
+    /// reload`; hickory does not handle signals so it gets restarted
+    fn reload(&mut self) -> Result<()> {
+        let zone_file = match &self.state.signed {
+            Some(signed) => &signed.signed,
+            None => &self.zone_file,
+        };
+        self.container
+            .cp(&zone_file_path(), &zone_file.to_string())?;
+
+        // secondaries that use zone transfers pick up the changes on their own
+        let zone_file = zone_file.clone();
+        for secondary in &mut self.state.secondaries {
+            if secondary.state.primary.is_none() {
+                secondary.zone_file = zone_file.clone();
+                secondary.reload()?;
+            }
+        }
+
+        let role = Role::NameServer;
+        let pidfile = self.implementation.pidfile(role);
+        if matches!(self.implementation, Implementation::Hickory(_)) {
+            let kill = format!(
+                "pid=$(cat {pidfile})
+kill -TERM $pid
+while kill -0 $pid 2>/dev/null; do sleep 0.1; done"
+            );
+            self.container.status_ok(&["sh", "-c", &kill])?;
+
+            let child = self.container.spawn(self.implementation.cmd_args(role))?;
+            // hickory does not shut down gracefully; see `terminate`
+            let _ = mem::replace(&mut self.state.child, child).wait();
+        } else {
+            let hup = format!("kill -HUP $(cat {pidfile})");
+            self.container.status_ok(&["sh", "-c", &hup])?;
+        }
+
+        // reloading is asynchronous; wait until the new version of the zone is being served
+        let zone = zone_file.origin();
+        let serial = zone_file.soa.settings.serial;
+        // the serial is the 7th field of the SOA record in the answer section
+        let poll = format!(
+            "for _ in $(seq 100); do
+    drill @127.0.0.1 SOA {zone} \\
+        | awk '$4 == \"SOA\" && $7 == \"{serial}\" {{ found = 1 }} END {{ exit !found }}' \\
+        && exit 0
+    sleep 0.1
+done
+echo 'timed out waiting for serial {serial} of {zone}' >&2
+exit 1"
+        );
+        self.container.status_ok(&["sh", "-c", &poll])
+    }
+
     /// Starts a `tshark` instance that captures DNS messages flowing through this network node
     pub fn eavesdrop(&self) -> Result<Tshark> {
         self.container.eavesdrop()
@@ -394,9 +1313,8 @@ kill -TERM $(cat {pidfile})"
         // will still get some logs so we'll ignore the fact that it fails to shut down ...
         let is_hickory = matches!(self.implementation, Implementation::Hickory(_));
         if !is_hickory && !output.status.success() {
-            return Err(
-                format!("could not terminate the `{}` process", self.implementation).into(),
-            );
+            let command = self.implementation.cmd_args(Role::NameServer);
+            return Err(self.container.command_failed(command, output));
         }
 
         assert!(
@@ -416,6 +1334,14 @@ impl<S> NameServer<S> {
         self.container.ipv4_addr()
     }
 
+    /// Fakes the clock of the name server's container
+    ///
+    /// It must be changed before `sign` or `start` to affect, respectively, the default
+    /// validity period picked by `ldns-signzone` and the name server process
+    pub fn set_clock(&self, clock: Clock) -> Result<()> {
+        self.container.set_clock(clock)
+    }
+
     /// Zone file BEFORE signing
     pub fn zone_file(&self) -> &ZoneFile {
         &self.zone_file
@@ -426,7 +1352,7 @@ impl<S> NameServer<S> {
     }
 
     pub fn fqdn(&self) -> &FQDN {
-        &self.zone_file.soa.nameserver
+        &self.fqdn
     }
 
     /// Returns the [`Record::A`] record for this server.
@@ -440,23 +1366,252 @@ impl<S> NameServer<S> {
     }
 }
 
-pub struct Stopped;
+pub struct Stopped {
+    /// Address of the name server the zone is transferred from
+    primary: Option<Ipv4Addr>,
+    secondaries: Vec<NameServer<Stopped>>,
+}
 
 pub struct Signed {
     ds: DS,
-    zsk: record::DNSKEY,
-    ksk: record::DNSKEY,
+    keys: Vec<ZoneKey>,
+    secondaries: Vec<NameServer<Stopped>>,
+    settings: SignSettings,
     signed: ZoneFile,
 }
 
+impl Signed {
+    fn active_key(&self, role: KeyRole) -> &record::DNSKEY {
+        self.keys
+            .iter()
+            .filter(|key| {
+                key.state == KeyState::Active
+                    && match role {
+                        KeyRole::KeySigning => key.role.signs_keys(),
+                        KeyRole::ZoneSigning => key.role.signs_zone(),
+                        KeyRole::Combined => key.role == KeyRole::Combined,
+                    }
+            })
+            // revoked keys still sign but they are not used as trust anchors; keys dedicated to
+            // the requested role are preferred over a CSK
+            .min_by_key(|key| (key.dnskey.is_revoked(), key.role == KeyRole::Combined))
+            .map(|key| &key.dnskey)
+            // `sign_zone_file` rejects key sets that lack an active KSK or ZSK
+            .expect("unreachable")
+    }
+
+    fn add_key(
+        &mut self,
+        container: &Container,
+        zone_file: &ZoneFile,
+        role: KeyRole,
+        algorithm: Algorithm,
+        state: KeyState,
+    ) -> Result<record::DNSKEY> {
+        let key = generate_key(container, zone_file, &self.settings, role, algorithm, state)?;
+        let dnskey = key.dnskey.clone();
+
+        self.update_keys(container, zone_file, |keys| {
+            keys.push(key);
+            Ok(())
+        })?;
+
+        Ok(dnskey)
+    }
+
+    fn set_key_state(
+        &mut self,
+        container: &Container,
+        zone_file: &ZoneFile,
+        key_tag: u16,
+        state: KeyState,
+    ) -> Result<()> {
+        self.update_keys(container, zone_file, |keys| {
+            let index = key_index(keys, key_tag)?;
+            keys[index].state = state;
+            Ok(())
+        })
+    }
+
+    fn revoke_key(
+        &mut self,
+        container: &Container,
+        zone_file: &ZoneFile,
+        key_tag: u16,
+    ) -> Result<record::DNSKEY> {
+        let mut revoked = None;
+        self.update_keys(container, zone_file, |keys| {
+            let index = key_index(keys, key_tag)?;
+            let key = &mut keys[index];
+            key.dnskey.set_revoke_bit();
+
+            match &mut key.private_key {
+                // `ldns-signzone` takes the flags from the `.key` file
+                PrivateKey::Ldns(filename) => container.cp(
+                    &format!("{ZONES_DIR}/{filename}.key"),
+                    &format!("{}\n", key.dnskey),
+                )?,
+                PrivateKey::InProcess(signing_key) => signing_key.set_revoke_bit(),
+            }
+
+            revoked = Some(key.dnskey.clone());
+            Ok(())
+        })?;
+
+        Ok(revoked.expect("unreachable"))
+    }
+
+    fn remove_key(
+        &mut self,
+        container: &Container,
+        zone_file: &ZoneFile,
+        key_tag: u16,
+    ) -> Result<()> {
+        self.update_keys(container, zone_file, |keys| {
+            keys.remove(key_index(keys, key_tag)?);
+            Ok(())
+        })
+    }
+
+    // the key set is only changed if the zone file can be signed with the new set
+    fn update_keys(
+        &mut self,
+        container: &Container,
+        zone_file: &ZoneFile,
+        update: impl FnOnce(&mut Vec<ZoneKey>) -> Result<()>,
+    ) -> Result<()> {
+        let mut keys = self.keys.clone();
+        update(&mut keys)?;
+
+        self.signed = sign_zone_file(container, zone_file, &self.settings, &keys)?;
+        self.keys = keys;
+
+        Ok(())
+    }
+}
+
+// serial number arithmetic (RFC1982) wraps around
+fn bump_serial(zone_file: &mut ZoneFile) {
+    let serial = &mut zone_file.soa.settings.serial;
+    *serial = serial.wrapping_add(1);
+}
+
+fn key_index(keys: &[ZoneKey], key_tag: u16) -> Result<usize> {
+    for (index, key) in keys.iter().enumerate() {
+        if key.dnskey.key_tag()? == key_tag {
+            return Ok(index);
+        }
+    }
+
+    Err(Error::UnknownKeyTag { key_tag })
+}
+
+/// Role of a key in a signed zone
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyRole {
+    /// Key signing key (KSK); signs the DNSKEY RRset and is referred to by the parent's DS
+    KeySigning,
+    /// Zone signing key (ZSK); signs all the other RRsets
+    ZoneSigning,
+    /// Combined signing key (CSK); plays both roles
+    Combined,
+}
+
+impl KeyRole {
+    fn signs_keys(self) -> bool {
+        matches!(self, KeyRole::KeySigning | KeyRole::Combined)
+    }
+
+    fn signs_zone(self) -> bool {
+        matches!(self, KeyRole::ZoneSigning | KeyRole::Combined)
+    }
+}
+
+/// Stage of a key's lifecycle; see section 3 of RFC7583
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyState {
+    /// The key is in the DNSKEY RRset but signs nothing yet
+    Published,
+    /// The key is in the DNSKEY RRset and signs RRsets
+    Active,
+    /// The key is in the DNSKEY RRset but no longer signs anything
+    Retired,
+}
+
+/// A key of a signed zone
+#[derive(Clone)]
+pub struct ZoneKey {
+    dnskey: record::DNSKEY,
+    role: KeyRole,
+    state: KeyState,
+    private_key: PrivateKey,
+}
+
+impl ZoneKey {
+    /// The DNSKEY record; it has the same TTL as the zone's SOA record
+    pub fn dnskey(&self) -> &record::DNSKEY {
+        &self.dnskey
+    }
+
+    pub fn role(&self) -> KeyRole {
+        self.role
+    }
+
+    pub fn state(&self) -> KeyState {
+        self.state
+    }
+}
+
+#[derive(Clone)]
+enum PrivateKey {
+    /// Base name of the files produced by `ldns-keygen`, in `ZONES_DIR`
+    Ldns(String),
+    InProcess(SigningKey),
+}
+
 pub struct Running {
     child: Child,
+    primary: Option<Ipv4Addr>,
+    secondaries: Vec<NameServer<Running>>,
+    signed: Option<Signed>,
+}
+
+/// The addresses of the `secondaries` that use zone transfers
+fn notified_secondaries(secondaries: &[NameServer<Stopped>]) -> Vec<Ipv4Addr> {
+    secondaries
+        .iter()
+        .filter(|secondary| secondary.state.primary.is_some())
+        .map(|secondary| secondary.ipv4_addr())
+        .collect()
+}
+
+/// Starts `secondaries`; the ones that don't use zone transfers will serve a copy of `zone_file`
+/// and the ones that do will authenticate them with `transfer_key`, if any
+fn start_secondaries(
+    secondaries: Vec<NameServer<Stopped>>,
+    zone_file: &ZoneFile,
+    transfer_key: Option<&TsigKey>,
+) -> Result<Vec<NameServer<Running>>> {
+    secondaries
+        .into_iter()
+        .map(|mut secondary| {
+            secondary.zone_file = zone_file.clone();
+            if secondary.state.primary.is_some() {
+                secondary.transfer_key = transfer_key.cloned();
+            }
+            secondary.start()
+        })
+        .collect()
 }
 
 fn primary_ns(ns_count: usize, zone: &FQDN) -> FQDN {
     FQDN(format!("primary{ns_count}.{}", expand_zone(zone))).unwrap()
 }
 
+fn secondary_ns(ns_count: usize, zone: &FQDN) -> FQDN {
+    FQDN(format!("secondary{ns_count}.{}", expand_zone(zone))).unwrap()
+}
+
 fn admin_ns(ns_count: usize, zone: &FQDN) -> FQDN {
     FQDN(format!("admin{ns_count}.{}", expand_zone(zone))).unwrap()
 }
@@ -476,8 +1631,9 @@ mod tests {
     use std::thread;
     use std::time::Duration;
 
-    use crate::client::{Client, DigSettings};
+    use crate::client::{Client, DigSettings, DigStatus, TsigError, TsigStatus, Update};
     use crate::record::RecordType;
+    use crate::tsig::TsigAlgorithm;
     use crate::Repository;
 
     use super::*;
@@ -525,10 +1681,213 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn secondaries_serve_the_signed_zone() -> Result<()> {
+        let network = Network::new()?;
+        let mut ns = NameServer::new(&Implementation::Unbound, FQDN::ROOT, &network)?;
+        ns.add_secondary(&Implementation::Bind, Replication::Copy)?;
+        ns.add_secondary(&Implementation::Unbound, Replication::Copy)?;
+        let ns = ns.sign(SignSettings::default())?.start()?;
+
+        let nameservers = ns
+            .signed_zone_file()
+            .unwrap()
+            .records
+            .iter()
+            .filter_map(|record| match record {
+                Record::NS(ns) => Some(&ns.nameserver),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(3, nameservers.len());
+
+        let client = Client::new(&network)?;
+        let settings = *DigSettings::default().dnssec();
+        for secondary in ns.secondaries() {
+            assert!(nameservers.contains(&secondary.fqdn()));
+
+            let output = client.dig(
+                settings,
+                secondary.ipv4_addr(),
+                RecordType::SOA,
+                &FQDN::ROOT,
+            )?;
+            assert!(output.status.is_noerror());
+            assert!(output.answer.iter().any(|record| record.is_soa()));
+            assert!(output
+                .answer
+                .iter()
+                .any(|record| matches!(record, Record::RRSIG(..))));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn secondaries_transfer_the_zone() -> Result<()> {
+        let network = Network::new()?;
+        let needle_fqdn = FQDN("example.com.")?;
+        let mut ns = NameServer::new(&Implementation::Bind, FQDN::COM, &network)?;
+        ns.add(Record::a(needle_fqdn.clone(), Ipv4Addr::new(1, 2, 3, 4)));
+        ns.add_secondary(&Implementation::Unbound, Replication::ZoneTransfer)?;
+        ns.add_secondary(&Implementation::Bind, Replication::ZoneTransfer)?;
+        let ns = ns.start()?;
+
+        let client = Client::new(&network)?;
+        for secondary in ns.secondaries() {
+            // give the secondary some time to complete the transfer
+            let mut output = None;
+            for _ in 0..10 {
+                let answer = client.dig(
+                    DigSettings::default(),
+                    secondary.ipv4_addr(),
+                    RecordType::A,
+                    &needle_fqdn,
+                )?;
+                if answer.status.is_noerror() && !answer.answer.is_empty() {
+                    output = Some(answer);
+                    break;
+                }
+                thread::sleep(Duration::from_millis(500));
+            }
+
+            let output = output.expect("secondary did not transfer the zone");
+            assert!(output.flags.authoritative_answer);
+
+            let zone_file = client.axfr(secondary.ipv4_addr(), &FQDN::COM)?;
+            assert_eq!(
+                ns.zone_file().soa.settings.serial,
+                zone_file.soa.settings.serial
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn zone_transfers_authenticated_with_tsig() -> Result<()> {
+        let network = Network::new()?;
+        let needle_fqdn = FQDN("example.com.")?;
+        let key = TsigKey::generate(FQDN("transfer-key.")?, TsigAlgorithm::HmacSHA256)?;
+        let mut ns = NameServer::new(&Implementation::Bind, FQDN::COM, &network)?;
+        ns.add(Record::a(needle_fqdn.clone(), Ipv4Addr::new(1, 2, 3, 4)))
+            .transfer_key(key.clone());
+        ns.add_secondary(&Implementation::Unbound, Replication::ZoneTransfer)?;
+        let ns = ns.start()?;
+
+        let client = Client::new(&network)?;
+        assert!(client.axfr(ns.ipv4_addr(), &FQDN::COM).is_err());
+
+        let secondary_addr = ns.secondaries()[0].ipv4_addr();
+        let mut transferred = false;
+        for _ in 0..10 {
+            let output = client.dig(
+                DigSettings::default(),
+                secondary_addr,
+                RecordType::A,
+                &needle_fqdn,
+            )?;
+            if output.status.is_noerror() && !output.answer.is_empty() {
+                transferred = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+        assert!(transferred);
+
+        let output = client.dig(
+            *DigSettings::default().tsig(&key),
+            ns.ipv4_addr(),
+            RecordType::SOA,
+            &FQDN::COM,
+        )?;
+        assert!(output.status.is_noerror());
+        assert_eq!(
+            Some(TsigStatus {
+                error: Some(TsigError::NOERROR),
+                verified: true,
+            }),
+            output.tsig
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn update_running_zone() -> Result<()> {
+        let network = Network::new()?;
+        let needle_fqdn = FQDN("example.com.")?;
+        let mut ns = NameServer::new(&Implementation::Bind, FQDN::COM, &network)?;
+        ns.add_secondary(&Implementation::Unbound, Replication::ZoneTransfer)?;
+        let mut ns = ns.sign(SignSettings::default())?.start()?;
+        let old_serial = ns.zone_file().soa.settings.serial;
+
+        ns.add(Record::a(needle_fqdn.clone(), Ipv4Addr::new(1, 2, 3, 4)))?;
+        assert_eq!(old_serial + 1, ns.zone_file().soa.settings.serial);
+
+        let client = Client::new(&network)?;
+        let settings = *DigSettings::default().dnssec();
+        let output = client.dig(settings, ns.ipv4_addr(), RecordType::A, &needle_fqdn)?;
+        assert!(output.status.is_noerror());
+        assert!(output
+            .answer
+            .iter()
+            .any(|record| matches!(record, Record::RRSIG(..))));
+
+        // the secondary is notified of the change
+        let secondary_addr = ns.secondaries()[0].ipv4_addr();
+        let mut transferred = false;
+        for _ in 0..10 {
+            let output = client.dig(settings, secondary_addr, RecordType::A, &needle_fqdn)?;
+            if output.status.is_noerror() && !output.answer.is_empty() {
+                transferred = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+        assert!(transferred);
+
+        ns.remove(|record| record.fqdn() == &needle_fqdn)?;
+        let output = client.dig(settings, ns.ipv4_addr(), RecordType::A, &needle_fqdn)?;
+        assert!(output.status.is_nxdomain());
+
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic_update() -> Result<()> {
+        let network = Network::new()?;
+        let needle_fqdn = FQDN("example.com.")?;
+        let key = TsigKey::generate(FQDN("update-key.")?, TsigAlgorithm::HmacSHA256)?;
+        let mut ns = NameServer::new(&Implementation::Bind, FQDN::COM, &network)?;
+        ns.allow_updates(UpdatePolicy::Tsig(key.clone()));
+        let ns = ns.sign(SignSettings::default())?.start()?;
+
+        let client = Client::new(&network)?;
+        let mut update = Update::new(FQDN::COM);
+        update.add(Record::a(needle_fqdn.clone(), Ipv4Addr::new(1, 2, 3, 4)));
+        let output = client.nsupdate(ns.ipv4_addr(), &update)?;
+        assert_eq!(DigStatus::REFUSED, output.status);
+
+        let output = client.nsupdate(ns.ipv4_addr(), update.key(key))?;
+        assert_eq!(DigStatus::NOERROR, output.status);
+
+        let settings = *DigSettings::default().dnssec();
+        let output = client.dig(settings, ns.ipv4_addr(), RecordType::A, &needle_fqdn)?;
+        assert!(output.status.is_noerror());
+        assert!(output
+            .answer
+            .iter()
+            .any(|record| matches!(record, Record::RRSIG(..))));
+
+        Ok(())
+    }
+
     #[test]
     fn signed() -> Result<()> {
         let network = Network::new()?;
-        let ns = NameServer::new(&Implementation::Unbound, FQDN::ROOT, &network)?.sign()?;
+        let ns = NameServer::new(&Implementation::Unbound, FQDN::ROOT, &network)?
+            .sign(SignSettings::default())?;
 
         eprintln!("KSK:\n{}", ns.key_signing_key());
         eprintln!("ZSK:\n{}", ns.zone_signing_key());
@@ -556,6 +1915,105 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn signed_with_salt() -> Result<()> {
+        let network = Network::new()?;
+        let settings = SignSettings {
+            nsec: Nsec::_3 {
+                iterations: 1,
+                opt_out: false,
+                salt: Some("AABBCCDD".to_string()),
+            },
+            ..SignSettings::default()
+        };
+        let ns = NameServer::new(&Implementation::Unbound, FQDN::ROOT, &network)?.sign(settings)?;
+
+        let nsec3param = ns
+            .signed_zone_file()
+            .records
+            .iter()
+            .find_map(|record| match record {
+                Record::NSEC3PARAM(nsec3param) => Some(nsec3param),
+                _ => None,
+            })
+            .expect("NSEC3PARAM record in signed zone");
+        assert_eq!("AABBCCDD", nsec3param.salt.to_uppercase());
+
+        Ok(())
+    }
+
+    #[test]
+    fn ldns_signzone_args_validate_the_salt() -> Result<()> {
+        let settings = |salt: &str| SignSettings {
+            nsec: Nsec::_3 {
+                iterations: 1,
+                opt_out: true,
+                salt: Some(salt.to_string()),
+            },
+            ..SignSettings::default()
+        };
+
+        assert_eq!(
+            "-n -t 1 -s AABBCCDD -p",
+            ldns_signzone_args(&settings("AABBCCDD"))?
+        );
+        assert_eq!("-n -t 1 -p", ldns_signzone_args(&settings("-"))?);
+
+        for salt in ["AABBCCD", "AA BB", "AA;reboot", "'AA'"] {
+            assert!(ldns_signzone_args(&settings(salt)).is_err(), "{salt}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "example.com. is not a child zone of .")]
+    fn zone_spec_rejects_grandchild() {
+        let mut root = ZoneSpec::new(FQDN::ROOT);
+        root.child(ZoneSpec::new(FQDN("example.com.").unwrap()));
+    }
+
+    #[test]
+    fn graph_from_spec() -> Result<()> {
+        let network = Network::new()?;
+
+        let mut com = ZoneSpec::new(FQDN::COM);
+        for zone in ["example.com.", "example2.com."] {
+            com.child(ZoneSpec::new(FQDN(zone)?));
+        }
+        let mut root = ZoneSpec::new(FQDN::ROOT);
+        root.child(com);
+
+        let graph = Graph::from_spec(root, &Implementation::Unbound, &network, Sign::No)?;
+
+        let zones = graph.nameservers.keys().map(|zone| zone.as_str());
+        assert_eq!(
+            [
+                ".",
+                "com.",
+                "example.com.",
+                "example2.com.",
+                "nameservers.com."
+            ],
+            zones.collect::<Vec<_>>().as_slice()
+        );
+
+        let client = Client::new(&network)?;
+        let com_ns = &graph.nameservers[&FQDN::COM];
+        let output = client.dig(
+            DigSettings::default(),
+            com_ns.ipv4_addr(),
+            RecordType::NS,
+            &FQDN("example2.com.")?,
+        )?;
+
+        assert!(output.status.is_noerror());
+        let [ns] = output.authority.try_into().expect("one referral");
+        assert!(matches!(ns, Record::NS(..)));
+
+        Ok(())
+    }
+
     #[test]
     fn terminate_nsd_works() -> Result<()> {
         let network = Network::new()?;
@@ -3,28 +3,97 @@
 use core::result::Result as CoreResult;
 use core::str::FromStr;
 use core::{array, fmt};
-use std::any;
 use std::fmt::Write;
-use std::net::Ipv4Addr;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::{any, mem};
 
-use crate::{Error, Result, DEFAULT_TTL, FQDN};
+use data_encoding::{HEXUPPER, HEXUPPER_PERMISSIVE};
+use ring::digest;
+
+use crate::{wire, Error, Result, DEFAULT_TTL, FQDN};
 
 const CLASS: &str = "IN"; // "internet"
 
+// precedes RDATA in the generic format of RFC3597
+const GENERIC_RDATA_MARKER: &str = "\\#";
+
+// mnemonics, from the IANA registry, of the types this crate does not model; `dig` prints the
+// RDATA of these types in their presentation format
+const UNMODELED_TYPES: &[(&str, u16)] = &[
+    ("MD", 3),
+    ("MF", 4),
+    ("MB", 7),
+    ("MG", 8),
+    ("MR", 9),
+    ("NULL", 10),
+    ("WKS", 11),
+    ("HINFO", 13),
+    ("MINFO", 14),
+    ("RP", 17),
+    ("AFSDB", 18),
+    ("X25", 19),
+    ("ISDN", 20),
+    ("RT", 21),
+    ("NSAP", 22),
+    ("NSAP-PTR", 23),
+    ("SIG", 24),
+    ("KEY", 25),
+    ("PX", 26),
+    ("GPOS", 27),
+    ("LOC", 29),
+    ("NXT", 30),
+    ("EID", 31),
+    ("NIMLOC", 32),
+    ("ATMA", 34),
+    ("NAPTR", 35),
+    ("KX", 36),
+    ("CERT", 37),
+    ("A6", 38),
+    ("SINK", 40),
+    ("APL", 42),
+    ("SSHFP", 44),
+    ("IPSECKEY", 45),
+    ("DHCID", 49),
+    ("SMIMEA", 53),
+    ("HIP", 55),
+    ("NINFO", 56),
+    ("RKEY", 57),
+    ("TALINK", 58),
+    ("OPENPGPKEY", 61),
+    ("CSYNC", 62),
+    ("SPF", 99),
+    ("UINFO", 100),
+    ("UID", 101),
+    ("GID", 102),
+    ("UNSPEC", 103),
+    ("NID", 104),
+    ("L32", 105),
+    ("L64", 106),
+    ("LP", 107),
+    ("EUI48", 108),
+    ("EUI64", 109),
+    ("URI", 256),
+    ("AVC", 258),
+    ("DOA", 259),
+    ("AMTRELAY", 260),
+    ("RESINFO", 261),
+    ("WALLET", 262),
+    ("TA", 32768),
+    ("DLV", 32769),
+];
+
 macro_rules! record_types {
     ($($variant:ident),*) => {
         #[allow(clippy::upper_case_acronyms)]
-        #[derive(Debug, PartialEq)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
         pub enum RecordType {
-            $($variant),*
+            $($variant,)*
+            /// A type this crate does not model, identified by its numeric value (RFC3597)
+            Unknown(u16),
         }
 
         impl RecordType {
-            pub fn as_str(&self) -> &'static str {
-                match self {
-                    $(Self::$variant => stringify!($variant)),*
-                }
-            }
+            const KNOWN: &[Self] = &[$(Self::$variant),*];
         }
 
         impl FromStr for RecordType {
@@ -35,36 +104,156 @@ macro_rules! record_types {
                     return Ok(Self::$variant);
                 })*
 
-                Err(format!("unknown record type: {input}").into())
+                if let Some((_, code)) = UNMODELED_TYPES
+                    .iter()
+                    .find(|(mnemonic, _)| *mnemonic == input)
+                {
+                    return Ok(Self::Unknown(*code));
+                }
+
+                // generic type syntax (section 5 of RFC3597), e.g. `TYPE65534`
+                if let Some(code) = input
+                    .strip_prefix("TYPE")
+                    .and_then(|code| code.parse().ok())
+                {
+                    return Ok(Self::from_code(code));
+                }
+
+                Err(Error::parse(format!("unknown record type: {input}")))
             }
         }
 
         impl fmt::Display for RecordType {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                f.write_str(self.as_str())
+                match self {
+                    $(Self::$variant => f.write_str(stringify!($variant)),)*
+                    Self::Unknown(code) => match UNMODELED_TYPES
+                        .iter()
+                        .find(|(_, unmodeled)| unmodeled == code)
+                    {
+                        Some((mnemonic, _)) => f.write_str(mnemonic),
+                        None => write!(f, "TYPE{code}"),
+                    },
+                }
             }
         }
     };
 }
 
-record_types!(A, AAAA, DNSKEY, DS, MX, NS, NSEC3, NSEC3PARAM, RRSIG, SOA, TXT);
+record_types!(
+    A, AAAA, CAA, CDNSKEY, CDS, CNAME, DNAME, DNSKEY, DS, HTTPS, MX, NS, NSEC, NSEC3, NSEC3PARAM,
+    PTR, RRSIG, SOA, SRV, SVCB, TLSA, TXT, ZONEMD
+);
+
+impl RecordType {
+    /// Numeric value used in the wire format
+    pub fn code(&self) -> u16 {
+        match self {
+            Self::A => 1,
+            Self::NS => 2,
+            Self::CNAME => 5,
+            Self::SOA => 6,
+            Self::PTR => 12,
+            Self::MX => 15,
+            Self::TXT => 16,
+            Self::AAAA => 28,
+            Self::SRV => 33,
+            Self::DNAME => 39,
+            Self::DS => 43,
+            Self::RRSIG => 46,
+            Self::NSEC => 47,
+            Self::DNSKEY => 48,
+            Self::NSEC3 => 50,
+            Self::NSEC3PARAM => 51,
+            Self::TLSA => 52,
+            Self::CDS => 59,
+            Self::CDNSKEY => 60,
+            Self::ZONEMD => 63,
+            Self::SVCB => 64,
+            Self::HTTPS => 65,
+            Self::CAA => 257,
+            Self::Unknown(code) => *code,
+        }
+    }
+
+    /// Inverse of `code`; codes of types this crate does not model map to `RecordType::Unknown`
+    pub fn from_code(code: u16) -> Self {
+        Self::KNOWN
+            .iter()
+            .copied()
+            .find(|record_type| record_type.code() == code)
+            .unwrap_or(Self::Unknown(code))
+    }
+}
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum Record {
     A(A),
+    AAAA(AAAA),
+    CAA(CAA),
+    CDNSKEY(CDNSKEY),
+    CDS(CDS),
+    CNAME(CNAME),
+    DNAME(DNAME),
     DNSKEY(DNSKEY),
     DS(DS),
+    HTTPS(HTTPS),
+    MX(MX),
     NS(NS),
+    NSEC(NSEC),
     NSEC3(NSEC3),
     NSEC3PARAM(NSEC3PARAM),
+    PTR(PTR),
     RRSIG(RRSIG),
     SOA(SOA),
+    SRV(SRV),
+    SVCB(SVCB),
+    TLSA(TLSA),
+    TXT(TXT),
+    ZONEMD(ZONEMD),
+    Unknown(Unknown),
 }
 
-impl From<NSEC3> for Record {
-    fn from(v: NSEC3) -> Self {
-        Self::NSEC3(v)
+impl From<A> for Record {
+    fn from(v: A) -> Self {
+        Self::A(v)
+    }
+}
+
+impl From<AAAA> for Record {
+    fn from(v: AAAA) -> Self {
+        Self::AAAA(v)
+    }
+}
+
+impl From<CAA> for Record {
+    fn from(v: CAA) -> Self {
+        Self::CAA(v)
+    }
+}
+
+impl From<CDNSKEY> for Record {
+    fn from(v: CDNSKEY) -> Self {
+        Self::CDNSKEY(v)
+    }
+}
+
+impl From<CDS> for Record {
+    fn from(v: CDS) -> Self {
+        Self::CDS(v)
+    }
+}
+
+impl From<CNAME> for Record {
+    fn from(v: CNAME) -> Self {
+        Self::CNAME(v)
+    }
+}
+
+impl From<DNAME> for Record {
+    fn from(v: DNAME) -> Self {
+        Self::DNAME(v)
     }
 }
 
@@ -80,9 +269,15 @@ impl From<DS> for Record {
     }
 }
 
-impl From<A> for Record {
-    fn from(v: A) -> Self {
-        Self::A(v)
+impl From<HTTPS> for Record {
+    fn from(v: HTTPS) -> Self {
+        Self::HTTPS(v)
+    }
+}
+
+impl From<MX> for Record {
+    fn from(v: MX) -> Self {
+        Self::MX(v)
     }
 }
 
@@ -92,6 +287,30 @@ impl From<NS> for Record {
     }
 }
 
+impl From<NSEC> for Record {
+    fn from(v: NSEC) -> Self {
+        Self::NSEC(v)
+    }
+}
+
+impl From<NSEC3> for Record {
+    fn from(v: NSEC3) -> Self {
+        Self::NSEC3(v)
+    }
+}
+
+impl From<NSEC3PARAM> for Record {
+    fn from(v: NSEC3PARAM) -> Self {
+        Self::NSEC3PARAM(v)
+    }
+}
+
+impl From<PTR> for Record {
+    fn from(v: PTR) -> Self {
+        Self::PTR(v)
+    }
+}
+
 impl From<RRSIG> for Record {
     fn from(v: RRSIG) -> Self {
         Self::RRSIG(v)
@@ -104,7 +323,131 @@ impl From<SOA> for Record {
     }
 }
 
+impl From<SRV> for Record {
+    fn from(v: SRV) -> Self {
+        Self::SRV(v)
+    }
+}
+
+impl From<SVCB> for Record {
+    fn from(v: SVCB) -> Self {
+        Self::SVCB(v)
+    }
+}
+
+impl From<TLSA> for Record {
+    fn from(v: TLSA) -> Self {
+        Self::TLSA(v)
+    }
+}
+
+impl From<TXT> for Record {
+    fn from(v: TXT) -> Self {
+        Self::TXT(v)
+    }
+}
+
+impl From<ZONEMD> for Record {
+    fn from(v: ZONEMD) -> Self {
+        Self::ZONEMD(v)
+    }
+}
+
+impl From<Unknown> for Record {
+    fn from(v: Unknown) -> Self {
+        Self::Unknown(v)
+    }
+}
+
 impl Record {
+    /// The owner name of the record
+    pub fn fqdn(&self) -> &FQDN {
+        match self {
+            Record::A(a) => &a.fqdn,
+            Record::AAAA(aaaa) => &aaaa.fqdn,
+            Record::CAA(caa) => &caa.fqdn,
+            Record::CDNSKEY(cdnskey) => &cdnskey.zone,
+            Record::CDS(cds) => &cds.zone,
+            Record::CNAME(cname) => &cname.fqdn,
+            Record::DNAME(dname) => &dname.fqdn,
+            Record::DNSKEY(dnskey) => &dnskey.zone,
+            Record::DS(ds) => &ds.zone,
+            Record::HTTPS(https) => &https.fqdn,
+            Record::MX(mx) => &mx.fqdn,
+            Record::NS(ns) => &ns.zone,
+            Record::NSEC(nsec) => &nsec.fqdn,
+            Record::NSEC3(nsec3) => &nsec3.fqdn,
+            Record::NSEC3PARAM(nsec3param) => &nsec3param.zone,
+            Record::PTR(ptr) => &ptr.fqdn,
+            Record::RRSIG(rrsig) => &rrsig.fqdn,
+            Record::SOA(soa) => &soa.zone,
+            Record::SRV(srv) => &srv.fqdn,
+            Record::SVCB(svcb) => &svcb.fqdn,
+            Record::TLSA(tlsa) => &tlsa.fqdn,
+            Record::TXT(txt) => &txt.fqdn,
+            Record::ZONEMD(zonemd) => &zonemd.zone,
+            Record::Unknown(unknown) => &unknown.fqdn,
+        }
+    }
+
+    pub fn ttl(&self) -> u32 {
+        match self {
+            Record::A(a) => a.ttl,
+            Record::AAAA(aaaa) => aaaa.ttl,
+            Record::CAA(caa) => caa.ttl,
+            Record::CDNSKEY(cdnskey) => cdnskey.ttl,
+            Record::CDS(cds) => cds.ttl,
+            Record::CNAME(cname) => cname.ttl,
+            Record::DNAME(dname) => dname.ttl,
+            Record::DNSKEY(dnskey) => dnskey.ttl,
+            Record::DS(ds) => ds.ttl,
+            Record::HTTPS(https) => https.ttl,
+            Record::MX(mx) => mx.ttl,
+            Record::NS(ns) => ns.ttl,
+            Record::NSEC(nsec) => nsec.ttl,
+            Record::NSEC3(nsec3) => nsec3.ttl,
+            Record::NSEC3PARAM(nsec3param) => nsec3param.ttl,
+            Record::PTR(ptr) => ptr.ttl,
+            Record::RRSIG(rrsig) => rrsig.ttl,
+            Record::SOA(soa) => soa.ttl,
+            Record::SRV(srv) => srv.ttl,
+            Record::SVCB(svcb) => svcb.ttl,
+            Record::TLSA(tlsa) => tlsa.ttl,
+            Record::TXT(txt) => txt.ttl,
+            Record::ZONEMD(zonemd) => zonemd.ttl,
+            Record::Unknown(unknown) => unknown.ttl,
+        }
+    }
+
+    pub fn record_type(&self) -> RecordType {
+        match self {
+            Record::A(..) => RecordType::A,
+            Record::AAAA(..) => RecordType::AAAA,
+            Record::CAA(..) => RecordType::CAA,
+            Record::CDNSKEY(..) => RecordType::CDNSKEY,
+            Record::CDS(..) => RecordType::CDS,
+            Record::CNAME(..) => RecordType::CNAME,
+            Record::DNAME(..) => RecordType::DNAME,
+            Record::DNSKEY(..) => RecordType::DNSKEY,
+            Record::DS(..) => RecordType::DS,
+            Record::HTTPS(..) => RecordType::HTTPS,
+            Record::MX(..) => RecordType::MX,
+            Record::NS(..) => RecordType::NS,
+            Record::NSEC(..) => RecordType::NSEC,
+            Record::NSEC3(..) => RecordType::NSEC3,
+            Record::NSEC3PARAM(..) => RecordType::NSEC3PARAM,
+            Record::PTR(..) => RecordType::PTR,
+            Record::RRSIG(..) => RecordType::RRSIG,
+            Record::SOA(..) => RecordType::SOA,
+            Record::SRV(..) => RecordType::SRV,
+            Record::SVCB(..) => RecordType::SVCB,
+            Record::TLSA(..) => RecordType::TLSA,
+            Record::TXT(..) => RecordType::TXT,
+            Record::ZONEMD(..) => RecordType::ZONEMD,
+            Record::Unknown(unknown) => RecordType::from_code(unknown.type_code),
+        }
+    }
+
     pub fn try_into_a(self) -> CoreResult<A, Self> {
         if let Self::A(v) = self {
             Ok(v)
@@ -113,34 +456,60 @@ impl Record {
         }
     }
 
-    pub fn try_into_rrsig(self) -> CoreResult<RRSIG, Self> {
-        if let Self::RRSIG(v) = self {
+    pub fn try_into_aaaa(self) -> CoreResult<AAAA, Self> {
+        if let Self::AAAA(v) = self {
             Ok(v)
         } else {
             Err(self)
         }
     }
 
-    pub fn is_soa(&self) -> bool {
-        matches!(self, Self::SOA(..))
+    pub fn try_into_caa(self) -> CoreResult<CAA, Self> {
+        if let Self::CAA(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
     }
 
-    pub fn a(fqdn: FQDN, ipv4_addr: Ipv4Addr) -> Self {
-        A {
-            fqdn,
-            ttl: DEFAULT_TTL,
-            ipv4_addr,
+    pub fn try_into_cdnskey(self) -> CoreResult<CDNSKEY, Self> {
+        if let Self::CDNSKEY(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
         }
-        .into()
     }
 
-    pub fn ns(zone: FQDN, nameserver: FQDN) -> Self {
-        NS {
-            zone,
-            ttl: DEFAULT_TTL,
-            nameserver,
+    pub fn try_into_cds(self) -> CoreResult<CDS, Self> {
+        if let Self::CDS(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
+
+    pub fn try_into_cname(self) -> CoreResult<CNAME, Self> {
+        if let Self::CNAME(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
+
+    pub fn try_into_dname(self) -> CoreResult<DNAME, Self> {
+        if let Self::DNAME(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
+
+    pub fn try_into_dnskey(self) -> CoreResult<DNSKEY, Self> {
+        if let Self::DNSKEY(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
         }
-        .into()
     }
 
     pub fn try_into_ds(self) -> CoreResult<DS, Self> {
@@ -150,94 +519,421 @@ impl Record {
             Err(self)
         }
     }
-}
-
-impl FromStr for Record {
-    type Err = Error;
 
-    fn from_str(input: &str) -> Result<Self> {
-        let record_type = input
-            .split_whitespace()
-            .nth(3)
-            .ok_or("record is missing the type column")?;
+    pub fn try_into_https(self) -> CoreResult<HTTPS, Self> {
+        if let Self::HTTPS(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
 
-        let record = match record_type {
-            "A" => Record::A(input.parse()?),
-            "DNSKEY" => Record::DNSKEY(input.parse()?),
-            "DS" => Record::DS(input.parse()?),
-            "NS" => Record::NS(input.parse()?),
-            "NSEC3" => Record::NSEC3(input.parse()?),
-            "NSEC3PARAM" => Record::NSEC3PARAM(input.parse()?),
-            "RRSIG" => Record::RRSIG(input.parse()?),
-            "SOA" => Record::SOA(input.parse()?),
-            _ => return Err(format!("unknown record type: {record_type}").into()),
-        };
+    pub fn try_into_mx(self) -> CoreResult<MX, Self> {
+        if let Self::MX(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
 
-        Ok(record)
+    pub fn try_into_ns(self) -> CoreResult<NS, Self> {
+        if let Self::NS(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
     }
-}
 
-impl fmt::Display for Record {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Record::A(a) => write!(f, "{a}"),
-            Record::DS(ds) => write!(f, "{ds}"),
-            Record::DNSKEY(dnskey) => write!(f, "{dnskey}"),
-            Record::NS(ns) => write!(f, "{ns}"),
-            Record::NSEC3(nsec3) => write!(f, "{nsec3}"),
-            Record::NSEC3PARAM(nsec3param) => write!(f, "{nsec3param}"),
-            Record::RRSIG(rrsig) => write!(f, "{rrsig}"),
-            Record::SOA(soa) => write!(f, "{soa}"),
+    pub fn try_into_nsec(self) -> CoreResult<NSEC, Self> {
+        if let Self::NSEC(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
         }
     }
-}
 
-#[derive(Debug)]
-pub struct A {
-    pub fqdn: FQDN,
-    pub ttl: u32,
-    pub ipv4_addr: Ipv4Addr,
-}
+    pub fn try_into_nsec3(self) -> CoreResult<NSEC3, Self> {
+        if let Self::NSEC3(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
 
-impl FromStr for A {
-    type Err = Error;
+    pub fn try_into_nsec3param(self) -> CoreResult<NSEC3PARAM, Self> {
+        if let Self::NSEC3PARAM(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
 
-    fn from_str(input: &str) -> Result<Self> {
-        let mut columns = input.split_whitespace();
+    pub fn try_into_ptr(self) -> CoreResult<PTR, Self> {
+        if let Self::PTR(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
 
-        let [Some(fqdn), Some(ttl), Some(class), Some(record_type), Some(ipv4_addr), None] =
-            array::from_fn(|_| columns.next())
-        else {
-            return Err("expected 5 columns".into());
-        };
+    pub fn try_into_rrsig(self) -> CoreResult<RRSIG, Self> {
+        if let Self::RRSIG(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
 
-        check_record_type::<Self>(record_type)?;
-        check_class(class)?;
+    pub fn try_into_soa(self) -> CoreResult<SOA, Self> {
+        if let Self::SOA(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
 
-        Ok(Self {
-            fqdn: fqdn.parse()?,
-            ttl: ttl.parse()?,
-            ipv4_addr: ipv4_addr.parse()?,
-        })
+    pub fn try_into_srv(self) -> CoreResult<SRV, Self> {
+        if let Self::SRV(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
     }
-}
 
-impl fmt::Display for A {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self {
-            fqdn,
-            ttl,
-            ipv4_addr,
-        } = self;
+    pub fn try_into_svcb(self) -> CoreResult<SVCB, Self> {
+        if let Self::SVCB(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
 
-        let record_type = unqualified_type_name::<Self>();
-        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{ipv4_addr}")
+    pub fn try_into_tlsa(self) -> CoreResult<TLSA, Self> {
+        if let Self::TLSA(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
     }
-}
 
-// integer types chosen based on bit sizes in section 2.1 of RFC4034
+    pub fn try_into_txt(self) -> CoreResult<TXT, Self> {
+        if let Self::TXT(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
+
+    pub fn try_into_zonemd(self) -> CoreResult<ZONEMD, Self> {
+        if let Self::ZONEMD(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
+
+    pub fn try_into_unknown(self) -> CoreResult<Unknown, Self> {
+        if let Self::Unknown(v) = self {
+            Ok(v)
+        } else {
+            Err(self)
+        }
+    }
+
+    pub fn is_soa(&self) -> bool {
+        matches!(self, Self::SOA(..))
+    }
+
+    pub fn a(fqdn: FQDN, ipv4_addr: Ipv4Addr) -> Self {
+        A {
+            fqdn,
+            ttl: DEFAULT_TTL,
+            ipv4_addr,
+        }
+        .into()
+    }
+
+    pub fn aaaa(fqdn: FQDN, ipv6_addr: Ipv6Addr) -> Self {
+        AAAA {
+            fqdn,
+            ttl: DEFAULT_TTL,
+            ipv6_addr,
+        }
+        .into()
+    }
+
+    pub fn cname(fqdn: FQDN, target: FQDN) -> Self {
+        CNAME {
+            fqdn,
+            ttl: DEFAULT_TTL,
+            target,
+        }
+        .into()
+    }
+
+    pub fn dname(fqdn: FQDN, target: FQDN) -> Self {
+        DNAME {
+            fqdn,
+            ttl: DEFAULT_TTL,
+            target,
+        }
+        .into()
+    }
+
+    pub fn mx(fqdn: FQDN, preference: u16, exchange: FQDN) -> Self {
+        MX {
+            fqdn,
+            ttl: DEFAULT_TTL,
+            preference,
+            exchange,
+        }
+        .into()
+    }
+
+    pub fn ns(zone: FQDN, nameserver: FQDN) -> Self {
+        NS {
+            zone,
+            ttl: DEFAULT_TTL,
+            nameserver,
+        }
+        .into()
+    }
+
+    pub fn ptr(fqdn: FQDN, target: FQDN) -> Self {
+        PTR {
+            fqdn,
+            ttl: DEFAULT_TTL,
+            target,
+        }
+        .into()
+    }
+
+    pub fn srv(fqdn: FQDN, priority: u16, weight: u16, port: u16, target: FQDN) -> Self {
+        SRV {
+            fqdn,
+            ttl: DEFAULT_TTL,
+            priority,
+            weight,
+            port,
+            target,
+        }
+        .into()
+    }
+
+    /// A TXT record with a single character string; `text` is escaped as necessary
+    pub fn txt(fqdn: FQDN, text: &str) -> Self {
+        TXT {
+            fqdn,
+            ttl: DEFAULT_TTL,
+            character_strings: vec![escape(text)],
+        }
+        .into()
+    }
+}
+
+impl FromStr for Record {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut columns = input.split_whitespace();
+        let record_type = columns
+            .nth(3)
+            .ok_or_else(|| Error::parse("record is missing the type column"))?;
+
+        // RDATA in the generic format (section 5 of RFC3597) is not decoded into a typed record
+        if columns.next() == Some(GENERIC_RDATA_MARKER) {
+            return Ok(Record::Unknown(input.parse()?));
+        }
+
+        let record = match record_type {
+            "A" => Record::A(input.parse()?),
+            "AAAA" => Record::AAAA(input.parse()?),
+            "CAA" => Record::CAA(input.parse()?),
+            "CDNSKEY" => Record::CDNSKEY(input.parse()?),
+            "CDS" => Record::CDS(input.parse()?),
+            "CNAME" => Record::CNAME(input.parse()?),
+            "DNAME" => Record::DNAME(input.parse()?),
+            "DNSKEY" => Record::DNSKEY(input.parse()?),
+            "DS" => Record::DS(input.parse()?),
+            "HTTPS" => Record::HTTPS(input.parse()?),
+            "MX" => Record::MX(input.parse()?),
+            "NS" => Record::NS(input.parse()?),
+            "NSEC" => Record::NSEC(input.parse()?),
+            "NSEC3" => Record::NSEC3(input.parse()?),
+            "NSEC3PARAM" => Record::NSEC3PARAM(input.parse()?),
+            "PTR" => Record::PTR(input.parse()?),
+            "RRSIG" => Record::RRSIG(input.parse()?),
+            "SOA" => Record::SOA(input.parse()?),
+            "SRV" => Record::SRV(input.parse()?),
+            "SVCB" => Record::SVCB(input.parse()?),
+            "TLSA" => Record::TLSA(input.parse()?),
+            "TXT" => Record::TXT(input.parse()?),
+            "ZONEMD" => Record::ZONEMD(input.parse()?),
+            _ => Record::Unknown(input.parse()?),
+        };
+
+        Ok(record)
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Record::A(a) => write!(f, "{a}"),
+            Record::AAAA(aaaa) => write!(f, "{aaaa}"),
+            Record::CAA(caa) => write!(f, "{caa}"),
+            Record::CDNSKEY(cdnskey) => write!(f, "{cdnskey}"),
+            Record::CDS(cds) => write!(f, "{cds}"),
+            Record::CNAME(cname) => write!(f, "{cname}"),
+            Record::DNAME(dname) => write!(f, "{dname}"),
+            Record::DNSKEY(dnskey) => write!(f, "{dnskey}"),
+            Record::DS(ds) => write!(f, "{ds}"),
+            Record::HTTPS(https) => write!(f, "{https}"),
+            Record::MX(mx) => write!(f, "{mx}"),
+            Record::NS(ns) => write!(f, "{ns}"),
+            Record::NSEC(nsec) => write!(f, "{nsec}"),
+            Record::NSEC3(nsec3) => write!(f, "{nsec3}"),
+            Record::NSEC3PARAM(nsec3param) => write!(f, "{nsec3param}"),
+            Record::PTR(ptr) => write!(f, "{ptr}"),
+            Record::RRSIG(rrsig) => write!(f, "{rrsig}"),
+            Record::SOA(soa) => write!(f, "{soa}"),
+            Record::SRV(srv) => write!(f, "{srv}"),
+            Record::SVCB(svcb) => write!(f, "{svcb}"),
+            Record::TLSA(tlsa) => write!(f, "{tlsa}"),
+            Record::TXT(txt) => write!(f, "{txt}"),
+            Record::ZONEMD(zonemd) => write!(f, "{zonemd}"),
+            Record::Unknown(unknown) => write!(f, "{unknown}"),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
-pub struct DNSKEY {
+pub struct A {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub ipv4_addr: Ipv4Addr,
+}
+
+impl FromStr for A {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut columns = input.split_whitespace();
+
+        let [Some(fqdn), Some(ttl), Some(class), Some(record_type), Some(ipv4_addr), None] =
+            array::from_fn(|_| columns.next())
+        else {
+            return Err(Error::parse("expected 5 columns"));
+        };
+
+        check_record_type::<Self>(record_type)?;
+        check_class(class)?;
+
+        Ok(Self {
+            fqdn: fqdn.parse()?,
+            ttl: ttl.parse()?,
+            ipv4_addr: ipv4_addr.parse()?,
+        })
+    }
+}
+
+impl fmt::Display for A {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            fqdn,
+            ttl,
+            ipv4_addr,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{ipv4_addr}")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AAAA {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub ipv6_addr: Ipv6Addr,
+}
+
+impl FromStr for AAAA {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (fqdn, ttl, rdata) = parse_header::<Self>(input)?;
+        let [ipv6_addr] = rdata_columns(rdata)?;
+
+        Ok(Self {
+            fqdn,
+            ttl,
+            ipv6_addr: ipv6_addr.parse()?,
+        })
+    }
+}
+
+impl fmt::Display for AAAA {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            fqdn,
+            ttl,
+            ipv6_addr,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{ipv6_addr}")
+    }
+}
+
+/// Certification authority authorization (RFC8659)
+#[derive(Clone, Debug)]
+pub struct CAA {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub flags: u8,
+    pub tag: String,
+    /// in presentation format, without the surrounding quotes
+    pub value: String,
+}
+
+impl FromStr for CAA {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (fqdn, ttl, rdata) = parse_header::<Self>(input)?;
+        let [flags, tag, value] = rdata_tokens(rdata)?;
+
+        Ok(Self {
+            fqdn,
+            ttl,
+            flags: flags.parse()?,
+            tag,
+            value,
+        })
+    }
+}
+
+impl fmt::Display for CAA {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            fqdn,
+            ttl,
+            flags,
+            tag,
+            value,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(
+            f,
+            "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{flags} {tag} \"{value}\""
+        )
+    }
+}
+
+/// Child copy of a DNSKEY record, published to update the parent's DS RRset (RFC7344)
+#[derive(Clone, Debug)]
+pub struct CDNSKEY {
     pub zone: FQDN,
     pub ttl: u32,
     pub flags: u16,
@@ -246,502 +942,1463 @@ pub struct DNSKEY {
     pub public_key: String,
 }
 
-impl DNSKEY {
-    const KSK_BIT: u16 = 1;
+impl From<DNSKEY> for CDNSKEY {
+    fn from(dnskey: DNSKEY) -> Self {
+        let DNSKEY {
+            zone,
+            ttl,
+            flags,
+            protocol,
+            algorithm,
+            public_key,
+        } = dnskey;
 
-    /// formats the `DNSKEY` in the format `delv` expects
-    pub(super) fn delv(&self) -> String {
-        let Self {
+        Self {
             zone,
+            ttl,
             flags,
             protocol,
             algorithm,
             public_key,
-            ..
-        } = self;
+        }
+    }
+}
+
+impl FromStr for CDNSKEY {
+    type Err = Error;
+
+    fn from_str(mut input: &str) -> Result<Self> {
+        if let Some((rr, _comment)) = input.rsplit_once(" ;") {
+            input = rr.trim_end();
+        }
+
+        let (zone, ttl, rdata) = parse_header::<Self>(input)?;
+        let mut columns = rdata.split_whitespace();
+        let [Some(flags), Some(protocol), Some(algorithm)] = array::from_fn(|_| columns.next())
+        else {
+            return Err(Error::parse("expected at least 7 columns"));
+        };
 
-        format!("{zone} static-key {flags} {protocol} {algorithm} \"{public_key}\";\n")
+        Ok(Self {
+            zone,
+            ttl,
+            flags: flags.parse()?,
+            protocol: protocol.parse()?,
+            algorithm: algorithm.parse()?,
+            public_key: columns.collect(),
+        })
+    }
+}
+
+impl fmt::Display for CDNSKEY {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            zone,
+            ttl,
+            flags,
+            protocol,
+            algorithm,
+            public_key,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(
+            f,
+            "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{flags} {protocol} {algorithm}"
+        )?;
+
+        write_split_long_string(f, public_key)
+    }
+}
+
+/// Child copy of a DS record, published to update the parent's DS RRset (RFC7344)
+#[derive(Clone, Debug)]
+pub struct CDS {
+    pub zone: FQDN,
+    pub ttl: u32,
+    pub key_tag: u16,
+    pub algorithm: u8,
+    pub digest_type: u8,
+    pub digest: String,
+}
+
+impl From<DS> for CDS {
+    fn from(ds: DS) -> Self {
+        let DS {
+            zone,
+            ttl,
+            key_tag,
+            algorithm,
+            digest_type,
+            digest,
+        } = ds;
+
+        Self {
+            zone,
+            ttl,
+            key_tag,
+            algorithm,
+            digest_type,
+            digest,
+        }
+    }
+}
+
+impl FromStr for CDS {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (zone, ttl, rdata) = parse_header::<Self>(input)?;
+        let mut columns = rdata.split_whitespace();
+        let [Some(key_tag), Some(algorithm), Some(digest_type)] =
+            array::from_fn(|_| columns.next())
+        else {
+            return Err(Error::parse("expected at least 7 columns"));
+        };
+
+        Ok(Self {
+            zone,
+            ttl,
+            key_tag: key_tag.parse()?,
+            algorithm: algorithm.parse()?,
+            digest_type: digest_type.parse()?,
+            digest: columns.collect(),
+        })
+    }
+}
+
+impl fmt::Display for CDS {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            zone,
+            ttl,
+            key_tag,
+            algorithm,
+            digest_type,
+            digest,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(
+            f,
+            "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{key_tag} {algorithm} {digest_type}"
+        )?;
+
+        write_split_long_string(f, digest)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CNAME {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub target: FQDN,
+}
+
+impl FromStr for CNAME {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (fqdn, ttl, rdata) = parse_header::<Self>(input)?;
+        let [target] = rdata_columns(rdata)?;
+
+        Ok(Self {
+            fqdn,
+            ttl,
+            target: target.parse()?,
+        })
+    }
+}
+
+impl fmt::Display for CNAME {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { fqdn, ttl, target } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{target}")
+    }
+}
+
+/// Redirection of a whole subtree of the domain name space (RFC6672)
+#[derive(Clone, Debug)]
+pub struct DNAME {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub target: FQDN,
+}
+
+impl FromStr for DNAME {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (fqdn, ttl, rdata) = parse_header::<Self>(input)?;
+        let [target] = rdata_columns(rdata)?;
+
+        Ok(Self {
+            fqdn,
+            ttl,
+            target: target.parse()?,
+        })
+    }
+}
+
+impl fmt::Display for DNAME {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { fqdn, ttl, target } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{target}")
+    }
+}
+
+// integer types chosen based on bit sizes in section 2.1 of RFC4034
+#[derive(Clone, Debug)]
+pub struct DNSKEY {
+    pub zone: FQDN,
+    pub ttl: u32,
+    pub flags: u16,
+    pub protocol: u8,
+    pub algorithm: u8,
+    pub public_key: String,
+}
+
+impl DNSKEY {
+    const KSK_BIT: u16 = 1;
+    const REVOKE_BIT: u16 = 1 << 7;
+
+    /// formats the `DNSKEY` in the format `delv` expects
+    pub(super) fn delv(&self) -> String {
+        self.bind_trust_anchor("static-key")
+    }
+
+    /// formats the `DNSKEY` as a BIND trust anchor that's managed as per RFC5011
+    pub(super) fn initial_key(&self) -> String {
+        self.bind_trust_anchor("initial-key")
+    }
+
+    fn bind_trust_anchor(&self, kind: &str) -> String {
+        let Self {
+            zone,
+            flags,
+            protocol,
+            algorithm,
+            public_key,
+            ..
+        } = self;
+
+        format!("{zone} {kind} {flags} {protocol} {algorithm} \"{public_key}\";\n")
+    }
+
+    pub fn clear_key_signing_key_bit(&mut self) {
+        self.flags &= !Self::KSK_BIT;
+    }
+
+    pub fn is_key_signing_key(&self) -> bool {
+        let mask = Self::KSK_BIT;
+        self.flags & mask == mask
+    }
+
+    pub fn is_zone_signing_key(&self) -> bool {
+        !self.is_key_signing_key()
+    }
+
+    /// Sets the REVOKE bit (RFC5011). Note that this changes the key tag
+    pub fn set_revoke_bit(&mut self) {
+        self.flags |= Self::REVOKE_BIT;
+    }
+
+    pub fn clear_revoke_bit(&mut self) {
+        self.flags &= !Self::REVOKE_BIT;
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.flags & Self::REVOKE_BIT != 0
+    }
+
+    /// The key tag that RRSIG and DS records use to refer to this key; see appendix B of RFC4034
+    pub fn key_tag(&self) -> Result<u16> {
+        let rdata = wire::rdata(&Record::DNSKEY(self.clone()))?;
+
+        let mut acc: u32 = 0;
+        for (index, byte) in rdata.iter().enumerate() {
+            acc += if index % 2 == 0 {
+                u32::from(*byte) << 8
+            } else {
+                u32::from(*byte)
+            };
+        }
+        acc += (acc >> 16) & 0xFFFF;
+
+        Ok((acc & 0xFFFF) as u16)
+    }
+
+    /// The DS record that refers to this key; see section 5.1.4 of RFC4034
+    pub fn ds(&self, digest_type: DigestType) -> Result<DS> {
+        let algorithm = match digest_type {
+            DigestType::SHA1 => &digest::SHA1_FOR_LEGACY_USE_ONLY,
+            DigestType::SHA256 => &digest::SHA256,
+            DigestType::SHA384 => &digest::SHA384,
+        };
+
+        let mut data = vec![];
+        wire::name(&self.zone, &mut data);
+        data.extend(wire::rdata(&Record::DNSKEY(self.clone()))?);
+
+        Ok(DS {
+            zone: self.zone.clone(),
+            ttl: self.ttl,
+            key_tag: self.key_tag()?,
+            algorithm: self.algorithm,
+            digest_type: digest_type.code(),
+            digest: HEXUPPER.encode(digest::digest(algorithm, &data).as_ref()),
+        })
+    }
+}
+
+impl FromStr for DNSKEY {
+    type Err = Error;
+
+    fn from_str(mut input: &str) -> Result<Self> {
+        if let Some((rr, _comment)) = input.rsplit_once(" ;") {
+            input = rr.trim_end();
+        }
+
+        let mut columns = input.split_whitespace();
+
+        let [Some(zone), Some(ttl), Some(class), Some(record_type), Some(flags), Some(protocol), Some(algorithm)] =
+            array::from_fn(|_| columns.next())
+        else {
+            return Err(Error::parse("expected at least 7 columns"));
+        };
+
+        check_record_type::<Self>(record_type)?;
+        check_class(class)?;
+
+        let mut public_key = String::new();
+        for column in columns {
+            public_key.push_str(column);
+        }
+
+        Ok(Self {
+            zone: zone.parse()?,
+            ttl: ttl.parse()?,
+            flags: flags.parse()?,
+            protocol: protocol.parse()?,
+            algorithm: algorithm.parse()?,
+            public_key,
+        })
+    }
+}
+
+impl fmt::Display for DNSKEY {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            zone,
+            ttl,
+            flags,
+            protocol,
+            algorithm,
+            public_key,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(
+            f,
+            "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{flags} {protocol} {algorithm}"
+        )?;
+
+        write_split_long_string(f, public_key)
+    }
+}
+
+/// DNSSEC algorithms; see section 3.1 of RFC8624
+#[allow(clippy::upper_case_acronyms, non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Algorithm {
+    RSASHA1,
+    RSASHA1_NSEC3_SHA1,
+    RSASHA256,
+    RSASHA512,
+    ECDSAP256SHA256,
+    ECDSAP384SHA384,
+    ED25519,
+    ED448,
+}
+
+impl Algorithm {
+    /// The algorithm number used in the `algorithm` field of DNSKEY, DS and RRSIG records
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::RSASHA1 => 5,
+            Self::RSASHA1_NSEC3_SHA1 => 7,
+            Self::RSASHA256 => 8,
+            Self::RSASHA512 => 10,
+            Self::ECDSAP256SHA256 => 13,
+            Self::ECDSAP384SHA384 => 14,
+            Self::ED25519 => 15,
+            Self::ED448 => 16,
+        }
+    }
+
+    /// The mnemonic of the algorithm, as used by `ldns`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RSASHA1 => "RSASHA1",
+            Self::RSASHA1_NSEC3_SHA1 => "RSASHA1-NSEC3-SHA1",
+            Self::RSASHA256 => "RSASHA256",
+            Self::RSASHA512 => "RSASHA512",
+            Self::ECDSAP256SHA256 => "ECDSAP256SHA256",
+            Self::ECDSAP384SHA384 => "ECDSAP384SHA384",
+            Self::ED25519 => "ED25519",
+            Self::ED448 => "ED448",
+        }
+    }
+
+    /// Whether the key size of this algorithm can be chosen
+    pub fn is_rsa(&self) -> bool {
+        matches!(
+            self,
+            Self::RSASHA1 | Self::RSASHA1_NSEC3_SHA1 | Self::RSASHA256 | Self::RSASHA512
+        )
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Digest algorithms used in DS records; see section 3.3 of RFC8624
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DigestType {
+    SHA1,
+    SHA256,
+    SHA384,
+}
+
+impl DigestType {
+    /// The digest type number used in the `digest_type` field of DS records
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::SHA1 => 1,
+            Self::SHA256 => 2,
+            Self::SHA384 => 4,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DS {
+    pub zone: FQDN,
+    pub ttl: u32,
+    pub key_tag: u16,
+    pub algorithm: u8,
+    pub digest_type: u8,
+    pub digest: String,
+}
+
+impl FromStr for DS {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut columns = input.split_whitespace();
+
+        let [Some(zone), Some(ttl), Some(class), Some(record_type), Some(key_tag), Some(algorithm), Some(digest_type)] =
+            array::from_fn(|_| columns.next())
+        else {
+            return Err(Error::parse("expected at least 7 columns"));
+        };
+
+        check_record_type::<Self>(record_type)?;
+        check_class(class)?;
+
+        let mut digest = String::new();
+        for column in columns {
+            digest.push_str(column);
+        }
+
+        Ok(Self {
+            zone: zone.parse()?,
+            ttl: ttl.parse()?,
+            key_tag: key_tag.parse()?,
+            algorithm: algorithm.parse()?,
+            digest_type: digest_type.parse()?,
+            digest,
+        })
+    }
+}
+
+impl fmt::Display for DS {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            zone,
+            ttl,
+            key_tag,
+            algorithm,
+            digest_type,
+            digest,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(
+            f,
+            "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{key_tag} {algorithm} {digest_type}"
+        )?;
+
+        write_split_long_string(f, digest)
+    }
+}
+
+/// Service binding for HTTPS origins (RFC9460); same format as `SVCB`
+#[derive(Clone, Debug)]
+pub struct HTTPS {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub priority: u16,
+    pub target: FQDN,
+    pub params: Vec<SvcParam>,
+}
+
+impl FromStr for HTTPS {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (fqdn, ttl, rdata) = parse_header::<Self>(input)?;
+        let (priority, target, params) = parse_svcb_rdata(rdata)?;
+
+        Ok(Self {
+            fqdn,
+            ttl,
+            priority,
+            target,
+            params,
+        })
+    }
+}
+
+impl fmt::Display for HTTPS {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            fqdn,
+            ttl,
+            priority,
+            target,
+            params,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(
+            f,
+            "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{priority} {target}"
+        )?;
+
+        write_svc_params(f, params)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MX {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub preference: u16,
+    pub exchange: FQDN,
+}
+
+impl FromStr for MX {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (fqdn, ttl, rdata) = parse_header::<Self>(input)?;
+        let [preference, exchange] = rdata_columns(rdata)?;
+
+        Ok(Self {
+            fqdn,
+            ttl,
+            preference: preference.parse()?,
+            exchange: exchange.parse()?,
+        })
+    }
+}
+
+impl fmt::Display for MX {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            fqdn,
+            ttl,
+            preference,
+            exchange,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(
+            f,
+            "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{preference} {exchange}"
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NS {
+    pub zone: FQDN,
+    pub ttl: u32,
+    pub nameserver: FQDN,
+}
+
+impl fmt::Display for NS {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            zone,
+            ttl,
+            nameserver,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(f, "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{nameserver}")
+    }
+}
+
+impl FromStr for NS {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut columns = input.split_whitespace();
+
+        let [Some(zone), Some(ttl), Some(class), Some(record_type), Some(nameserver), None] =
+            array::from_fn(|_| columns.next())
+        else {
+            return Err(Error::parse("expected 5 columns"));
+        };
+
+        check_record_type::<Self>(record_type)?;
+        check_class(class)?;
+
+        Ok(Self {
+            zone: zone.parse()?,
+            ttl: ttl.parse()?,
+            nameserver: nameserver.parse()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NSEC {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub next_domain: FQDN,
+    pub record_types: Vec<RecordType>,
+}
+
+impl FromStr for NSEC {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut columns = input.split_whitespace();
+
+        let [Some(fqdn), Some(ttl), Some(class), Some(record_type), Some(next_domain)] =
+            array::from_fn(|_| columns.next())
+        else {
+            return Err(Error::parse("expected at least 5 columns"));
+        };
+
+        check_record_type::<Self>(record_type)?;
+        check_class(class)?;
+
+        let mut record_types = vec![];
+        for column in columns {
+            record_types.push(column.parse()?);
+        }
+
+        Ok(Self {
+            fqdn: fqdn.parse()?,
+            ttl: ttl.parse()?,
+            next_domain: next_domain.parse()?,
+            record_types,
+        })
+    }
+}
+
+impl fmt::Display for NSEC {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            fqdn,
+            ttl,
+            next_domain,
+            record_types,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{next_domain}")?;
+
+        for record_type in record_types {
+            write!(f, " {record_type}")?;
+        }
+
+        Ok(())
+    }
+}
+
+// integer types chosen based on bit sizes in section 3.2 of RFC5155
+#[derive(Clone, Debug)]
+pub struct NSEC3 {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub hash_alg: u8,
+    pub flags: u8,
+    pub iterations: u16,
+    /// hex encoded; `-` when the salt is empty
+    pub salt: String,
+    pub next_hashed_owner_name: String,
+    pub record_types: Vec<RecordType>,
+}
+
+impl FromStr for NSEC3 {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut columns = input.split_whitespace();
+
+        let [Some(fqdn), Some(ttl), Some(class), Some(record_type), Some(hash_alg), Some(flags), Some(iterations), Some(salt), Some(next_hashed_owner_name)] =
+            array::from_fn(|_| columns.next())
+        else {
+            return Err(Error::parse("expected at least 9 columns"));
+        };
+
+        check_record_type::<Self>(record_type)?;
+        check_class(class)?;
+        check_salt(salt)?;
+
+        let mut record_types = vec![];
+        for column in columns {
+            record_types.push(column.parse()?);
+        }
+
+        Ok(Self {
+            fqdn: fqdn.parse()?,
+            ttl: ttl.parse()?,
+            hash_alg: hash_alg.parse()?,
+            flags: flags.parse()?,
+            iterations: iterations.parse()?,
+            salt: salt.to_string(),
+            next_hashed_owner_name: next_hashed_owner_name.to_string(),
+            record_types,
+        })
+    }
+}
+
+impl fmt::Display for NSEC3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            fqdn,
+            ttl,
+            hash_alg,
+            flags,
+            iterations,
+            salt,
+            next_hashed_owner_name,
+            record_types,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{hash_alg} {flags} {iterations} {salt}  {next_hashed_owner_name}")?;
+
+        for record_type in record_types {
+            write!(f, " {record_type}")?;
+        }
+
+        Ok(())
+    }
+}
+
+// integer types chosen based on bit sizes in section 4.2 of RFC5155
+#[derive(Clone, Debug)]
+pub struct NSEC3PARAM {
+    pub zone: FQDN,
+    pub ttl: u32,
+    pub hash_alg: u8,
+    pub flags: u8,
+    pub iterations: u16,
+    /// hex encoded; `-` when the salt is empty
+    pub salt: String,
+}
+
+impl FromStr for NSEC3PARAM {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut columns = input.split_whitespace();
+
+        let [Some(zone), Some(ttl), Some(class), Some(record_type), Some(hash_alg), Some(flags), Some(iterations), Some(salt), None] =
+            array::from_fn(|_| columns.next())
+        else {
+            return Err(Error::parse("expected 8 columns"));
+        };
+
+        check_record_type::<Self>(record_type)?;
+        check_class(class)?;
+        check_salt(salt)?;
+
+        Ok(Self {
+            zone: zone.parse()?,
+            ttl: ttl.parse()?,
+            hash_alg: hash_alg.parse()?,
+            flags: flags.parse()?,
+            iterations: iterations.parse()?,
+            salt: salt.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for NSEC3PARAM {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            zone,
+            ttl,
+            hash_alg,
+            flags,
+            iterations,
+            salt,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(
+            f,
+            "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{hash_alg} {flags} {iterations} {salt}"
+        )
     }
+}
 
-    pub fn clear_key_signing_key_bit(&mut self) {
-        self.flags &= !Self::KSK_BIT;
-    }
+#[derive(Clone, Debug)]
+pub struct PTR {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub target: FQDN,
+}
 
-    pub fn is_key_signing_key(&self) -> bool {
-        let mask = Self::KSK_BIT;
-        self.flags & mask == mask
+impl FromStr for PTR {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (fqdn, ttl, rdata) = parse_header::<Self>(input)?;
+        let [target] = rdata_columns(rdata)?;
+
+        Ok(Self {
+            fqdn,
+            ttl,
+            target: target.parse()?,
+        })
     }
+}
 
-    pub fn is_zone_signing_key(&self) -> bool {
-        !self.is_key_signing_key()
+impl fmt::Display for PTR {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { fqdn, ttl, target } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{target}")
     }
 }
 
-impl FromStr for DNSKEY {
-    type Err = Error;
+// integer types chosen based on bit sizes in section 3.1 of RFC4034
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Debug)]
+pub struct RRSIG {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub type_covered: RecordType,
+    pub algorithm: u8,
+    pub labels: u8,
+    pub original_ttl: u32,
+    // NOTE on the wire these are 32-bit UNIX timestamps but in text representation they are
+    // `strftime` formatted
+    // TODO switch these to `chrono::DateTime<Utc>`?
+    pub signature_expiration: u64,
+    pub signature_inception: u64,
+    pub key_tag: u16,
+    pub signer_name: FQDN,
+    /// base64 encoded
+    pub signature: String,
+}
 
-    fn from_str(mut input: &str) -> Result<Self> {
-        if let Some((rr, _comment)) = input.rsplit_once(" ;") {
-            input = rr.trim_end();
-        }
+impl FromStr for RRSIG {
+    type Err = Error;
 
+    fn from_str(input: &str) -> CoreResult<Self, Self::Err> {
         let mut columns = input.split_whitespace();
 
-        let [Some(zone), Some(ttl), Some(class), Some(record_type), Some(flags), Some(protocol), Some(algorithm)] =
+        let [Some(fqdn), Some(ttl), Some(class), Some(record_type), Some(type_covered), Some(algorithm), Some(labels), Some(original_ttl), Some(signature_expiration), Some(signature_inception), Some(key_tag), Some(signer_name)] =
             array::from_fn(|_| columns.next())
         else {
-            return Err("expected at least 7 columns".into());
+            return Err(Error::parse("expected at least 12 columns"));
         };
 
         check_record_type::<Self>(record_type)?;
         check_class(class)?;
 
-        let mut public_key = String::new();
+        let mut signature = String::new();
         for column in columns {
-            public_key.push_str(column);
+            signature.push_str(column);
         }
 
         Ok(Self {
-            zone: zone.parse()?,
+            fqdn: fqdn.parse()?,
             ttl: ttl.parse()?,
-            flags: flags.parse()?,
-            protocol: protocol.parse()?,
+            type_covered: type_covered.parse()?,
             algorithm: algorithm.parse()?,
-            public_key,
+            labels: labels.parse()?,
+            original_ttl: original_ttl.parse()?,
+            signature_expiration: signature_expiration.parse()?,
+            signature_inception: signature_inception.parse()?,
+            key_tag: key_tag.parse()?,
+            signer_name: signer_name.parse()?,
+            signature,
         })
     }
 }
 
-impl fmt::Display for DNSKEY {
+impl fmt::Display for RRSIG {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Self {
-            zone,
+            fqdn,
             ttl,
-            flags,
-            protocol,
+            type_covered,
             algorithm,
-            public_key,
+            labels,
+            original_ttl,
+            signature_expiration,
+            signature_inception,
+            key_tag,
+            signer_name,
+            signature,
         } = self;
 
         let record_type = unqualified_type_name::<Self>();
-        write!(
-            f,
-            "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{flags} {protocol} {algorithm}"
-        )?;
+        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{type_covered} {algorithm} {labels} {original_ttl} {signature_expiration} {signature_inception} {key_tag} {signer_name}")?;
 
-        write_split_long_string(f, public_key)
+        write_split_long_string(f, signature)
     }
 }
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Debug)]
-pub struct DS {
+pub struct SOA {
     pub zone: FQDN,
     pub ttl: u32,
-    pub key_tag: u16,
-    pub algorithm: u8,
-    pub digest_type: u8,
-    pub digest: String,
+    pub nameserver: FQDN,
+    pub admin: FQDN,
+    pub settings: SoaSettings,
 }
 
-impl FromStr for DS {
+impl FromStr for SOA {
     type Err = Error;
 
     fn from_str(input: &str) -> Result<Self> {
         let mut columns = input.split_whitespace();
 
-        let [Some(zone), Some(ttl), Some(class), Some(record_type), Some(key_tag), Some(algorithm), Some(digest_type)] =
+        let [Some(zone), Some(ttl), Some(class), Some(record_type), Some(nameserver), Some(admin), Some(serial), Some(refresh), Some(retry), Some(expire), Some(minimum), None] =
             array::from_fn(|_| columns.next())
         else {
-            return Err("expected at least 7 columns".into());
+            return Err(Error::parse("expected 11 columns"));
         };
 
         check_record_type::<Self>(record_type)?;
         check_class(class)?;
 
-        let mut digest = String::new();
-        for column in columns {
-            digest.push_str(column);
-        }
-
         Ok(Self {
             zone: zone.parse()?,
             ttl: ttl.parse()?,
-            key_tag: key_tag.parse()?,
-            algorithm: algorithm.parse()?,
-            digest_type: digest_type.parse()?,
-            digest,
+            nameserver: nameserver.parse()?,
+            admin: admin.parse()?,
+            settings: SoaSettings {
+                serial: serial.parse()?,
+                refresh: refresh.parse()?,
+                retry: retry.parse()?,
+                expire: expire.parse()?,
+                minimum: minimum.parse()?,
+            },
         })
     }
 }
 
-impl fmt::Display for DS {
+impl fmt::Display for SOA {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Self {
             zone,
             ttl,
-            key_tag,
-            algorithm,
-            digest_type,
-            digest,
+            nameserver,
+            admin,
+            settings,
         } = self;
 
         let record_type = unqualified_type_name::<Self>();
         write!(
             f,
-            "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{key_tag} {algorithm} {digest_type}"
-        )?;
-
-        write_split_long_string(f, digest)
+            "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{nameserver} {admin} {settings}"
+        )
     }
 }
 
-#[derive(Debug)]
-pub struct NS {
-    pub zone: FQDN,
-    pub ttl: u32,
-    pub nameserver: FQDN,
+#[derive(Clone, Debug)]
+pub struct SoaSettings {
+    pub serial: u32,
+    pub refresh: u32,
+    pub retry: u32,
+    pub expire: u32,
+    pub minimum: u32,
 }
 
-impl fmt::Display for NS {
+impl Default for SoaSettings {
+    fn default() -> Self {
+        Self {
+            serial: 2024010101,
+            refresh: 1800,  // 30 minutes
+            retry: 900,     // 15 minutes
+            expire: 604800, // 1 week
+            minimum: 86400, // 1 day
+        }
+    }
+}
+
+impl fmt::Display for SoaSettings {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Self {
-            zone,
-            ttl,
-            nameserver,
+            serial,
+            refresh,
+            retry,
+            expire,
+            minimum,
         } = self;
 
-        let record_type = unqualified_type_name::<Self>();
-        write!(f, "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{nameserver}")
+        write!(f, "{serial} {refresh} {retry} {expire} {minimum}")
     }
 }
 
-impl FromStr for NS {
+/// Service location (RFC2782)
+#[derive(Clone, Debug)]
+pub struct SRV {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub priority: u16,
+    pub weight: u16,
+    pub port: u16,
+    pub target: FQDN,
+}
+
+impl FromStr for SRV {
     type Err = Error;
 
     fn from_str(input: &str) -> Result<Self> {
-        let mut columns = input.split_whitespace();
-
-        let [Some(zone), Some(ttl), Some(class), Some(record_type), Some(nameserver), None] =
-            array::from_fn(|_| columns.next())
-        else {
-            return Err("expected 5 columns".into());
-        };
-
-        check_record_type::<Self>(record_type)?;
-        check_class(class)?;
+        let (fqdn, ttl, rdata) = parse_header::<Self>(input)?;
+        let [priority, weight, port, target] = rdata_columns(rdata)?;
 
         Ok(Self {
-            zone: zone.parse()?,
-            ttl: ttl.parse()?,
-            nameserver: nameserver.parse()?,
+            fqdn,
+            ttl,
+            priority: priority.parse()?,
+            weight: weight.parse()?,
+            port: port.parse()?,
+            target: target.parse()?,
         })
     }
 }
 
-// integer types chosen based on bit sizes in section 3.2 of RFC5155
-#[derive(Debug)]
-pub struct NSEC3 {
+impl fmt::Display for SRV {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            fqdn,
+            ttl,
+            priority,
+            weight,
+            port,
+            target,
+        } = self;
+
+        let record_type = unqualified_type_name::<Self>();
+        write!(
+            f,
+            "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{priority} {weight} {port} {target}"
+        )
+    }
+}
+
+/// General purpose service binding (RFC9460)
+#[derive(Clone, Debug)]
+pub struct SVCB {
     pub fqdn: FQDN,
     pub ttl: u32,
-    pub hash_alg: u8,
-    pub flags: u8,
-    pub iterations: u16,
-    pub salt: String,
-    pub next_hashed_owner_name: String,
-    pub record_types: Vec<RecordType>,
+    /// 0 means that the record is in alias mode
+    pub priority: u16,
+    pub target: FQDN,
+    pub params: Vec<SvcParam>,
 }
 
-impl FromStr for NSEC3 {
+impl FromStr for SVCB {
     type Err = Error;
 
     fn from_str(input: &str) -> Result<Self> {
-        let mut columns = input.split_whitespace();
-
-        let [Some(fqdn), Some(ttl), Some(class), Some(record_type), Some(hash_alg), Some(flags), Some(iterations), Some(salt), Some(next_hashed_owner_name)] =
-            array::from_fn(|_| columns.next())
-        else {
-            return Err("expected at least 9 columns".into());
-        };
-
-        check_record_type::<Self>(record_type)?;
-        check_class(class)?;
-
-        let mut record_types = vec![];
-        for column in columns {
-            record_types.push(column.parse()?);
-        }
+        let (fqdn, ttl, rdata) = parse_header::<Self>(input)?;
+        let (priority, target, params) = parse_svcb_rdata(rdata)?;
 
         Ok(Self {
-            fqdn: fqdn.parse()?,
-            ttl: ttl.parse()?,
-            hash_alg: hash_alg.parse()?,
-            flags: flags.parse()?,
-            iterations: iterations.parse()?,
-            salt: salt.to_string(),
-            next_hashed_owner_name: next_hashed_owner_name.to_string(),
-            record_types,
+            fqdn,
+            ttl,
+            priority,
+            target,
+            params,
         })
     }
 }
 
-impl fmt::Display for NSEC3 {
+impl fmt::Display for SVCB {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Self {
             fqdn,
             ttl,
-            hash_alg,
-            flags,
-            iterations,
-            salt,
-            next_hashed_owner_name,
-            record_types,
+            priority,
+            target,
+            params,
         } = self;
 
-        let record_type = unqualified_type_name::<Self>();
-        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{hash_alg} {flags} {iterations} {salt}  {next_hashed_owner_name}")?;
+        let record_type = unqualified_type_name::<Self>();
+        write!(
+            f,
+            "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{priority} {target}"
+        )?;
+
+        write_svc_params(f, params)
+    }
+}
 
-        for record_type in record_types {
-            write!(f, " {record_type}")?;
-        }
+/// A `key=value` pair of SVCB and HTTPS records (section 2.1 of RFC9460)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvcParam {
+    /// e.g. `alpn`, `port` or `key65333`
+    pub key: String,
+    /// in presentation format, without the surrounding quotes; `None` for keys without a value,
+    /// like `no-default-alpn`
+    pub value: Option<String>,
+}
 
-        Ok(())
+fn parse_svcb_rdata(rdata: &str) -> Result<(u16, FQDN, Vec<SvcParam>)> {
+    let mut tokens = tokenize(rdata)?.into_iter();
+    let (Some(priority), Some(target)) = (tokens.next(), tokens.next()) else {
+        return Err(Error::parse("expected at least 6 columns"));
+    };
+
+    let params = tokens
+        .map(|token| match token.split_once('=') {
+            Some((key, value)) => SvcParam {
+                key: key.to_string(),
+                value: Some(value.to_string()),
+            },
+            None => SvcParam {
+                key: token,
+                value: None,
+            },
+        })
+        .collect();
+
+    Ok((priority.parse()?, target.parse()?, params))
+}
+
+fn write_svc_params(f: &mut fmt::Formatter<'_>, params: &[SvcParam]) -> fmt::Result {
+    for SvcParam { key, value } in params {
+        write!(f, " {key}")?;
+        if let Some(value) = value {
+            // like BIND, only quote the values that may contain arbitrary characters
+            if matches!(
+                key.as_str(),
+                "mandatory" | "port" | "ipv4hint" | "ech" | "ipv6hint"
+            ) {
+                write!(f, "={value}")?;
+            } else {
+                write!(f, "=\"{value}\"")?;
+            }
+        }
     }
+
+    Ok(())
 }
 
-// integer types chosen based on bit sizes in section 4.2 of RFC5155
-#[derive(Debug)]
-pub struct NSEC3PARAM {
-    pub zone: FQDN,
+/// TLS certificate association (RFC6698)
+#[derive(Clone, Debug)]
+pub struct TLSA {
+    pub fqdn: FQDN,
     pub ttl: u32,
-    pub hash_alg: u8,
-    pub flags: u8,
-    pub iterations: u16,
+    pub usage: u8,
+    pub selector: u8,
+    pub matching_type: u8,
+    /// hex encoded
+    pub data: String,
 }
 
-impl FromStr for NSEC3PARAM {
+impl FromStr for TLSA {
     type Err = Error;
 
     fn from_str(input: &str) -> Result<Self> {
-        let mut columns = input.split_whitespace();
-
-        let [Some(zone), Some(ttl), Some(class), Some(record_type), Some(hash_alg), Some(flags), Some(iterations), Some(dash), None] =
-            array::from_fn(|_| columns.next())
+        let (fqdn, ttl, rdata) = parse_header::<Self>(input)?;
+        let mut columns = rdata.split_whitespace();
+        let [Some(usage), Some(selector), Some(matching_type)] = array::from_fn(|_| columns.next())
         else {
-            return Err("expected 8 columns".into());
+            return Err(Error::parse("expected at least 8 columns"));
         };
 
-        check_record_type::<Self>(record_type)?;
-        check_class(class)?;
-
-        if dash != "-" {
-            todo!("salt is not implemented")
-        }
-
         Ok(Self {
-            zone: zone.parse()?,
-            ttl: ttl.parse()?,
-            hash_alg: hash_alg.parse()?,
-            flags: flags.parse()?,
-            iterations: iterations.parse()?,
+            fqdn,
+            ttl,
+            usage: usage.parse()?,
+            selector: selector.parse()?,
+            matching_type: matching_type.parse()?,
+            data: columns.collect(),
         })
     }
 }
 
-impl fmt::Display for NSEC3PARAM {
+impl fmt::Display for TLSA {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Self {
-            zone,
+            fqdn,
             ttl,
-            hash_alg,
-            flags,
-            iterations,
+            usage,
+            selector,
+            matching_type,
+            data,
         } = self;
 
         let record_type = unqualified_type_name::<Self>();
         write!(
             f,
-            "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{hash_alg} {flags} {iterations} -"
-        )
+            "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{usage} {selector} {matching_type}"
+        )?;
+
+        write_split_long_string(f, data)
     }
 }
 
-// integer types chosen based on bit sizes in section 3.1 of RFC4034
-#[allow(clippy::upper_case_acronyms)]
-#[derive(Debug)]
-pub struct RRSIG {
+#[derive(Clone, Debug)]
+pub struct TXT {
     pub fqdn: FQDN,
     pub ttl: u32,
-    pub type_covered: RecordType,
-    pub algorithm: u8,
-    pub labels: u8,
-    pub original_ttl: u32,
-    // NOTE on the wire these are 32-bit UNIX timestamps but in text representation they are
-    // `strftime` formatted
-    // TODO switch these to `chrono::DateTime<Utc>`?
-    pub signature_expiration: u64,
-    pub signature_inception: u64,
-    pub key_tag: u16,
-    pub signer_name: FQDN,
-    /// base64 encoded
-    pub signature: String,
+    /// in presentation format, without the surrounding quotes
+    pub character_strings: Vec<String>,
 }
 
-impl FromStr for RRSIG {
+impl FromStr for TXT {
     type Err = Error;
 
-    fn from_str(input: &str) -> CoreResult<Self, Self::Err> {
-        let mut columns = input.split_whitespace();
-
-        let [Some(fqdn), Some(ttl), Some(class), Some(record_type), Some(type_covered), Some(algorithm), Some(labels), Some(original_ttl), Some(signature_expiration), Some(signature_inception), Some(key_tag), Some(signer_name)] =
-            array::from_fn(|_| columns.next())
-        else {
-            return Err("expected at least 12 columns".into());
-        };
-
-        check_record_type::<Self>(record_type)?;
-        check_class(class)?;
-
-        let mut signature = String::new();
-        for column in columns {
-            signature.push_str(column);
+    fn from_str(input: &str) -> Result<Self> {
+        let (fqdn, ttl, rdata) = parse_header::<Self>(input)?;
+        let character_strings = tokenize(rdata)?;
+        if character_strings.is_empty() {
+            return Err(Error::parse("expected at least 5 columns"));
         }
 
         Ok(Self {
-            fqdn: fqdn.parse()?,
-            ttl: ttl.parse()?,
-            type_covered: type_covered.parse()?,
-            algorithm: algorithm.parse()?,
-            labels: labels.parse()?,
-            original_ttl: original_ttl.parse()?,
-            signature_expiration: signature_expiration.parse()?,
-            signature_inception: signature_inception.parse()?,
-            key_tag: key_tag.parse()?,
-            signer_name: signer_name.parse()?,
-            signature,
+            fqdn,
+            ttl,
+            character_strings,
         })
     }
 }
 
-impl fmt::Display for RRSIG {
+impl fmt::Display for TXT {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Self {
             fqdn,
             ttl,
-            type_covered,
-            algorithm,
-            labels,
-            original_ttl,
-            signature_expiration,
-            signature_inception,
-            key_tag,
-            signer_name,
-            signature,
+            character_strings,
         } = self;
 
         let record_type = unqualified_type_name::<Self>();
-        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{type_covered} {algorithm} {labels} {original_ttl} {signature_expiration} {signature_inception} {key_tag} {signer_name}")?;
+        write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t")?;
 
-        write_split_long_string(f, signature)
+        for (index, character_string) in character_strings.iter().enumerate() {
+            if index != 0 {
+                f.write_char(' ')?;
+            }
+            write!(f, "\"{character_string}\"")?;
+        }
+
+        Ok(())
     }
 }
 
-#[allow(clippy::upper_case_acronyms)]
-#[derive(Debug)]
-pub struct SOA {
+/// Message digest of the whole zone (RFC8976)
+#[derive(Clone, Debug)]
+pub struct ZONEMD {
     pub zone: FQDN,
     pub ttl: u32,
-    pub nameserver: FQDN,
-    pub admin: FQDN,
-    pub settings: SoaSettings,
+    pub serial: u32,
+    pub scheme: u8,
+    pub hash_algorithm: u8,
+    /// hex encoded
+    pub digest: String,
 }
 
-impl FromStr for SOA {
+impl FromStr for ZONEMD {
     type Err = Error;
 
     fn from_str(input: &str) -> Result<Self> {
-        let mut columns = input.split_whitespace();
-
-        let [Some(zone), Some(ttl), Some(class), Some(record_type), Some(nameserver), Some(admin), Some(serial), Some(refresh), Some(retry), Some(expire), Some(minimum), None] =
-            array::from_fn(|_| columns.next())
+        let (zone, ttl, rdata) = parse_header::<Self>(input)?;
+        let mut columns = rdata.split_whitespace();
+        let [Some(serial), Some(scheme), Some(hash_algorithm)] = array::from_fn(|_| columns.next())
         else {
-            return Err("expected 11 columns".into());
+            return Err(Error::parse("expected at least 8 columns"));
         };
 
-        check_record_type::<Self>(record_type)?;
-        check_class(class)?;
-
         Ok(Self {
-            zone: zone.parse()?,
-            ttl: ttl.parse()?,
-            nameserver: nameserver.parse()?,
-            admin: admin.parse()?,
-            settings: SoaSettings {
-                serial: serial.parse()?,
-                refresh: refresh.parse()?,
-                retry: retry.parse()?,
-                expire: expire.parse()?,
-                minimum: minimum.parse()?,
-            },
+            zone,
+            ttl,
+            serial: serial.parse()?,
+            scheme: scheme.parse()?,
+            hash_algorithm: hash_algorithm.parse()?,
+            digest: columns.collect(),
         })
     }
 }
 
-impl fmt::Display for SOA {
+impl fmt::Display for ZONEMD {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Self {
             zone,
             ttl,
-            nameserver,
-            admin,
-            settings,
+            serial,
+            scheme,
+            hash_algorithm,
+            digest,
         } = self;
 
         let record_type = unqualified_type_name::<Self>();
         write!(
             f,
-            "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{nameserver} {admin} {settings}"
-        )
+            "{zone}\t{ttl}\t{CLASS}\t{record_type}\t{serial} {scheme} {hash_algorithm}"
+        )?;
+
+        write_split_long_string(f, digest)
     }
 }
 
-#[derive(Debug)]
-pub struct SoaSettings {
-    pub serial: u32,
-    pub refresh: u32,
-    pub retry: u32,
-    pub expire: u32,
-    pub minimum: u32,
+/// A record of a type this crate does not model, or whose RDATA is in the generic format of
+/// RFC3597, e.g. `example.com. 3600 IN TYPE65534 \# 5 0D13C60000`
+///
+/// `dig` uses the generic format for records of types it does not know, or for all records with
+/// `DigSettings::unknown_format`; the RDATA of the types that it knows is kept in its
+/// presentation format
+#[derive(Clone, Debug)]
+pub struct Unknown {
+    pub fqdn: FQDN,
+    pub ttl: u32,
+    pub type_code: u16,
+    pub rdata: UnknownRdata,
 }
 
-impl Default for SoaSettings {
-    fn default() -> Self {
-        Self {
-            serial: 2024010101,
-            refresh: 1800,  // 30 minutes
-            retry: 900,     // 15 minutes
-            expire: 604800, // 1 week
-            minimum: 86400, // 1 day
-        }
+/// The RDATA of an `Unknown` record
+#[derive(Clone, Debug, PartialEq)]
+pub enum UnknownRdata {
+    /// The RDATA octets, given in the generic format of RFC3597
+    Generic(Vec<u8>),
+    /// The RDATA in the presentation format of its type, e.g. `"ARM64" "Linux"` for HINFO. It
+    /// can't be converted into the wire format
+    Presentation(String),
+}
+
+impl FromStr for Unknown {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut columns = input.split_whitespace();
+
+        let [Some(fqdn), Some(ttl), Some(class), Some(record_type), Some(marker)] =
+            array::from_fn(|_| columns.next())
+        else {
+            return Err(Error::parse("expected at least 5 columns"));
+        };
+
+        check_class(class)?;
+
+        let rdata = if marker == GENERIC_RDATA_MARKER {
+            let len = columns
+                .next()
+                .ok_or_else(|| Error::parse("RDATA length is missing"))?
+                .parse::<usize>()?;
+            let hex = columns.collect::<String>();
+            let rdata = HEXUPPER_PERMISSIVE.decode(hex.as_bytes())?;
+            if rdata.len() != len {
+                return Err(Error::parse(format!(
+                    "RDATA length is {len} but {} bytes were provided",
+                    rdata.len()
+                )));
+            }
+
+            UnknownRdata::Generic(rdata)
+        } else {
+            // everything after the type column, including the whitespace in quoted strings
+            let mut rdata = input;
+            for _ in 0..4 {
+                rdata = rdata.trim_start();
+                rdata = &rdata[rdata.find(char::is_whitespace).unwrap_or(rdata.len())..];
+            }
+            UnknownRdata::Presentation(rdata.trim().to_string())
+        };
+
+        Ok(Self {
+            fqdn: fqdn.parse()?,
+            ttl: ttl.parse()?,
+            type_code: record_type.parse::<RecordType>()?.code(),
+            rdata,
+        })
     }
 }
 
-impl fmt::Display for SoaSettings {
+impl fmt::Display for Unknown {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Self {
-            serial,
-            refresh,
-            retry,
-            expire,
-            minimum,
+            fqdn,
+            ttl,
+            type_code,
+            rdata,
         } = self;
 
-        write!(f, "{serial} {refresh} {retry} {expire} {minimum}")
+        match rdata {
+            UnknownRdata::Generic(rdata) => {
+                write!(
+                    f,
+                    "{fqdn}\t{ttl}\t{CLASS}\tTYPE{type_code}\t{GENERIC_RDATA_MARKER} {}",
+                    rdata.len()
+                )?;
+
+                write_split_long_string(f, &HEXUPPER.encode(rdata))
+            }
+
+            UnknownRdata::Presentation(rdata) => {
+                let record_type = RecordType::from_code(*type_code);
+                write!(f, "{fqdn}\t{ttl}\t{CLASS}\t{record_type}\t{rdata}")
+            }
+        }
     }
 }
 
 fn check_class(class: &str) -> Result<()> {
     if class != "IN" {
-        return Err(format!("unknown class: {class}").into());
+        return Err(Error::parse(format!("unknown class: {class}")));
+    }
+
+    Ok(())
+}
+
+// text representation of the NSEC3 salt field (RFC5155 section 3.3): `-` for an empty salt,
+// otherwise up to 255 bytes encoded as hex
+pub(crate) fn check_salt(salt: &str) -> Result<()> {
+    if salt == "-" {
+        return Ok(());
+    }
+
+    if !salt.len().is_multiple_of(2)
+        || salt.len() > 2 * usize::from(u8::MAX)
+        || !salt.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Err(Error::parse(format!("invalid salt: {salt}")));
     }
 
     Ok(())
@@ -752,7 +2409,9 @@ fn check_record_type<T>(record_type: &str) -> Result<()> {
     if record_type == expected {
         Ok(())
     } else {
-        Err(format!("tried to parse `{record_type}` record as an {expected} record").into())
+        Err(Error::parse(format!(
+            "tried to parse `{record_type}` record as an {expected} record"
+        )))
     }
 }
 
@@ -765,14 +2424,112 @@ fn unqualified_type_name<T>() -> &'static str {
     }
 }
 
-fn write_split_long_string(f: &mut fmt::Formatter<'_>, field: &str) -> fmt::Result {
-    for (index, c) in field.chars().enumerate() {
-        if index % 56 == 0 {
-            f.write_char(' ')?;
+fn write_split_long_string(f: &mut fmt::Formatter<'_>, field: &str) -> fmt::Result {
+    for (index, c) in field.chars().enumerate() {
+        if index % 56 == 0 {
+            f.write_char(' ')?;
+        }
+        f.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// Splits `input` into its owner name, TTL and RDATA, after checking that its class is IN and
+/// its type is `T`
+fn parse_header<T>(input: &str) -> Result<(FQDN, u32, &str)> {
+    let mut rest = input.trim();
+    let mut columns = [""; 4];
+    for column in &mut columns {
+        let (head, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        *column = head;
+        rest = tail.trim_start();
+    }
+
+    let [fqdn, ttl, class, record_type] = columns;
+    if record_type.is_empty() {
+        return Err(Error::parse("expected at least 4 columns"));
+    }
+
+    check_record_type::<T>(record_type)?;
+    check_class(class)?;
+
+    Ok((fqdn.parse()?, ttl.parse()?, rest))
+}
+
+/// Splits `rdata` into exactly `N` whitespace separated columns
+fn rdata_columns<const N: usize>(rdata: &str) -> Result<[&str; N]> {
+    let columns = rdata.split_whitespace().collect::<Vec<_>>();
+    columns
+        .try_into()
+        .map_err(|_| Error::parse(format!("expected {} columns", N + 4)))
+}
+
+/// Like `rdata_columns` but honors quoted strings; see `tokenize`
+fn rdata_tokens<const N: usize>(rdata: &str) -> Result<[String; N]> {
+    tokenize(rdata)?
+        .try_into()
+        .map_err(|_| Error::parse(format!("expected {} columns", N + 4)))
+}
+
+/// Splits `rdata` into whitespace separated tokens. Double quotes group characters, including
+/// whitespace, into a token and are removed; escape sequences (`\X` and `\DDD`) are preserved
+fn tokenize(rdata: &str) -> Result<Vec<String>> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut in_token = false;
+    let mut in_quotes = false;
+    let mut chars = rdata.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let escaped = chars
+                    .next()
+                    .ok_or_else(|| Error::parse("incomplete escape sequence"))?;
+                token.push(c);
+                token.push(escaped);
+                in_token = true;
+            }
+
+            '"' => {
+                in_quotes = !in_quotes;
+                in_token = true;
+            }
+
+            c if c.is_whitespace() && !in_quotes => {
+                if in_token {
+                    tokens.push(mem::take(&mut token));
+                    in_token = false;
+                }
+            }
+
+            c => {
+                token.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(Error::parse("unterminated quoted string"));
+    }
+
+    if in_token {
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Escapes `text` so it can be used as a quoted string in presentation format
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == '"' || c == '\\' {
+            escaped.push('\\');
         }
-        f.write_char(c)?;
+        escaped.push(c);
     }
-    Ok(())
+    escaped
 }
 
 #[cfg(test)]
@@ -802,6 +2559,122 @@ mod tests {
         Ok(())
     }
 
+    // dig AAAA a.root-servers.net
+    const AAAA_INPUT: &str = "a.root-servers.net.	518400	IN	AAAA	2001:503:ba3e::2:30";
+
+    #[test]
+    fn aaaa() -> Result<()> {
+        let aaaa @ AAAA {
+            fqdn,
+            ttl,
+            ipv6_addr,
+        } = &AAAA_INPUT.parse()?;
+
+        assert_eq!("a.root-servers.net.", fqdn.as_str());
+        assert_eq!(518400, *ttl);
+        assert_eq!(
+            Ipv6Addr::new(0x2001, 0x503, 0xba3e, 0, 0, 0, 2, 0x30),
+            *ipv6_addr
+        );
+
+        let output = aaaa.to_string();
+        assert_eq!(AAAA_INPUT, output);
+
+        Ok(())
+    }
+
+    // dig CAA google.com
+    const CAA_INPUT: &str = "google.com.	86400	IN	CAA	0 issue \"pki.goog\"";
+
+    #[test]
+    fn caa() -> Result<()> {
+        let caa @ CAA {
+            fqdn,
+            ttl,
+            flags,
+            tag,
+            value,
+        } = &CAA_INPUT.parse()?;
+
+        assert_eq!("google.com.", fqdn.as_str());
+        assert_eq!(86400, *ttl);
+        assert_eq!(0, *flags);
+        assert_eq!("issue", tag);
+        assert_eq!("pki.goog", value);
+
+        let output = caa.to_string();
+        assert_eq!(CAA_INPUT, output);
+
+        Ok(())
+    }
+
+    // dig CDS nic.cz
+    const CDS_INPUT: &str =
+        "nic.cz.	1800	IN	CDS	20237 13 2 CFF0F3ECDBC529C1F0031BA1840BFB835853B9209ED1E508FFF48451 D7B778E2";
+
+    #[test]
+    fn cds() -> Result<()> {
+        let cds: CDS = CDS_INPUT.parse()?;
+
+        assert_eq!("nic.cz.", cds.zone.as_str());
+        assert_eq!(20237, cds.key_tag);
+        assert_eq!(13, cds.algorithm);
+        assert_eq!(2, cds.digest_type);
+        let expected = "CFF0F3ECDBC529C1F0031BA1840BFB835853B9209ED1E508FFF48451D7B778E2";
+        assert_eq!(expected, cds.digest);
+
+        let output = cds.to_string();
+        assert_eq!(CDS_INPUT, output);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cdnskey_from_dnskey() -> Result<()> {
+        let dnskey: DNSKEY = DNSKEY_INPUT.parse()?;
+        let cdnskey = CDNSKEY::from(dnskey.clone());
+
+        let expected = DNSKEY_INPUT.replacen("DNSKEY", "CDNSKEY", 1);
+        assert_eq!(expected, cdnskey.to_string());
+
+        let cdnskey: CDNSKEY = expected.parse()?;
+        assert_eq!(dnskey.public_key, cdnskey.public_key);
+
+        Ok(())
+    }
+
+    // dig CNAME www.github.com
+    const CNAME_INPUT: &str = "www.github.com.	3600	IN	CNAME	github.com.";
+
+    #[test]
+    fn cname() -> Result<()> {
+        let cname @ CNAME { fqdn, ttl, target } = &CNAME_INPUT.parse()?;
+
+        assert_eq!("www.github.com.", fqdn.as_str());
+        assert_eq!(3600, *ttl);
+        assert_eq!("github.com.", target.as_str());
+
+        let output = cname.to_string();
+        assert_eq!(CNAME_INPUT, output);
+
+        Ok(())
+    }
+
+    const DNAME_INPUT: &str = "example.com.	86400	IN	DNAME	example.net.";
+
+    #[test]
+    fn dname() -> Result<()> {
+        let dname @ DNAME { fqdn, target, .. } = &DNAME_INPUT.parse()?;
+
+        assert_eq!("example.com.", fqdn.as_str());
+        assert_eq!("example.net.", target.as_str());
+
+        let output = dname.to_string();
+        assert_eq!(DNAME_INPUT, output);
+
+        Ok(())
+    }
+
     // dig DNSKEY .
     const DNSKEY_INPUT: &str = ".	1116	IN	DNSKEY	257 3 8 AwEAAaz/tAm8yTn4Mfeh5eyI96WSVexTBAvkMgJzkKTOiW1vkIbzxeF3 +/4RgWOq7HrxRixHlFlExOLAJr5emLvN7SWXgnLh4+B5xQlNVz8Og8kv ArMtNROxVQuCaSnIDdD5LKyWbRd2n9WGe2R8PzgCmr3EgVLrjyBxWezF 0jLHwVN8efS3rCj/EWgvIWgb9tarpVUDK/b58Da+sqqls3eNbuv7pr+e oZG+SrDK6nWeL3c6H5Apxz7LjVc1uTIdsIXxuOLYA4/ilBmSVIzuDWfd RUfhHdY6+cn8HFRm+2hM8AnXGXws9555KrUB5qihylGa8subX2Nn6UwN R1AkUTV74bU=";
 
@@ -843,6 +2716,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn dnskey_key_tag_and_ds() -> Result<()> {
+        // the root zone's KSK-2017
+        let dnskey: DNSKEY = DNSKEY_INPUT.parse()?;
+        assert_eq!(20326, dnskey.key_tag()?);
+
+        let ds = dnskey.ds(DigestType::SHA256)?;
+        assert_eq!(FQDN::ROOT, ds.zone);
+        assert_eq!(20326, ds.key_tag);
+        assert_eq!(8, ds.algorithm);
+        assert_eq!(2, ds.digest_type);
+        let expected = "E06D44B80B8F1D39A95C0B0D7C65D08458E880409BBC683457104237C7F8EC8D";
+        assert_eq!(expected, ds.digest);
+
+        // example from section 5.4 of RFC4034
+        let dnskey: DNSKEY = "dskey.example.com.	86400	IN	DNSKEY	256 3 5 AQOeiiR0GOMYkDshWoSKz9XzfwJr1AYtsmx3TGkJaNXVbfi/2pHm822aJ5iI9BMzNXxeYCmZDRD99WYwYqUSdjMmmAphXdvxegXd/M5+X7OrzKBaMbCVdFLUUh6DhweJBjEVv5f2wwjM9XzcnOf+EPbtG9DMBmADjFDc2w/rljwvFw==".parse()?;
+        assert_eq!(60485, dnskey.key_tag()?);
+
+        let ds = dnskey.ds(DigestType::SHA1)?;
+        assert_eq!(1, ds.digest_type);
+        assert_eq!("2BB183AF5F22588179A53B0A98631FAD1A292118", ds.digest);
+
+        Ok(())
+    }
+
+    // section 2.1 of RFC5011
+    #[test]
+    fn revoke_bit() -> Result<()> {
+        let mut dnskey: DNSKEY = DNSKEY_INPUT.parse()?;
+        assert!(!dnskey.is_revoked());
+
+        dnskey.set_revoke_bit();
+        assert!(dnskey.is_revoked());
+        assert!(dnskey.is_key_signing_key());
+        assert_eq!(385, dnskey.flags);
+        // the REVOKE bit is part of the RDATA so the key tag changes
+        assert_eq!(20326 + 128, dnskey.key_tag()?);
+
+        dnskey.clear_revoke_bit();
+        assert!(!dnskey.is_revoked());
+        assert_eq!(20326, dnskey.key_tag()?);
+
+        Ok(())
+    }
+
     // dig DS com.
     const DS_INPUT: &str =
         "com.	7612	IN	DS	19718 13 2 8ACBB0CD28F41250A80A491389424D341522D946B0DA0C0291F2D3D7 71D7805A";
@@ -872,6 +2790,66 @@ mod tests {
         Ok(())
     }
 
+    // dig HTTPS cloudflare.com
+    const HTTPS_INPUT: &str = "cloudflare.com.	300	IN	HTTPS	1 . alpn=\"h3,h2\" ipv4hint=104.16.132.229,104.16.133.229 ipv6hint=2606:4700::6810:84e5,2606:4700::6810:85e5";
+
+    #[test]
+    fn https() -> Result<()> {
+        let https @ HTTPS {
+            fqdn,
+            ttl,
+            priority,
+            target,
+            params,
+        } = &HTTPS_INPUT.parse()?;
+
+        assert_eq!("cloudflare.com.", fqdn.as_str());
+        assert_eq!(300, *ttl);
+        assert_eq!(1, *priority);
+        assert_eq!(FQDN::ROOT, *target);
+        assert_eq!(3, params.len());
+        assert_eq!(
+            SvcParam {
+                key: "alpn".to_string(),
+                value: Some("h3,h2".to_string()),
+            },
+            params[0]
+        );
+        assert_eq!("ipv4hint", params[1].key);
+        assert_eq!(
+            Some("104.16.132.229,104.16.133.229"),
+            params[1].value.as_deref()
+        );
+
+        let output = https.to_string();
+        assert_eq!(HTTPS_INPUT, output);
+
+        Ok(())
+    }
+
+    // dig MX google.com
+    const MX_INPUT: &str = "google.com.	300	IN	MX	10 smtp.google.com.";
+
+    #[test]
+    fn mx() -> Result<()> {
+        let mx @ MX {
+            fqdn,
+            ttl,
+            preference,
+            exchange,
+        } = &MX_INPUT.parse()?;
+
+        assert_eq!("google.com.", fqdn.as_str());
+        assert_eq!(300, *ttl);
+        assert_eq!(10, *preference);
+        assert_eq!("smtp.google.com.", exchange.as_str());
+
+        let output = mx.to_string();
+        assert_eq!(MX_INPUT, output);
+
+        Ok(())
+    }
+
     // dig NS .
     const NS_INPUT: &str = ".	86400	IN	NS	f.root-servers.net.";
 
@@ -893,6 +2871,39 @@ mod tests {
         Ok(())
     }
 
+    // dig +dnssec A unicorn.nameservers.com. (zone signed with NSEC)
+    const NSEC_INPUT: &str =
+        "nameservers.com.	86400	IN	NSEC	example.nameservers.com. NS SOA RRSIG NSEC DNSKEY";
+
+    #[test]
+    fn nsec() -> Result<()> {
+        let nsec @ NSEC {
+            fqdn,
+            ttl,
+            next_domain,
+            record_types,
+        } = &NSEC_INPUT.parse()?;
+
+        assert_eq!("nameservers.com.", fqdn.as_str());
+        assert_eq!(86400, *ttl);
+        assert_eq!("example.nameservers.com.", next_domain.as_str());
+        assert_eq!(
+            [
+                RecordType::NS,
+                RecordType::SOA,
+                RecordType::RRSIG,
+                RecordType::NSEC,
+                RecordType::DNSKEY
+            ],
+            record_types.as_slice()
+        );
+
+        let output = nsec.to_string();
+        assert_eq!(NSEC_INPUT, output);
+
+        Ok(())
+    }
+
     // dig +dnssec A unicorn.example.com.
     const NSEC3_INPUT: &str = "abhif1b25fhcda5amfk5hnrsh6jid2ki.example.com.	3571	IN	NSEC3	1 0 5 53BCBC5805D2B761  GVPMD82B8ER38VUEGP72I721LIH19RGR A NS SOA MX TXT AAAA RRSIG DNSKEY NSEC3PARAM";
 
@@ -951,6 +2962,7 @@ mod tests {
             hash_alg,
             flags,
             iterations,
+            salt,
         } = &NSEC3PARAM_INPUT.parse()?;
 
         assert_eq!(FQDN::COM, *zone);
@@ -958,6 +2970,7 @@ mod tests {
         assert_eq!(1, *hash_alg);
         assert_eq!(0, *flags);
         assert_eq!(0, *iterations);
+        assert_eq!("-", salt);
 
         let output = nsec3param.to_string();
         assert_eq!(NSEC3PARAM_INPUT, output);
@@ -965,6 +2978,49 @@ mod tests {
         Ok(())
     }
 
+    // dig NSEC3PARAM example.com.
+    const SALTED_NSEC3PARAM_INPUT: &str = "example.com.	3600	IN	NSEC3PARAM	1 0 5 53BCBC5805D2B761";
+
+    #[test]
+    fn salted_nsec3param() -> Result<()> {
+        let nsec3param @ NSEC3PARAM {
+            iterations, salt, ..
+        } = &SALTED_NSEC3PARAM_INPUT.parse()?;
+
+        assert_eq!(5, *iterations);
+        assert_eq!("53BCBC5805D2B761", salt);
+
+        let output = nsec3param.to_string();
+        assert_eq!(SALTED_NSEC3PARAM_INPUT, output);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_salt() {
+        for salt in ["53BCBC5805D2B76", "SALT", ""] {
+            let input = format!("com.\t86238\tIN\tNSEC3PARAM\t1 0 0 {salt}");
+            assert!(input.parse::<NSEC3PARAM>().is_err(), "{input}");
+        }
+    }
+
+    // dig -x 8.8.8.8
+    const PTR_INPUT: &str = "8.8.8.8.in-addr.arpa.	20308	IN	PTR	dns.google.";
+
+    #[test]
+    fn ptr() -> Result<()> {
+        let ptr @ PTR { fqdn, ttl, target } = &PTR_INPUT.parse()?;
+
+        assert_eq!("8.8.8.8.in-addr.arpa.", fqdn.as_str());
+        assert_eq!(20308, *ttl);
+        assert_eq!("dns.google.", target.as_str());
+
+        let output = ptr.to_string();
+        assert_eq!(PTR_INPUT, output);
+
+        Ok(())
+    }
+
     // dig +dnssec SOA .
     const RRSIG_INPUT: &str = ".	1800	IN	RRSIG	SOA 7 0 1800 20240306132701 20240207132701 11264 . wXpRU4elJPGYm2kgVVsIwGf1IkYJcQ3UE4mwmItWdxj0XWSWY07MO4Ll DMJgsE0u64Q/345Ck7+aQ904uLebwCvpFnsmkyCxk82XIAfHN9FiwzSy qoR/zZEvBONaej3vrvsqPwh8q/pvypLft9647HcFdwY0juzZsbrAaDAX 8WY=";
 
@@ -1028,16 +3084,293 @@ mod tests {
         Ok(())
     }
 
+    // dig SRV _xmpp-server._tcp.jabber.org
+    const SRV_INPUT: &str = "_xmpp-server._tcp.jabber.org.	900	IN	SRV	30 30 5269 zeus.jabber.org.";
+
+    #[test]
+    fn srv() -> Result<()> {
+        let srv @ SRV {
+            fqdn,
+            ttl,
+            priority,
+            weight,
+            port,
+            target,
+        } = &SRV_INPUT.parse()?;
+
+        assert_eq!("_xmpp-server._tcp.jabber.org.", fqdn.as_str());
+        assert_eq!(900, *ttl);
+        assert_eq!(30, *priority);
+        assert_eq!(30, *weight);
+        assert_eq!(5269, *port);
+        assert_eq!("zeus.jabber.org.", target.as_str());
+
+        let output = srv.to_string();
+        assert_eq!(SRV_INPUT, output);
+
+        Ok(())
+    }
+
+    // example from section 2.4.3 of RFC9460
+    const SVCB_INPUT: &str = "_8443._foo.api.example.com.	7200	IN	SVCB	0 svc4.example.net.";
+
+    #[test]
+    fn svcb_alias_mode() -> Result<()> {
+        let svcb @ SVCB {
+            priority,
+            target,
+            params,
+            ..
+        } = &SVCB_INPUT.parse()?;
+
+        assert_eq!(0, *priority);
+        assert_eq!("svc4.example.net.", target.as_str());
+        assert!(params.is_empty());
+
+        let output = svcb.to_string();
+        assert_eq!(SVCB_INPUT, output);
+
+        Ok(())
+    }
+
+    #[test]
+    fn svcb_key_without_value() -> Result<()> {
+        let input =
+            "example.com.\t300\tIN\tSVCB\t16 foo.example.org. alpn=\"h2\" no-default-alpn port=53";
+        let svcb: SVCB = input.parse()?;
+
+        assert_eq!(
+            SvcParam {
+                key: "no-default-alpn".to_string(),
+                value: None,
+            },
+            svcb.params[1]
+        );
+        assert_eq!(input, svcb.to_string());
+
+        Ok(())
+    }
+
+    // dig TLSA _25._tcp.mail.ietf.org
+    const TLSA_INPUT: &str = "_25._tcp.mail.ietf.org.	1800	IN	TLSA	3 1 1 0C72AC70B745AC19998811B131D662C9AC69DBDBE7CB23E5B514B566 64C5D3D6";
+
+    #[test]
+    fn tlsa() -> Result<()> {
+        let tlsa @ TLSA {
+            usage,
+            selector,
+            matching_type,
+            data,
+            ..
+        } = &TLSA_INPUT.parse()?;
+
+        assert_eq!(3, *usage);
+        assert_eq!(1, *selector);
+        assert_eq!(1, *matching_type);
+        let expected = "0C72AC70B745AC19998811B131D662C9AC69DBDBE7CB23E5B514B56664C5D3D6";
+        assert_eq!(expected, data);
+
+        let output = tlsa.to_string();
+        assert_eq!(TLSA_INPUT, output);
+
+        Ok(())
+    }
+
+    // dig TXT example.com
+    const TXT_INPUT: &str = "example.com.	86400	IN	TXT	\"v=spf1 -all\" \"say \\\"hi\\\"\"";
+
+    #[test]
+    fn txt() -> Result<()> {
+        let txt @ TXT {
+            fqdn,
+            ttl,
+            character_strings,
+        } = &TXT_INPUT.parse()?;
+
+        assert_eq!("example.com.", fqdn.as_str());
+        assert_eq!(86400, *ttl);
+        assert_eq!(
+            ["v=spf1 -all", "say \\\"hi\\\""],
+            character_strings.as_slice()
+        );
+
+        let output = txt.to_string();
+        assert_eq!(TXT_INPUT, output);
+
+        Ok(())
+    }
+
+    #[test]
+    fn txt_constructor_escapes() -> Result<()> {
+        let txt = Record::txt(FQDN("example.com.")?, "say \"hi\"");
+        assert_eq!(
+            "example.com.\t86400\tIN\tTXT\t\"say \\\"hi\\\"\"",
+            txt.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn txt_rejects_unterminated_string() {
+        let input = "example.com.\t86400\tIN\tTXT\t\"v=spf1";
+        assert!(input.parse::<TXT>().is_err());
+    }
+
+    // dig ZONEMD root-servers.net
+    const ZONEMD_INPUT: &str = "root-servers.net.	3600000	IN	ZONEMD	2023102900 1 1 7C6CF7A8F9F3B91D0E1E3AEDEB2D16E87E0C0AAD7A1C2C7DFBD3ED0D 40E3F5F9F64E8D6C6F3B2F8E2BB2D1A87BCD3A4C";
+
+    #[test]
+    fn zonemd() -> Result<()> {
+        let zonemd @ ZONEMD {
+            zone,
+            serial,
+            scheme,
+            hash_algorithm,
+            digest,
+            ..
+        } = &ZONEMD_INPUT.parse()?;
+
+        assert_eq!("root-servers.net.", zone.as_str());
+        assert_eq!(2023102900, *serial);
+        assert_eq!(1, *scheme);
+        assert_eq!(1, *hash_algorithm);
+        assert_eq!(96, digest.len());
+
+        let output = zonemd.to_string();
+        assert_eq!(ZONEMD_INPUT, output);
+
+        Ok(())
+    }
+
+    // example from section 5 of RFC3597
+    const UNKNOWN_INPUT: &str = "a.example.	3600	IN	TYPE731	\\# 6 ABCDEF012345";
+
+    #[test]
+    fn unknown() -> Result<()> {
+        let unknown @ Unknown {
+            fqdn,
+            ttl,
+            type_code,
+            rdata,
+        } = &UNKNOWN_INPUT.parse()?;
+
+        assert_eq!("a.example.", fqdn.as_str());
+        assert_eq!(3600, *ttl);
+        assert_eq!(731, *type_code);
+        assert_eq!(
+            UnknownRdata::Generic(vec![0xAB, 0xCD, 0xEF, 0x01, 0x23, 0x45]),
+            *rdata
+        );
+
+        let output = unknown.to_string();
+        assert_eq!(UNKNOWN_INPUT, output);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_with_empty_rdata() -> Result<()> {
+        let input = "a.example.\t0\tIN\tTYPE62347\t\\# 0";
+        let unknown: Unknown = input.parse()?;
+
+        assert_eq!(UnknownRdata::Generic(vec![]), unknown.rdata);
+        assert_eq!(input, unknown.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_rejects_length_mismatch() {
+        let input = "a.example.\t0\tIN\tTYPE731\t\\# 4 ABCDEF";
+        assert!(input.parse::<Unknown>().is_err());
+    }
+
+    #[test]
+    fn unmodeled_type_in_presentation_format() -> Result<()> {
+        for (input, type_code) in [
+            (
+                "host.example.\t3600\tIN\tHINFO\t\"ARM64\" \"Linux 6.1\"",
+                13,
+            ),
+            ("1.example.\t3600\tIN\tSSHFP\t1 1 ABCDEF0123", 44),
+            (
+                "example.\t3600\tIN\tNAPTR\t100 10 \"S\" \"SIP+D2U\" \"\" _sip._udp.example.",
+                35,
+            ),
+        ] {
+            let record: Record = input.parse()?;
+            assert_eq!(RecordType::Unknown(type_code), record.record_type());
+            assert_eq!(input, record.to_string());
+
+            let unknown = record.try_into_unknown().expect("not an unknown record");
+            assert!(matches!(unknown.rdata, UnknownRdata::Presentation(_)));
+            assert!(Record::Unknown(unknown).to_wire().is_err());
+        }
+
+        assert_eq!(RecordType::Unknown(13), "HINFO".parse()?);
+        assert_eq!("HINFO", RecordType::Unknown(13).to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn known_type_in_generic_format() -> Result<()> {
+        let record: Record = "a.example.\t0\tIN\tA\t\\# 4 0A000001".parse()?;
+
+        assert_eq!(RecordType::A, record.record_type());
+        let unknown = record.try_into_unknown().expect("not an unknown record");
+        assert_eq!(1, unknown.type_code);
+        assert_eq!(
+            "a.example.\t0\tIN\tTYPE1\t\\# 4 0A000001",
+            unknown.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn generic_record_type() -> Result<()> {
+        assert_eq!(RecordType::A, "TYPE1".parse()?);
+        assert_eq!(RecordType::Unknown(65534), "TYPE65534".parse()?);
+        assert_eq!("TYPE65534", RecordType::Unknown(65534).to_string());
+        assert_eq!(RecordType::CAA, RecordType::from_code(257));
+        assert!("TYPE65536".parse::<RecordType>().is_err());
+        assert!("FOO".parse::<RecordType>().is_err());
+
+        // BIND keeps track of the signing process with private type records
+        let nsec: NSEC =
+            "example.\t0\tIN\tNSEC\ta.example. NS SOA RRSIG NSEC DNSKEY TYPE65534".parse()?;
+        assert_eq!(Some(&RecordType::Unknown(65534)), nsec.record_types.last());
+
+        Ok(())
+    }
+
     #[test]
     fn any() -> Result<()> {
         assert!(matches!(A_INPUT.parse()?, Record::A(..)));
+        assert!(matches!(AAAA_INPUT.parse()?, Record::AAAA(..)));
+        assert!(matches!(CAA_INPUT.parse()?, Record::CAA(..)));
+        assert!(matches!(CDS_INPUT.parse()?, Record::CDS(..)));
+        assert!(matches!(CNAME_INPUT.parse()?, Record::CNAME(..)));
+        assert!(matches!(DNAME_INPUT.parse()?, Record::DNAME(..)));
         assert!(matches!(DNSKEY_INPUT.parse()?, Record::DNSKEY(..)));
         assert!(matches!(DS_INPUT.parse()?, Record::DS(..)));
+        assert!(matches!(HTTPS_INPUT.parse()?, Record::HTTPS(..)));
+        assert!(matches!(MX_INPUT.parse()?, Record::MX(..)));
         assert!(matches!(NS_INPUT.parse()?, Record::NS(..)));
+        assert!(matches!(NSEC_INPUT.parse()?, Record::NSEC(..)));
         assert!(matches!(NSEC3_INPUT.parse()?, Record::NSEC3(..)));
         assert!(matches!(NSEC3PARAM_INPUT.parse()?, Record::NSEC3PARAM(..)));
+        assert!(matches!(PTR_INPUT.parse()?, Record::PTR(..)));
         assert!(matches!(RRSIG_INPUT.parse()?, Record::RRSIG(..)));
         assert!(matches!(SOA_INPUT.parse()?, Record::SOA(..)));
+        assert!(matches!(SRV_INPUT.parse()?, Record::SRV(..)));
+        assert!(matches!(SVCB_INPUT.parse()?, Record::SVCB(..)));
+        assert!(matches!(TLSA_INPUT.parse()?, Record::TLSA(..)));
+        assert!(matches!(TXT_INPUT.parse()?, Record::TXT(..)));
+        assert!(matches!(ZONEMD_INPUT.parse()?, Record::ZONEMD(..)));
+        assert!(matches!(UNKNOWN_INPUT.parse()?, Record::Unknown(..)));
 
         Ok(())
     }
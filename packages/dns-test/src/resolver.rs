@@ -2,7 +2,7 @@ use core::fmt::Write;
 use std::io::{BufRead, BufReader};
 use std::net::Ipv4Addr;
 
-use crate::container::{Child, Container, Network};
+use crate::container::{Child, Clock, Container, Network};
 use crate::implementation::{Config, Role};
 use crate::record::DNSKEY;
 use crate::trust_anchor::TrustAnchor;
@@ -20,7 +20,10 @@ impl Resolver {
     #[allow(clippy::new_ret_no_self)]
     pub fn new(network: &Network, root: Root) -> ResolverSettings {
         ResolverSettings {
+            clock: Clock::Real,
             ede: false,
+            hold_down: None,
+            managed_trust_anchor: false,
             network: network.clone(),
             roots: vec![root],
             trust_anchor: TrustAnchor::empty(),
@@ -62,7 +65,8 @@ kill -TERM $(cat {pidfile})"
         // the hickory-dns binary does not do signal handling so it won't shut down gracefully; we
         // will still get some logs so we'll ignore the fact that it fails to shut down ...
         if !implementation.is_hickory() && !output.status.success() {
-            return Err(format!("could not terminate the `{}` process", implementation).into());
+            let command = implementation.cmd_args(Role::Resolver);
+            return Err(container.command_failed(command, output));
         }
 
         assert!(
@@ -74,8 +78,13 @@ kill -TERM $(cat {pidfile})"
 }
 
 pub struct ResolverSettings {
+    clock: Clock,
     /// Extended DNS Errors (RFC8914)
     ede: bool,
+    /// RFC5011 hold-down time, in seconds
+    hold_down: Option<u32>,
+    /// Automated updates of the trust anchor (RFC5011)
+    managed_trust_anchor: bool,
     network: Network,
     roots: Vec<Root>,
     trust_anchor: TrustAnchor,
@@ -88,6 +97,7 @@ impl ResolverSettings {
     pub fn start(&self, implementation: &Implementation) -> Result<Resolver> {
         let image = implementation.clone().into();
         let container = Container::run(&image, &self.network)?;
+        container.set_clock(self.clock)?;
 
         let mut hints = String::new();
         for root in &self.roots {
@@ -101,6 +111,8 @@ impl ResolverSettings {
             use_dnssec,
             netmask: self.network.netmask(),
             ede: self.ede,
+            managed_trust_anchor: self.managed_trust_anchor,
+            hold_down: self.hold_down,
         };
         container.cp(
             implementation.conf_file_path(config.role()),
@@ -110,20 +122,36 @@ impl ResolverSettings {
         if use_dnssec {
             let path = if implementation.is_bind() {
                 "/etc/bind/bind.keys"
+            } else if self.managed_trust_anchor {
+                // unbound needs to be able to update this file
+                "/var/lib/unbound/trusted-key.key"
             } else {
                 "/etc/trusted-key.key"
             };
 
-            let contents = if implementation.is_bind() {
-                self.trust_anchor.delv()
-            } else {
+            let contents = if !implementation.is_bind() {
                 self.trust_anchor.to_string()
+            } else if self.managed_trust_anchor {
+                self.trust_anchor.initial_keys()
+            } else {
+                self.trust_anchor.delv()
             };
 
             container.cp(path, &contents)?;
         }
 
-        let mut child = container.spawn(implementation.cmd_args(config.role()))?;
+        let mut cmd_args = implementation.cmd_args(config.role()).to_vec();
+        // BIND has no configuration option for the hold-down time but it can scale its RFC5011
+        // timers: `-T mkeytimers=<hour>/<day>/<month>`, where the hold-down time is one "month"
+        let mkeytimers;
+        if let (true, Some(hold_down)) = (implementation.is_bind(), self.hold_down) {
+            let hour = (hold_down / 720).max(1);
+            let day = (hold_down / 30).max(1);
+            mkeytimers = format!("mkeytimers={hour}/{day}/{hold_down}");
+            cmd_args.extend(["-T", &mkeytimers]);
+        }
+
+        let mut child = container.spawn(&cmd_args)?;
 
         // For HickoryDNS we need to wait until its start sequence finished. Only then the server is able
         // to accept connections. The start sequence logs are consumed here.
@@ -152,6 +180,29 @@ impl ResolverSettings {
         self
     }
 
+    /// Lets the resolver update its trust anchor as described in RFC5011 instead of using the
+    /// trust anchor keys as static keys
+    ///
+    /// Not supported by hickory
+    pub fn managed_trust_anchor(&mut self) -> &mut Self {
+        self.managed_trust_anchor = true;
+        self
+    }
+
+    /// Runs the resolver with a faked clock, e.g. to validate signatures that have expired or that
+    /// are not valid yet
+    pub fn clock(&mut self, clock: Clock) -> &mut Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Overrides the RFC5011 add and remove hold-down times, which are 30 days by default, so
+    /// that they can elapse within a test
+    pub fn hold_down(&mut self, seconds: u32) -> &mut Self {
+        self.hold_down = Some(seconds);
+        self
+    }
+
     /// Adds a root hint
     pub fn root(&mut self, root: Root) -> &mut Self {
         self.roots.push(root);
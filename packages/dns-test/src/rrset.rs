@@ -0,0 +1,230 @@
+//! Resource record sets (RRsets)
+
+use crate::record::{Record, RecordType, Unknown, UnknownRdata, RRSIG};
+use crate::wire::{self, CanonicalName};
+use crate::{Result, FQDN};
+
+/// The records that share an owner name, class and type, together with the RRSIG records that
+/// cover them
+///
+/// As all records in this crate are of class IN, records are grouped by owner name and type.
+/// Owner names are compared case-insensitively.
+#[derive(Clone, Debug)]
+pub struct RRset {
+    fqdn: FQDN,
+    record_type: RecordType,
+    /// In canonical order (section 6.3 of RFC4034), without duplicates; empty if only RRSIG
+    /// records that cover this RRset were grouped
+    pub records: Vec<Record>,
+    /// The RRSIG records whose `type_covered` is this RRset's type, in canonical order
+    pub rrsigs: Vec<RRSIG>,
+}
+
+impl RRset {
+    /// Groups `records` into RRsets, attaching the RRSIG records to the RRset they cover; the
+    /// RRsets are sorted in canonical order: by owner name (section 6.1 of RFC4034) and then by
+    /// type
+    pub fn group(records: impl IntoIterator<Item = Record>) -> Result<Vec<Self>> {
+        let mut rrsets: Vec<(CanonicalName, Self)> = vec![];
+        for record in records {
+            let owner = wire::canonical_name(record.fqdn());
+            let record_type = match &record {
+                Record::RRSIG(rrsig) => rrsig.type_covered,
+                _ => record.record_type(),
+            };
+
+            let index = match rrsets
+                .iter()
+                .position(|(key, rrset)| *key == owner && rrset.record_type == record_type)
+            {
+                Some(index) => index,
+                None => {
+                    rrsets.push((
+                        owner,
+                        Self {
+                            fqdn: record.fqdn().clone(),
+                            record_type,
+                            records: vec![],
+                            rrsigs: vec![],
+                        },
+                    ));
+                    rrsets.len() - 1
+                }
+            };
+
+            let rrset = &mut rrsets[index].1;
+            match record {
+                Record::RRSIG(rrsig) => rrset.rrsigs.push(rrsig),
+                record => rrset.records.push(record),
+            }
+        }
+
+        for (_, rrset) in &mut rrsets {
+            rrset.records = canonical_order(rrset.records.drain(..))?;
+            rrset.rrsigs = canonical_order(rrset.rrsigs.drain(..).map(Record::RRSIG))?
+                .into_iter()
+                .filter_map(|record| record.try_into_rrsig().ok())
+                .collect();
+        }
+
+        rrsets.sort_by(|(a_owner, a), (b_owner, b)| {
+            a_owner
+                .cmp(b_owner)
+                .then(a.record_type.code().cmp(&b.record_type.code()))
+        });
+
+        Ok(rrsets.into_iter().map(|(_, rrset)| rrset).collect())
+    }
+
+    /// The owner name, as it appeared in the first grouped record
+    pub fn fqdn(&self) -> &FQDN {
+        &self.fqdn
+    }
+
+    pub fn record_type(&self) -> RecordType {
+        self.record_type
+    }
+
+    pub fn is_signed(&self) -> bool {
+        !self.rrsigs.is_empty()
+    }
+}
+
+/// Sorts records by their canonical RDATA and removes duplicates
+///
+/// Records whose RDATA is only known in presentation format are sorted by that text instead
+fn canonical_order(records: impl IntoIterator<Item = Record>) -> Result<Vec<Record>> {
+    let mut keyed = records
+        .into_iter()
+        .map(|record| {
+            let key = match &record {
+                Record::Unknown(Unknown {
+                    rdata: UnknownRdata::Presentation(rdata),
+                    ..
+                }) => rdata.as_bytes().to_vec(),
+                _ => wire::rdata(&record)?,
+            };
+            Ok((key, record))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    keyed.dedup_by(|(a, _), (b, _)| a == b);
+
+    Ok(keyed.into_iter().map(|(_, record)| record).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn records(lines: &[&str]) -> Result<Vec<Record>> {
+        lines.iter().map(|line| line.parse()).collect()
+    }
+
+    #[test]
+    fn groups_by_owner_and_type() -> Result<()> {
+        let rrsets = RRset::group(records(&[
+            "a.example.\t300\tIN\tA\t192.0.2.2",
+            "example.\t300\tIN\tNS\tns.example.",
+            "A.example.\t300\tIN\tA\t192.0.2.1",
+            "a.example.\t300\tIN\tAAAA\t2001:db8::1",
+        ])?)?;
+
+        let summary = rrsets
+            .iter()
+            .map(|rrset| {
+                (
+                    rrset.fqdn().as_str(),
+                    rrset.record_type(),
+                    rrset.records.len(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            [
+                ("example.", RecordType::NS, 1),
+                ("a.example.", RecordType::A, 2),
+                ("a.example.", RecordType::AAAA, 1),
+            ],
+            summary.as_slice()
+        );
+
+        // sorted by RDATA, regardless of the input order
+        let addrs = rrsets[1]
+            .records
+            .iter()
+            .map(|record| record.clone().try_into_a().unwrap().ipv4_addr.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(["192.0.2.1", "192.0.2.2"], addrs.as_slice());
+
+        Ok(())
+    }
+
+    #[test]
+    fn attaches_rrsigs() -> Result<()> {
+        let rrsets = RRset::group(records(&[
+            ".\t1800\tIN\tRRSIG\tSOA 7 0 1800 20240306132701 20240207132701 11264 . wXpRU4elJPGYm2kgVVsIwGf1IkYJcQ3UE4mwmItWdxj0XWSWY07MO4Ll",
+            ".\t15633\tIN\tSOA\ta.root-servers.net. nstld.verisign-grs.com. 2024020501 1800 900 604800 86400",
+            ".\t1800\tIN\tRRSIG\tNS 7 0 1800 20240306132701 20240207132701 11264 . wXpRU4elJPGYm2kgVVsIwGf1IkYJcQ3UE4mwmItWdxj0XWSWY07MO4Ll",
+        ])?)?;
+
+        let [ns, soa] = rrsets.try_into().expect("exactly two RRsets");
+        assert_eq!(RecordType::NS, ns.record_type());
+        assert!(ns.records.is_empty());
+        assert!(ns.is_signed());
+
+        assert_eq!(RecordType::SOA, soa.record_type());
+        assert_eq!(1, soa.records.len());
+        let [rrsig] = soa.rrsigs.try_into().expect("exactly one RRSIG");
+        assert_eq!(RecordType::SOA, rrsig.type_covered);
+
+        Ok(())
+    }
+
+    #[test]
+    fn removes_duplicates() -> Result<()> {
+        let rrsets = RRset::group(records(&[
+            "a.example.\t300\tIN\tA\t192.0.2.1",
+            "a.example.\t60\tIN\tA\t192.0.2.1",
+        ])?)?;
+
+        let [rrset] = rrsets.try_into().expect("exactly one RRset");
+        assert_eq!(1, rrset.records.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn groups_records_only_known_in_presentation_format() -> Result<()> {
+        let rrsets = RRset::group(records(&[
+            "host.example.	300	IN	HINFO	\"x86_64\" \"Linux\"",
+            "host.example.	300	IN	A	192.0.2.1",
+            "host.example.	300	IN	HINFO	\"ARM64\" \"Linux\"",
+            "host.example.	60	IN	HINFO	\"ARM64\" \"Linux\"",
+        ])?)?;
+
+        let [a, hinfo] = rrsets.try_into().expect("exactly two RRsets");
+        assert_eq!(RecordType::A, a.record_type());
+        assert_eq!(RecordType::Unknown(13), hinfo.record_type());
+
+        let rdata = hinfo
+            .records
+            .iter()
+            .map(|record| match record {
+                Record::Unknown(unknown) => unknown.rdata.clone(),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            [
+                UnknownRdata::Presentation("\"ARM64\" \"Linux\"".to_string()),
+                UnknownRdata::Presentation("\"x86_64\" \"Linux\"".to_string()),
+            ],
+            rdata.as_slice()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,895 @@
+//! In-process DNSSEC signing of zone files
+//!
+//! This is an alternative to the `ldns-keygen` + `ldns-signzone` + `ldns-key2ds` pipeline that
+//! runs inside the name server containers. Individual RRsets can be signed with `sign_rrset`,
+//! which makes it possible to corrupt records *before* their signatures are computed
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::slice;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use data_encoding::{BASE32HEX_NOPAD, BASE64, HEXUPPER_PERMISSIVE};
+use ring::digest;
+use ring::rand::SystemRandom;
+use ring::signature::{
+    EcdsaKeyPair, Ed25519KeyPair, KeyPair as _, ECDSA_P256_SHA256_FIXED_SIGNING,
+    ECDSA_P384_SHA384_FIXED_SIGNING,
+};
+
+use crate::name_server::Nsec;
+use crate::record::{
+    self, Algorithm, DigestType, Record, RecordType, DNSKEY, DS, NSEC, NSEC3, NSEC3PARAM, RRSIG,
+};
+use crate::wire::{self, CanonicalName};
+use crate::zone_file::ZoneFile;
+use crate::{Error, Result, DEFAULT_TTL, FQDN};
+
+const ZONE_KEY_FLAG: u16 = 256;
+const SECURE_ENTRY_POINT_FLAG: u16 = 1;
+const NSEC3_SHA1: u8 = 1;
+const NSEC3_OPT_OUT_FLAG: u8 = 1;
+
+/// How long signatures are valid for, by default
+const VALIDITY: u64 = 30 * 24 * 60 * 60; // 30 days
+
+/// A DNSKEY and its private counterpart
+#[derive(Clone)]
+pub struct SigningKey {
+    dnskey: DNSKEY,
+    key_pair: Arc<KeyPair>,
+    // combined signing key (CSK): signs the DNSKEY RRset and all other RRsets
+    is_combined: bool,
+}
+
+enum KeyPair {
+    Ecdsa(EcdsaKeyPair),
+    Ed25519(Ed25519KeyPair),
+}
+
+impl SigningKey {
+    /// Generates a new key signing key (KSK) for `zone`
+    pub fn key_signing_key(zone: FQDN, algorithm: Algorithm) -> Result<Self> {
+        Self::generate(zone, algorithm, ZONE_KEY_FLAG | SECURE_ENTRY_POINT_FLAG)
+    }
+
+    /// Generates a new zone signing key (ZSK) for `zone`
+    pub fn zone_signing_key(zone: FQDN, algorithm: Algorithm) -> Result<Self> {
+        Self::generate(zone, algorithm, ZONE_KEY_FLAG)
+    }
+
+    /// Generates a new combined signing key (CSK) for `zone`; a single key that plays both the
+    /// KSK and the ZSK roles
+    pub fn combined_signing_key(zone: FQDN, algorithm: Algorithm) -> Result<Self> {
+        let mut key = Self::key_signing_key(zone, algorithm)?;
+        key.is_combined = true;
+        Ok(key)
+    }
+
+    fn generate(zone: FQDN, algorithm: Algorithm, flags: u16) -> Result<Self> {
+        let rng = SystemRandom::new();
+
+        let (key_pair, public_key) = match algorithm {
+            Algorithm::ECDSAP256SHA256 | Algorithm::ECDSAP384SHA384 => {
+                let signing = if algorithm == Algorithm::ECDSAP256SHA256 {
+                    &ECDSA_P256_SHA256_FIXED_SIGNING
+                } else {
+                    &ECDSA_P384_SHA384_FIXED_SIGNING
+                };
+                let pkcs8 = EcdsaKeyPair::generate_pkcs8(signing, &rng)?;
+                let key_pair = EcdsaKeyPair::from_pkcs8(signing, pkcs8.as_ref(), &rng)?;
+                // RFC6605 section 4: the uncompressed point without its 0x04 prefix
+                let public_key = key_pair.public_key().as_ref()[1..].to_vec();
+                (KeyPair::Ecdsa(key_pair), public_key)
+            }
+
+            Algorithm::ED25519 => {
+                let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng)?;
+                let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref())?;
+                let public_key = key_pair.public_key().as_ref().to_vec();
+                (KeyPair::Ed25519(key_pair), public_key)
+            }
+
+            _ => {
+                return Err(Error::InvalidInput(format!(
+                    "{algorithm} is not supported by the in-process signer"
+                )))
+            }
+        };
+
+        Ok(Self {
+            dnskey: DNSKEY {
+                zone,
+                ttl: DEFAULT_TTL,
+                flags,
+                protocol: 3,
+                algorithm: algorithm.code(),
+                public_key: BASE64.encode(&public_key),
+            },
+            key_pair: Arc::new(key_pair),
+            is_combined: false,
+        })
+    }
+
+    pub fn dnskey(&self) -> &DNSKEY {
+        &self.dnskey
+    }
+
+    /// Whether the key signs the DNSKEY RRset
+    pub fn is_key_signing_key(&self) -> bool {
+        self.dnskey.is_key_signing_key()
+    }
+
+    /// Whether the key signs the RRsets other than the DNSKEY RRset
+    pub fn is_zone_signing_key(&self) -> bool {
+        self.is_combined || self.dnskey.is_zone_signing_key()
+    }
+
+    /// Sets the REVOKE bit (RFC5011) of the key's DNSKEY record
+    pub fn set_revoke_bit(&mut self) {
+        self.dnskey.set_revoke_bit();
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let signature = match &*self.key_pair {
+            KeyPair::Ecdsa(key_pair) => {
+                key_pair.sign(&SystemRandom::new(), data)?.as_ref().to_vec()
+            }
+            KeyPair::Ed25519(key_pair) => key_pair.sign(data).as_ref().to_vec(),
+        };
+
+        Ok(signature)
+    }
+}
+
+/// Signs zone files with one or more KSK / ZSK pairs
+pub struct Signer {
+    keys: Vec<SigningKey>,
+    published: Vec<DNSKEY>,
+    nsec: Nsec,
+    /// Start of the signature validity period, in seconds since the UNIX epoch
+    pub inception: u64,
+    /// End of the signature validity period, in seconds since the UNIX epoch
+    pub expiration: u64,
+}
+
+impl Signer {
+    /// Signatures are valid from now and for the next 30 days
+    pub fn new(ksk: SigningKey, zsk: SigningKey, nsec: Nsec) -> Result<Self> {
+        Self::with_keys(vec![ksk, zsk], nsec)
+    }
+
+    /// Signs with all the given `keys`, e.g. the old and the new keys during a rollover
+    ///
+    /// There must be at least one KSK and one ZSK, or a CSK
+    pub fn with_keys(keys: Vec<SigningKey>, nsec: Nsec) -> Result<Self> {
+        let has_ksk = keys.iter().any(SigningKey::is_key_signing_key);
+        let has_zsk = keys.iter().any(SigningKey::is_zone_signing_key);
+        if !has_ksk || !has_zsk {
+            return Err(Error::MissingKeys {
+                zone: keys.first().map(|key| key.dnskey().zone.clone()),
+            });
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        Ok(Self {
+            keys,
+            published: vec![],
+            nsec,
+            inception: now,
+            expiration: now + VALIDITY,
+        })
+    }
+
+    /// Adds `dnskey` to the DNSKEY RRset without signing anything with it, e.g. a pre-published
+    /// or a retired key
+    pub fn publish(&mut self, dnskey: DNSKEY) -> &mut Self {
+        self.published.push(dnskey);
+        self
+    }
+
+    /// The first KSK, or CSK, passed to the constructor
+    pub fn key_signing_key(&self) -> &SigningKey {
+        self.keys
+            .iter()
+            .find(|key| key.is_key_signing_key())
+            .expect("unreachable")
+    }
+
+    /// The first ZSK, or CSK, passed to the constructor
+    pub fn zone_signing_key(&self) -> &SigningKey {
+        self.zone_signing_keys().next().expect("unreachable")
+    }
+
+    /// The DS record that the parent zone needs to publish to delegate to the signed zone
+    pub fn ds(&self, digest_type: DigestType) -> Result<DS> {
+        self.key_signing_key().dnskey().ds(digest_type)
+    }
+
+    /// Produces an RRSIG record that covers `rrset` using `key`
+    ///
+    /// All the records in `rrset` must have the same owner name and type
+    pub fn sign_rrset(&self, key: &SigningKey, rrset: &[Record]) -> Result<RRSIG> {
+        let first = rrset
+            .first()
+            .ok_or_else(|| Error::InvalidInput("cannot sign an empty RRset".to_string()))?;
+        let fqdn = first.fqdn();
+        let type_covered = first.record_type();
+        let original_ttl = first.ttl();
+
+        if rrset
+            .iter()
+            .any(|record| record.fqdn() != fqdn || record.record_type() != type_covered)
+        {
+            return Err(Error::InvalidInput(
+                "records in an RRset must have the same owner name and type".to_string(),
+            ));
+        }
+
+        let dnskey = key.dnskey();
+        let mut rrsig = RRSIG {
+            fqdn: fqdn.clone(),
+            ttl: original_ttl,
+            type_covered,
+            algorithm: dnskey.algorithm,
+            labels: labels(fqdn),
+            original_ttl,
+            signature_expiration: wire::text_timestamp(self.expiration),
+            signature_inception: wire::text_timestamp(self.inception),
+            key_tag: dnskey.key_tag()?,
+            signer_name: dnskey.zone.clone(),
+            signature: String::new(),
+        };
+
+        // section 3.1.8.1 of RFC4034
+        let mut records = vec![];
+        for record in rrset {
+            let mut buf = vec![];
+            wire::record(record, record.fqdn(), original_ttl, &mut buf)?;
+            records.push(buf);
+        }
+        records.sort();
+        records.dedup();
+
+        let mut data = vec![];
+        wire::rrsig_rdata_without_signature(&rrsig, &mut data)?;
+        for record in records {
+            data.extend(record);
+        }
+
+        rrsig.signature = BASE64.encode(&key.sign(&data)?);
+
+        Ok(rrsig)
+    }
+
+    /// Adds DNSKEY, RRSIG and NSEC / NSEC3 records to the zone
+    ///
+    /// The DNSKEY RRset is signed with every KSK; all other RRsets are signed with every ZSK. A
+    /// CSK signs all RRsets
+    pub fn sign_zone(&self, zone_file: &ZoneFile) -> Result<ZoneFile> {
+        let origin = zone_file.origin().clone();
+        let soa = &zone_file.soa;
+
+        let mut zone = Zone::default();
+        zone.insert(&origin, soa.clone().into())?;
+        for record in &zone_file.records {
+            if matches!(
+                record,
+                Record::RRSIG(..) | Record::NSEC(..) | Record::NSEC3(..) | Record::NSEC3PARAM(..)
+            ) {
+                return Err(Error::InvalidInput(format!(
+                    "zone {origin} has already been signed"
+                )));
+            }
+
+            zone.insert(&origin, record.clone())?;
+        }
+
+        let dnskeys = self.keys.iter().map(SigningKey::dnskey);
+        for dnskey in dnskeys.chain(&self.published) {
+            // inherit SOA's TTL value
+            let mut dnskey = dnskey.clone();
+            dnskey.ttl = soa.ttl;
+            zone.insert(&origin, dnskey.into())?;
+        }
+
+        // section 3 of RFC4034 and RFC9077
+        let denial_ttl = soa.ttl.min(soa.settings.minimum);
+
+        let mut nsec3_records = vec![];
+        match &self.nsec {
+            Nsec::_1 => zone.add_nsec_chain(denial_ttl),
+
+            Nsec::_3 {
+                iterations,
+                opt_out,
+                salt,
+            } => {
+                // an empty salt is written as `-` in presentation format
+                let salt = match salt.as_deref() {
+                    None | Some("" | "-") => "-".to_string(),
+                    Some(salt) => {
+                        record::check_salt(salt)?;
+                        salt.to_uppercase()
+                    }
+                };
+                zone.insert(
+                    &origin,
+                    NSEC3PARAM {
+                        zone: origin.clone(),
+                        ttl: 0,
+                        hash_alg: NSEC3_SHA1,
+                        flags: 0,
+                        iterations: *iterations,
+                        salt: salt.clone(),
+                    }
+                    .into(),
+                )?;
+
+                nsec3_records =
+                    zone.nsec3_chain(&origin, denial_ttl, *iterations, *opt_out, &salt)?;
+            }
+        }
+
+        let mut records = vec![];
+        for (key, node) in &zone.nodes {
+            let is_glue = zone.is_glue(key);
+            let is_delegation = zone.delegations.contains(key);
+
+            for rrset in node.values() {
+                let record_type = rrset[0].record_type();
+                if !rrset[0].is_soa() {
+                    records.extend(rrset.iter().cloned());
+                }
+
+                let is_signed = if is_glue {
+                    false
+                } else if is_delegation {
+                    matches!(record_type, RecordType::DS | RecordType::NSEC)
+                } else {
+                    true
+                };
+
+                if is_signed {
+                    for key in &self.keys {
+                        let signs = if record_type == RecordType::DNSKEY {
+                            key.is_key_signing_key()
+                        } else {
+                            key.is_zone_signing_key()
+                        };
+
+                        if signs {
+                            records.push(self.sign_rrset(key, rrset)?.into());
+                        }
+                    }
+                }
+            }
+        }
+
+        for nsec3 in nsec3_records {
+            let mut rrsigs = vec![];
+            for key in self.zone_signing_keys() {
+                rrsigs.push(self.sign_rrset(key, slice::from_ref(&nsec3))?.into());
+            }
+            records.push(nsec3);
+            records.extend(rrsigs);
+        }
+
+        let mut signed = ZoneFile::new(soa.clone());
+        signed.records = records;
+
+        Ok(signed)
+    }
+
+    fn zone_signing_keys(&self) -> impl Iterator<Item = &SigningKey> {
+        self.keys.iter().filter(|key| key.is_zone_signing_key())
+    }
+}
+
+#[derive(Default)]
+struct Zone {
+    // RRsets grouped by owner name and then by record type
+    nodes: BTreeMap<CanonicalName, BTreeMap<u16, Vec<Record>>>,
+    fqdns: BTreeMap<CanonicalName, FQDN>,
+    // names, other than the apex, that own NS records
+    delegations: BTreeSet<CanonicalName>,
+}
+
+impl Zone {
+    fn insert(&mut self, origin: &FQDN, record: Record) -> Result<()> {
+        let fqdn = record.fqdn();
+        let key = wire::canonical_name(fqdn);
+
+        if !key.starts_with(&wire::canonical_name(origin)) {
+            return Err(Error::InvalidInput(format!(
+                "record {fqdn} is outside of zone {origin}"
+            )));
+        }
+
+        if record.record_type() == RecordType::NS && fqdn != origin {
+            self.delegations.insert(key.clone());
+        }
+
+        self.fqdns
+            .entry(key.clone())
+            .or_insert_with(|| fqdn.clone());
+        self.nodes
+            .entry(key)
+            .or_default()
+            .entry(record.record_type().code())
+            .or_default()
+            .push(record);
+
+        Ok(())
+    }
+
+    // names below a zone cut are not authoritative data
+    fn is_glue(&self, key: &CanonicalName) -> bool {
+        self.delegations
+            .iter()
+            .any(|delegation| key.len() > delegation.len() && key.starts_with(delegation))
+    }
+
+    fn record_types(&self, key: &CanonicalName) -> Vec<RecordType> {
+        self.nodes[key]
+            .values()
+            .map(|rrset| rrset[0].record_type())
+            .collect()
+    }
+
+    // section 2.3 of RFC4035
+    fn add_nsec_chain(&mut self, ttl: u32) {
+        let keys = self
+            .nodes
+            .keys()
+            .filter(|key| !self.is_glue(key))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for (index, key) in keys.iter().enumerate() {
+            let next = &keys[(index + 1) % keys.len()];
+
+            let mut record_types = self.record_types(key);
+            record_types.extend([RecordType::RRSIG, RecordType::NSEC]);
+
+            let fqdn = self.fqdns[key].clone();
+            let nsec = NSEC {
+                fqdn,
+                ttl,
+                next_domain: self.fqdns[next].clone(),
+                record_types,
+            };
+
+            self.nodes
+                .get_mut(key)
+                .unwrap()
+                .insert(RecordType::NSEC.code(), vec![nsec.into()]);
+        }
+    }
+
+    // section 7.1 of RFC5155
+    fn nsec3_chain(
+        &self,
+        origin: &FQDN,
+        ttl: u32,
+        iterations: u16,
+        opt_out: bool,
+        salt: &str,
+    ) -> Result<Vec<Record>> {
+        let apex = wire::canonical_name(origin);
+
+        let mut names = BTreeMap::new();
+        for key in self.nodes.keys() {
+            if self.is_glue(key) {
+                continue;
+            }
+
+            let record_types = self.record_types(key);
+            let is_delegation = self.delegations.contains(key);
+            let is_secure_delegation = record_types.contains(&RecordType::DS);
+
+            if is_delegation && !is_secure_delegation && opt_out {
+                continue;
+            }
+
+            let mut record_types = record_types;
+            if !is_delegation || is_secure_delegation {
+                record_types.push(RecordType::RRSIG);
+            }
+            names.insert(key.clone(), record_types);
+
+            // empty non-terminals
+            for len in apex.len() + 1..key.len() {
+                names.entry(key[..len].to_vec()).or_default();
+            }
+        }
+
+        let salt_bytes = if salt == "-" {
+            vec![]
+        } else {
+            HEXUPPER_PERMISSIVE.decode(salt.as_bytes())?
+        };
+
+        let mut hashed = names
+            .into_iter()
+            .map(|(key, record_types)| {
+                let fqdn = wire::fqdn_from_canonical(&key)?;
+                let hash = nsec3_hash(&fqdn, &salt_bytes, iterations);
+                Ok((hash, record_types))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        hashed.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let flags = if opt_out { NSEC3_OPT_OUT_FLAG } else { 0 };
+        let mut records = vec![];
+        for (index, (hash, record_types)) in hashed.iter().enumerate() {
+            let (next_hash, _) = &hashed[(index + 1) % hashed.len()];
+
+            let label = BASE32HEX_NOPAD.encode(hash).to_ascii_lowercase();
+            let fqdn = if origin.is_root() {
+                FQDN(format!("{label}."))?
+            } else {
+                FQDN(format!("{label}.{origin}"))?
+            };
+
+            records.push(
+                NSEC3 {
+                    fqdn,
+                    ttl,
+                    hash_alg: NSEC3_SHA1,
+                    flags,
+                    iterations,
+                    salt: salt.to_string(),
+                    next_hashed_owner_name: BASE32HEX_NOPAD.encode(next_hash),
+                    record_types: record_types.clone(),
+                }
+                .into(),
+            );
+        }
+
+        Ok(records)
+    }
+}
+
+// section 3.1.3 of RFC4034: the wildcard label is not counted
+pub(crate) fn labels(fqdn: &FQDN) -> u8 {
+    let labels = fqdn.num_labels();
+    let labels = if fqdn.as_str().starts_with("*.") {
+        labels - 1
+    } else {
+        labels
+    };
+
+    labels as u8
+}
+
+// section 5 of RFC5155
+pub(crate) fn nsec3_hash(fqdn: &FQDN, salt: &[u8], iterations: u16) -> Vec<u8> {
+    let mut input = vec![];
+    wire::name(fqdn, &mut input);
+
+    let mut hash = input;
+    for _ in 0..=iterations {
+        let mut ctx = digest::Context::new(&digest::SHA1_FOR_LEGACY_USE_ONLY);
+        ctx.update(&hash);
+        ctx.update(salt);
+        hash = ctx.finish().as_ref().to_vec();
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_FIXED};
+
+    use super::*;
+    use crate::record::SOA;
+
+    use pretty_assertions::assert_eq;
+
+    // example from appendix A of RFC5155
+    #[test]
+    fn rfc5155_nsec3_hash() -> Result<()> {
+        let salt = HEXUPPER_PERMISSIVE.decode(b"AABBCCDD")?;
+        let hash = nsec3_hash(&FQDN("example.")?, &salt, 12);
+
+        assert_eq!(
+            "0p9mhaveqvm6t7vbl5lop2u3t2rp3tom",
+            BASE32HEX_NOPAD.encode(&hash).to_ascii_lowercase()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn signs_with_nsec() -> Result<()> {
+        let signed = signer(Nsec::_1)?.sign_zone(&zone_file()?)?;
+
+        let nsecs = signed
+            .records
+            .iter()
+            .filter_map(|record| match record {
+                Record::NSEC(nsec) => Some((nsec.fqdn.as_str(), nsec.next_domain.as_str())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // glue (`ns.child.example.`) is not part of the chain
+        assert_eq!(
+            [
+                ("example.", "a.example."),
+                ("a.example.", "child.example."),
+                ("child.example.", "example."),
+            ],
+            nsecs.as_slice()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn signs_with_nsec3() -> Result<()> {
+        let nsec = Nsec::_3 {
+            iterations: 0,
+            opt_out: true,
+            salt: None,
+        };
+        let signed = signer(nsec)?.sign_zone(&zone_file()?)?;
+
+        let nsec3s = signed
+            .records
+            .iter()
+            .filter_map(|record| match record {
+                Record::NSEC3(nsec3) => Some(nsec3),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // the insecure delegation `child.example.` is opted out
+        assert_eq!(2, nsec3s.len());
+        assert!(nsec3s.iter().all(|nsec3| nsec3.flags == NSEC3_OPT_OUT_FLAG));
+
+        let apex_hash = BASE32HEX_NOPAD.encode(&nsec3_hash(&FQDN("example.")?, &[], 0));
+        let apex = nsec3s
+            .iter()
+            .find(|nsec3| {
+                nsec3.fqdn.as_str() == format!("{}.example.", apex_hash.to_ascii_lowercase())
+            })
+            .expect("apex NSEC3 record not found");
+        assert!(apex.record_types.contains(&RecordType::NSEC3PARAM));
+
+        Ok(())
+    }
+
+    #[test]
+    fn signatures_verify() -> Result<()> {
+        let signer = signer(Nsec::_1)?;
+        let signed = signer.sign_zone(&zone_file()?)?;
+
+        let mut rrsets: BTreeMap<(String, u16), Vec<Record>> = BTreeMap::new();
+        let mut rrsigs = vec![];
+        for record in signed
+            .records
+            .iter()
+            .cloned()
+            .chain([signed.soa.clone().into()])
+        {
+            if let Record::RRSIG(rrsig) = record {
+                rrsigs.push(rrsig);
+            } else {
+                rrsets
+                    .entry((
+                        record.fqdn().as_str().to_string(),
+                        record.record_type().code(),
+                    ))
+                    .or_default()
+                    .push(record);
+            }
+        }
+
+        // every authoritative RRset is covered; glue and the delegation's NS RRset are not
+        assert!(!rrsigs
+            .iter()
+            .any(|rrsig| rrsig.fqdn.as_str() == "ns.child.example."));
+        assert!(!rrsigs
+            .iter()
+            .any(|rrsig| rrsig.fqdn.as_str() == "child.example."
+                && rrsig.type_covered == RecordType::NS));
+        assert_eq!(rrsets.len() - 2, rrsigs.len());
+
+        for rrsig in rrsigs {
+            let key = if rrsig.type_covered == RecordType::DNSKEY {
+                signer.key_signing_key()
+            } else {
+                signer.zone_signing_key()
+            };
+            assert_eq!(key.dnskey().key_tag()?, rrsig.key_tag);
+
+            let rrset = &rrsets[&(rrsig.fqdn.as_str().to_string(), rrsig.type_covered.code())];
+
+            let mut records = rrset
+                .iter()
+                .map(|record| {
+                    let mut buf = vec![];
+                    wire::record(record, record.fqdn(), rrsig.original_ttl, &mut buf)?;
+                    Ok(buf)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            records.sort();
+
+            let mut data = vec![];
+            wire::rrsig_rdata_without_signature(&rrsig, &mut data)?;
+            data.extend(records.concat());
+
+            let mut public_key = vec![0x04];
+            public_key.extend(BASE64.decode(key.dnskey().public_key.as_bytes())?);
+            let signature = BASE64.decode(rrsig.signature.as_bytes())?;
+            let verified = UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, public_key)
+                .verify(&data, &signature);
+            assert!(
+                verified.is_ok(),
+                "bad signature over {} {}",
+                rrsig.fqdn,
+                rrsig.type_covered
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn signs_with_every_active_key() -> Result<()> {
+        let zone = FQDN("example.")?;
+        let ksk = SigningKey::key_signing_key(zone.clone(), Algorithm::ECDSAP256SHA256)?;
+        let old_zsk = SigningKey::zone_signing_key(zone.clone(), Algorithm::ECDSAP256SHA256)?;
+        let new_zsk = SigningKey::zone_signing_key(zone.clone(), Algorithm::ED25519)?;
+        let retired_zsk = SigningKey::zone_signing_key(zone, Algorithm::ECDSAP256SHA256)?;
+
+        let mut signer = Signer::with_keys(vec![ksk, old_zsk, new_zsk], Nsec::_1)?;
+        signer.publish(retired_zsk.dnskey().clone());
+        let signed = signer.sign_zone(&zone_file()?)?;
+
+        let dnskeys = signed
+            .records
+            .iter()
+            .filter(|record| matches!(record, Record::DNSKEY(..)))
+            .count();
+        assert_eq!(4, dnskeys);
+
+        let covering = |record_type| {
+            signed
+                .records
+                .iter()
+                .filter(|record| match record {
+                    Record::RRSIG(rrsig) => {
+                        rrsig.fqdn.as_str() == "a.example." && rrsig.type_covered == record_type
+                    }
+                    _ => false,
+                })
+                .count()
+        };
+        assert_eq!(2, covering(RecordType::A));
+
+        let retired_key_tag = retired_zsk.dnskey().key_tag()?;
+        assert!(!signed.records.iter().any(|record| match record {
+            Record::RRSIG(rrsig) => rrsig.key_tag == retired_key_tag,
+            _ => false,
+        }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn signs_with_combined_key() -> Result<()> {
+        let csk = SigningKey::combined_signing_key(FQDN("example.")?, Algorithm::ED25519)?;
+        let key_tag = csk.dnskey().key_tag()?;
+
+        let signer = Signer::with_keys(vec![csk], Nsec::_1)?;
+        assert_eq!(key_tag, signer.key_signing_key().dnskey().key_tag()?);
+        assert_eq!(key_tag, signer.zone_signing_key().dnskey().key_tag()?);
+
+        let signed = signer.sign_zone(&zone_file()?)?;
+        let rrsigs = signed
+            .records
+            .iter()
+            .filter_map(|record| match record {
+                Record::RRSIG(rrsig) => Some(rrsig),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert!(rrsigs.iter().all(|rrsig| rrsig.key_tag == key_tag));
+        for record_type in [RecordType::DNSKEY, RecordType::SOA, RecordType::A] {
+            assert!(rrsigs.iter().any(|rrsig| rrsig.type_covered == record_type));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn requires_ksk_and_zsk() -> Result<()> {
+        let zsk = SigningKey::zone_signing_key(FQDN::ROOT, Algorithm::ECDSAP256SHA256)?;
+        assert!(matches!(
+            Signer::with_keys(vec![zsk], Nsec::_1),
+            Err(Error::MissingKeys { zone: Some(zone) }) if zone.is_root()
+        ));
+        assert!(matches!(
+            Signer::with_keys(vec![], Nsec::_1),
+            Err(Error::MissingKeys { zone: None })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unsupported_algorithm() {
+        assert!(matches!(
+            SigningKey::zone_signing_key(FQDN::ROOT, Algorithm::RSASHA256),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn nsec3_salt() -> Result<()> {
+        let nsec3param_salt = |salt: Option<&str>| -> Result<String> {
+            let nsec = Nsec::_3 {
+                iterations: 0,
+                opt_out: false,
+                salt: salt.map(str::to_string),
+            };
+            let signed = signer(nsec)?.sign_zone(&zone_file()?)?;
+
+            Ok(signed
+                .records
+                .iter()
+                .find_map(|record| match record {
+                    Record::NSEC3PARAM(nsec3param) => Some(nsec3param.salt.clone()),
+                    _ => None,
+                })
+                .expect("NSEC3PARAM record not found"))
+        };
+
+        assert_eq!("-", nsec3param_salt(None)?);
+        assert_eq!("-", nsec3param_salt(Some(""))?);
+        assert_eq!("-", nsec3param_salt(Some("-"))?);
+        assert_eq!("AABBCCDD", nsec3param_salt(Some("aabbccdd"))?);
+
+        for salt in ["AABBCCD", "AA BB", "XYZW"] {
+            assert!(
+                matches!(nsec3param_salt(Some(salt)), Err(Error::Parse { .. })),
+                "{salt}"
+            );
+        }
+
+        Ok(())
+    }
+
+    fn signer(nsec: Nsec) -> Result<Signer> {
+        let zone = FQDN("example.")?;
+        let ksk = SigningKey::key_signing_key(zone.clone(), Algorithm::ECDSAP256SHA256)?;
+        let zsk = SigningKey::zone_signing_key(zone, Algorithm::ECDSAP256SHA256)?;
+        Signer::new(ksk, zsk, nsec)
+    }
+
+    fn zone_file() -> Result<ZoneFile> {
+        let zone = FQDN("example.")?;
+        let mut zone_file = ZoneFile::new(SOA {
+            zone: zone.clone(),
+            ttl: DEFAULT_TTL,
+            nameserver: FQDN("ns.example.")?,
+            admin: FQDN("admin.example.")?,
+            settings: Default::default(),
+        });
+        zone_file.add(Record::ns(zone, FQDN("a.example.")?));
+        zone_file.add(Record::a(FQDN("a.example.")?, [1, 2, 3, 4].into()));
+        zone_file.referral(
+            FQDN("child.example.")?,
+            FQDN("ns.child.example.")?,
+            [5, 6, 7, 8].into(),
+        );
+
+        Ok(zone_file)
+    }
+}
@@ -35,6 +35,18 @@ impl TrustAnchor {
         buf.push_str("};");
         buf
     }
+
+    /// formats the `TrustAnchor` as BIND trust anchors that are managed as per RFC5011
+    pub(super) fn initial_keys(&self) -> String {
+        let mut buf = "trust-anchors {".to_string();
+
+        for key in &self.keys {
+            buf.push_str(&key.initial_key());
+        }
+
+        buf.push_str("};");
+        buf
+    }
 }
 
 impl fmt::Display for TrustAnchor {
@@ -10,7 +10,7 @@ use serde::Deserialize;
 use serde_with::{serde_as, DisplayFromStr};
 
 use crate::container::{Child, Container};
-use crate::Result;
+use crate::{Error, Result};
 
 static ID: AtomicUsize = AtomicUsize::new(0);
 
@@ -84,7 +84,7 @@ impl Tshark {
             }
         }
 
-        Err("unexpected EOF".into())
+        Err(Error::parse("unexpected EOF"))
     }
 
     pub fn terminate(self) -> Result<Vec<Capture>> {
@@ -95,7 +95,9 @@ impl Tshark {
         let output = self.child.wait()?;
 
         if !output.status.success() {
-            return Err("could not terminate the `tshark` process".into());
+            return Err(Error::Other(
+                "could not terminate the `tshark` process".to_string(),
+            ));
         }
 
         // wait until the message "NN packets captured" appears
@@ -122,9 +124,9 @@ impl Tshark {
                     destination: ip.dst,
                 }
             } else {
-                return Err(
-                    format!("unexpected IP packet found in wireshark trace: {ip:?}").into(),
-                );
+                return Err(Error::parse(format!(
+                    "unexpected IP packet found in wireshark trace: {ip:?}"
+                )));
             };
 
             messages.push(Capture {
@@ -0,0 +1,113 @@
+//! Transaction signatures (RFC8945)
+
+use core::fmt;
+
+use data_encoding::BASE64;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::{Error, Result, FQDN};
+
+/// A shared secret used to authenticate DNS messages
+#[derive(Clone, Debug)]
+pub struct TsigKey {
+    pub name: FQDN,
+    pub algorithm: TsigAlgorithm,
+    secret: Vec<u8>,
+}
+
+impl TsigKey {
+    /// Creates a key with a random secret as long as the output of the `algorithm`'s hash
+    pub fn generate(name: FQDN, algorithm: TsigAlgorithm) -> Result<Self> {
+        let mut secret = vec![0; algorithm.output_len()];
+        SystemRandom::new()
+            .fill(&mut secret)
+            .map_err(|_| Error::Other("failed to generate a TSIG secret".to_string()))?;
+
+        Ok(Self {
+            name,
+            algorithm,
+            secret,
+        })
+    }
+
+    /// Creates a key from a base64 encoded `secret`
+    pub fn new(name: FQDN, algorithm: TsigAlgorithm, secret: &str) -> Result<Self> {
+        Ok(Self {
+            name,
+            algorithm,
+            secret: BASE64.decode(secret.as_bytes())?,
+        })
+    }
+
+    /// The base64 encoded secret
+    pub fn secret(&self) -> String {
+        BASE64.encode(&self.secret)
+    }
+
+    /// Key statement in the format of `named.conf` and `nsupdate -k` key files
+    pub(crate) fn bind_key_statement(&self) -> String {
+        format!(
+            "key \"{}\" {{\n    algorithm {};\n    secret \"{}\";\n}};\n",
+            self.name,
+            self.algorithm,
+            self.secret()
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TsigAlgorithm {
+    HmacSHA1,
+    #[default]
+    HmacSHA256,
+    HmacSHA384,
+    HmacSHA512,
+}
+
+impl TsigAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::HmacSHA1 => "hmac-sha1",
+            Self::HmacSHA256 => "hmac-sha256",
+            Self::HmacSHA384 => "hmac-sha384",
+            Self::HmacSHA512 => "hmac-sha512",
+        }
+    }
+
+    fn output_len(&self) -> usize {
+        match self {
+            Self::HmacSHA1 => 20,
+            Self::HmacSHA256 => 32,
+            Self::HmacSHA384 => 48,
+            Self::HmacSHA512 => 64,
+        }
+    }
+}
+
+impl fmt::Display for TsigAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate() -> Result<()> {
+        let key = TsigKey::generate(FQDN("update.")?, TsigAlgorithm::HmacSHA512)?;
+        assert_eq!(64, BASE64.decode(key.secret().as_bytes())?.len());
+
+        let statement = key.bind_key_statement();
+        assert!(statement.starts_with("key \"update.\" {"));
+        assert!(statement.contains("algorithm hmac-sha512;"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn new_rejects_invalid_secret() {
+        assert!(TsigKey::new(FQDN::ROOT, TsigAlgorithm::HmacSHA256, "not base64!").is_err());
+    }
+}
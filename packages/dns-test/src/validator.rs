@@ -0,0 +1,1443 @@
+//! Offline DNSSEC validation
+//!
+//! The `Validator` checks RRSIG signatures, the DS -> DNSKEY chain of trust and NSEC / NSEC3
+//! denial of existence proofs over `record::Record` values. It does not talk to any server: all
+//! the records it needs, e.g. the DNSKEY and DS RRsets of every zone in the chain, must be added
+//! to it beforehand
+
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use data_encoding::{BASE32HEX_NOPAD, HEXUPPER_PERMISSIVE};
+use ring::signature::{self, RsaPublicKeyComponents, UnparsedPublicKey};
+
+use crate::client::{DigOutput, DigStatus};
+use crate::record::{DigestType, Record, RecordType, DNSKEY, DS, NSEC, NSEC3, RRSIG};
+use crate::wire::{self, CanonicalName};
+use crate::zone_file::ZoneFile;
+use crate::{signer, Error, Result, TrustAnchor, FQDN};
+
+const NSEC3_OPT_OUT_FLAG: u8 = 1;
+
+/// Outcome of validating an RRset or a response
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    /// There's a chain of trust from the trust anchor to the data
+    Secure,
+    /// It has been proven that there's no chain of trust to the data
+    Insecure(Reason),
+    /// There should be a chain of trust to the data but it is broken
+    Bogus(Reason),
+}
+
+impl Verdict {
+    pub fn is_secure(&self) -> bool {
+        matches!(self, Self::Secure)
+    }
+
+    pub fn is_insecure(&self) -> bool {
+        matches!(self, Self::Insecure(..))
+    }
+
+    pub fn is_bogus(&self) -> bool {
+        matches!(self, Self::Bogus(..))
+    }
+}
+
+/// Why the data is not `Secure`
+#[derive(Debug, PartialEq)]
+pub enum Reason {
+    /// Neither the zone nor any of its ancestors has a trust anchor
+    NoTrustAnchor {
+        zone: FQDN,
+    },
+    /// The parent zone proves that there's no DS RRset for the zone
+    NoDs {
+        zone: FQDN,
+    },
+    /// None of the algorithms or digest types used by the zone is supported
+    UnsupportedAlgorithm {
+        zone: FQDN,
+    },
+    /// The NSEC3 record that covers the name has the opt-out flag set
+    OptOut {
+        fqdn: FQDN,
+    },
+    /// There's neither a DS RRset nor a proof of its non-existence for the zone
+    MissingDs {
+        zone: FQDN,
+    },
+    MissingDnskey {
+        zone: FQDN,
+    },
+    /// No DNSKEY in the zone matches its DS RRset
+    DsMismatch {
+        zone: FQDN,
+    },
+    /// The RRset to validate has not been added to the validator
+    MissingRrset {
+        fqdn: FQDN,
+        record_type: RecordType,
+    },
+    MissingRrsig {
+        fqdn: FQDN,
+        record_type: RecordType,
+    },
+    /// No trusted DNSKEY matches the key tag and algorithm of the RRSIGs
+    NoMatchingKey {
+        fqdn: FQDN,
+        record_type: RecordType,
+    },
+    SignatureExpired {
+        fqdn: FQDN,
+        record_type: RecordType,
+    },
+    SignatureNotYetValid {
+        fqdn: FQDN,
+        record_type: RecordType,
+    },
+    BadSignature {
+        fqdn: FQDN,
+        record_type: RecordType,
+    },
+    /// The labels field of the RRSIG is larger than the number of labels of its owner name
+    InvalidLabels {
+        fqdn: FQDN,
+        record_type: RecordType,
+    },
+    /// A negative response contains no NSEC or NSEC3 records that prove the non-existence
+    MissingDenialProof {
+        fqdn: FQDN,
+        record_type: RecordType,
+    },
+    /// The NSEC or NSEC3 records contradict the negative response
+    InvalidDenialProof {
+        fqdn: FQDN,
+        record_type: RecordType,
+    },
+    /// The record could not be converted into its wire format
+    MalformedRecord {
+        fqdn: FQDN,
+        record_type: RecordType,
+    },
+}
+
+/// Validates records against a trust anchor
+#[derive(Clone)]
+pub struct Validator {
+    trust_anchor: Vec<DNSKEY>,
+    rrsets: BTreeMap<(CanonicalName, u16), Vec<Record>>,
+    rrsigs: BTreeMap<(CanonicalName, u16), Vec<RRSIG>>,
+    now: u64,
+}
+
+impl Validator {
+    pub fn new(trust_anchor: &TrustAnchor) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            trust_anchor: trust_anchor.keys().to_vec(),
+            rrsets: BTreeMap::new(),
+            rrsigs: BTreeMap::new(),
+            now,
+        }
+    }
+
+    /// Validates signatures as if the current time was `unix_timestamp`
+    pub fn at_time(&mut self, unix_timestamp: u64) -> &mut Self {
+        self.now = unix_timestamp;
+        self
+    }
+
+    /// Makes `records` available to the validation process
+    pub fn add_records(&mut self, records: impl IntoIterator<Item = Record>) -> &mut Self {
+        for record in records {
+            let key = wire::canonical_name(record.fqdn());
+            if let Record::RRSIG(rrsig) = record {
+                self.rrsigs
+                    .entry((key, rrsig.type_covered.code()))
+                    .or_default()
+                    .push(rrsig);
+            } else {
+                self.rrsets
+                    .entry((key, record.record_type().code()))
+                    .or_default()
+                    .push(record);
+            }
+        }
+        self
+    }
+
+    /// Makes all the records in `zone_file`, including its SOA record, available to the
+    /// validation process
+    pub fn add_zone_file(&mut self, zone_file: &ZoneFile) -> &mut Self {
+        self.add_records([zone_file.soa.clone().into()]);
+        self.add_records(zone_file.records.iter().cloned())
+    }
+
+    /// Validates a previously added RRset
+    pub fn validate_rrset(&self, fqdn: &FQDN, record_type: RecordType) -> Verdict {
+        let key = (wire::canonical_name(fqdn), record_type.code());
+        let Some(rrset) = self.rrsets.get(&key) else {
+            return Verdict::Bogus(Reason::MissingRrset {
+                fqdn: fqdn.clone(),
+                record_type,
+            });
+        };
+
+        self.verify_rrset(rrset)
+    }
+
+    /// Validates every authoritative RRset in `zone_file`
+    ///
+    /// `zone_file` does not need to be added to the validator beforehand. Returns the first
+    /// verdict that is not `Secure`
+    pub fn validate_zone_file(&self, zone_file: &ZoneFile) -> Verdict {
+        let mut validator = self.clone();
+        validator.add_zone_file(zone_file);
+
+        let apex = wire::canonical_name(zone_file.origin());
+        let delegations = validator
+            .rrsets
+            .keys()
+            .filter(|(key, code)| *code == RecordType::NS.code() && *key != apex)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        for ((key, code), rrset) in &validator.rrsets {
+            if !key.starts_with(&apex) {
+                continue;
+            }
+
+            let is_glue = delegations
+                .iter()
+                .any(|delegation| key.len() > delegation.len() && key.starts_with(delegation));
+            let is_delegation = delegations.contains(key);
+            // only the DS and NSEC RRsets are authoritative at a delegation point
+            let is_authoritative = !is_glue
+                && (!is_delegation
+                    || *code == RecordType::DS.code()
+                    || *code == RecordType::NSEC.code());
+
+            if is_authoritative {
+                let verdict = validator.verify_rrset(rrset);
+                if !verdict.is_secure() {
+                    return verdict;
+                }
+            }
+        }
+
+        Verdict::Secure
+    }
+
+    /// Validates the response to a `qtype` query for `qname`
+    ///
+    /// The query must have been sent with the DO bit set so that the response includes RRSIG,
+    /// NSEC and NSEC3 records. Only NOERROR and NXDOMAIN responses can be validated
+    pub fn validate_response(
+        &self,
+        output: &DigOutput,
+        qname: &FQDN,
+        qtype: RecordType,
+    ) -> Result<Verdict> {
+        let mut validator = self.clone();
+        validator.add_records(output.answer.iter().cloned());
+        validator.add_records(output.authority.iter().cloned());
+
+        let authority = &output.authority;
+        let verdict = match output.status {
+            DigStatus::NOERROR if !output.answer.is_empty() => {
+                let key = (wire::canonical_name(qname), qtype.code());
+                let rrset = validator.rrsets.get(&key).ok_or_else(|| {
+                    Error::InvalidInput(format!(
+                        "answer section contains no {qtype} RRset for {qname}"
+                    ))
+                })?;
+                validator.verify_rrset(rrset)
+            }
+
+            DigStatus::NOERROR => validator.verify_nodata(authority, qname, qtype),
+
+            DigStatus::NXDOMAIN => validator.verify_nxdomain(authority, qname, qtype),
+
+            status => {
+                return Err(Error::InvalidInput(format!(
+                    "cannot validate a {status:?} response"
+                )))
+            }
+        };
+
+        Ok(verdict)
+    }
+
+    fn verify_rrset(&self, rrset: &[Record]) -> Verdict {
+        let is_ds = rrset[0].record_type() == RecordType::DS;
+        self.verify_rrset_signed_by(rrset, is_ds)
+    }
+
+    // when `by_parent` is set the RRset must have been signed by an ancestor zone of its owner;
+    // this is the case for DS RRsets and the NSEC RRsets at delegation points
+    fn verify_rrset_signed_by(&self, rrset: &[Record], by_parent: bool) -> Verdict {
+        let first = &rrset[0];
+        let fqdn = first.fqdn();
+        let record_type = first.record_type();
+        let key = (wire::canonical_name(fqdn), record_type.code());
+
+        let rrsigs = self.rrsigs.get(&key).map(Vec::as_slice).unwrap_or_default();
+        if rrsigs.is_empty() {
+            let start = if by_parent {
+                fqdn.parent()
+            } else {
+                Some(fqdn.clone())
+            };
+            // unsigned data is fine if it lives in an insecure zone
+            return match start
+                .and_then(|start| self.enclosing_zone(&start))
+                .map(|zone| self.zone_keys(&zone))
+            {
+                Some(Err(verdict)) if verdict.is_insecure() => verdict,
+                None => Verdict::Insecure(Reason::NoTrustAnchor { zone: fqdn.clone() }),
+                _ => Verdict::Bogus(Reason::MissingRrsig {
+                    fqdn: fqdn.clone(),
+                    record_type,
+                }),
+            };
+        }
+
+        let owner = wire::canonical_name(fqdn);
+        let mut last_failure = Reason::NoMatchingKey {
+            fqdn: fqdn.clone(),
+            record_type,
+        };
+        for rrsig in rrsigs {
+            let signer = wire::canonical_name(&rrsig.signer_name);
+            let is_ancestor =
+                owner.starts_with(&signer) && (!by_parent || owner.len() > signer.len());
+            if !is_ancestor {
+                continue;
+            }
+
+            let keys = match self.zone_keys(&rrsig.signer_name) {
+                Ok(keys) => keys,
+                Err(verdict @ Verdict::Insecure(_)) => return verdict,
+                Err(Verdict::Bogus(reason)) => {
+                    last_failure = reason;
+                    continue;
+                }
+                Err(Verdict::Secure) => unreachable!(),
+            };
+
+            match self.verify_rrsig(rrsig, rrset, &keys) {
+                Ok(()) if rrsig.labels < signer::labels(&rrsig.fqdn) => {
+                    return self.verify_wildcard_expansion(rrsig)
+                }
+                Ok(()) => return Verdict::Secure,
+                Err(reason) => last_failure = reason,
+            }
+        }
+
+        Verdict::Bogus(last_failure)
+    }
+
+    // section 5.3.4 of RFC4035 and section 8.8 of RFC5155: the RRset was synthesized from a
+    // wildcard so there must be a proof that no closer match exists
+    fn verify_wildcard_expansion(&self, rrsig: &RRSIG) -> Verdict {
+        let qname = &rrsig.fqdn;
+        let name = wire::canonical_name(qname);
+        let zone = &rrsig.signer_name;
+
+        let mut next_closer = qname.clone();
+        while next_closer.num_labels() > usize::from(rrsig.labels) + 1 {
+            let Some(parent) = next_closer.parent() else {
+                break;
+            };
+            next_closer = parent;
+        }
+
+        let proof = self
+            .rrsets
+            .iter()
+            .filter(|((_, code), _)| {
+                *code == RecordType::NSEC.code() || *code == RecordType::NSEC3.code()
+            })
+            .flat_map(|(_, rrset)| rrset)
+            .find(|record| match record {
+                Record::NSEC(nsec) => is_ancestor(zone, &nsec.fqdn) && nsec_covers(nsec, &name),
+                Record::NSEC3(nsec3) => {
+                    nsec3.fqdn.parent().as_ref() == Some(zone)
+                        && hash_of(nsec3, &next_closer)
+                            .is_some_and(|hash| nsec3_covers(nsec3, &hash))
+                }
+                _ => false,
+            });
+
+        match proof {
+            Some(record) => self.verify_rrset(std::slice::from_ref(record)),
+            None => Verdict::Bogus(Reason::MissingDenialProof {
+                fqdn: qname.clone(),
+                record_type: rrsig.type_covered,
+            }),
+        }
+    }
+
+    // verifies `rrsig` using the first key in `keys` that matches its key tag and algorithm
+    fn verify_rrsig(&self, rrsig: &RRSIG, rrset: &[Record], keys: &[DNSKEY]) -> CoreResult {
+        let fqdn = rrsig.fqdn.clone();
+        let record_type = rrsig.type_covered;
+        let malformed = || Reason::MalformedRecord {
+            fqdn: fqdn.clone(),
+            record_type,
+        };
+
+        let inception = wire::timestamp(rrsig.signature_inception).map_err(|_| malformed())?;
+        let expiration = wire::timestamp(rrsig.signature_expiration).map_err(|_| malformed())?;
+        // serial number arithmetic; see section 3.1.5 of RFC4034
+        let now = self.now as u32;
+        if (now.wrapping_sub(inception) as i32) < 0 {
+            return Err(Reason::SignatureNotYetValid { fqdn, record_type });
+        }
+        if (expiration.wrapping_sub(now) as i32) < 0 {
+            return Err(Reason::SignatureExpired { fqdn, record_type });
+        }
+
+        // section 5.3.2 of RFC4035: an RRset synthesized from a wildcard was signed with the
+        // wildcard as its owner name
+        let owner_labels = signer::labels(&fqdn);
+        let owner = if rrsig.labels > owner_labels {
+            return Err(Reason::InvalidLabels { fqdn, record_type });
+        } else if rrsig.labels < owner_labels {
+            wildcard_owner(&fqdn, rrsig.labels).ok_or_else(malformed)?
+        } else {
+            fqdn.clone()
+        };
+
+        let mut records = vec![];
+        for record in rrset {
+            let mut buf = vec![];
+            wire::record(record, &owner, rrsig.original_ttl, &mut buf).map_err(|_| malformed())?;
+            records.push(buf);
+        }
+        records.sort();
+        records.dedup();
+
+        let mut data = vec![];
+        wire::rrsig_rdata_without_signature(rrsig, &mut data).map_err(|_| malformed())?;
+        data.extend(records.concat());
+
+        let signature = wire::base64(&rrsig.signature).map_err(|_| malformed())?;
+
+        let mut result = Err(Reason::NoMatchingKey {
+            fqdn: fqdn.clone(),
+            record_type,
+        });
+        for key in keys {
+            if key.algorithm != rrsig.algorithm || key.key_tag().ok() != Some(rrsig.key_tag) {
+                continue;
+            }
+
+            result = match verify_signature(key, &data, &signature) {
+                Some(true) => return Ok(()),
+                Some(false) => Err(Reason::BadSignature {
+                    fqdn: fqdn.clone(),
+                    record_type,
+                }),
+                None => Err(Reason::UnsupportedAlgorithm {
+                    zone: rrsig.signer_name.clone(),
+                }),
+            };
+        }
+
+        result
+    }
+
+    // the DNSKEYs of `zone` that have been authenticated through the chain of trust
+    fn zone_keys(&self, zone: &FQDN) -> core::result::Result<Vec<DNSKEY>, Verdict> {
+        let apex = wire::canonical_name(zone);
+        let dnskeys = self
+            .rrsets
+            .get(&(apex.clone(), RecordType::DNSKEY.code()))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let anchors = self
+            .trust_anchor
+            .iter()
+            .filter(|key| wire::canonical_name(&key.zone) == apex)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let secure_entry_points = if anchors.is_empty() {
+            self.secure_entry_points(zone, dnskeys)?
+        } else if dnskeys.is_empty() {
+            return Ok(anchors);
+        } else {
+            anchors
+        };
+
+        let dnskeys_rrsigs = self
+            .rrsigs
+            .get(&(apex, RecordType::DNSKEY.code()))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let mut last_failure = Reason::MissingRrsig {
+            fqdn: zone.clone(),
+            record_type: RecordType::DNSKEY,
+        };
+        for rrsig in dnskeys_rrsigs {
+            match self.verify_rrsig(rrsig, dnskeys, &secure_entry_points) {
+                Ok(()) => {
+                    return Ok(dnskeys
+                        .iter()
+                        .filter_map(|record| match record {
+                            Record::DNSKEY(dnskey) => Some(dnskey.clone()),
+                            _ => None,
+                        })
+                        .collect())
+                }
+                Err(reason) => last_failure = reason,
+            }
+        }
+
+        Err(Verdict::Bogus(last_failure))
+    }
+
+    // the DNSKEYs of `zone` that match its (authenticated) DS RRset
+    fn secure_entry_points(
+        &self,
+        zone: &FQDN,
+        dnskeys: &[Record],
+    ) -> core::result::Result<Vec<DNSKEY>, Verdict> {
+        if zone.parent().is_none() {
+            return Err(Verdict::Insecure(Reason::NoTrustAnchor {
+                zone: zone.clone(),
+            }));
+        }
+
+        let key = (wire::canonical_name(zone), RecordType::DS.code());
+        let Some(ds_rrset) = self.rrsets.get(&key) else {
+            return Err(self.verify_no_ds(zone));
+        };
+
+        match self.verify_rrset(ds_rrset) {
+            Verdict::Secure => {}
+            verdict => return Err(verdict),
+        }
+
+        if dnskeys.is_empty() {
+            return Err(Verdict::Bogus(Reason::MissingDnskey { zone: zone.clone() }));
+        }
+
+        // section 5.2 of RFC4035: DS records with an unsupported algorithm or digest type are
+        // ignored; if none is left, the zone is treated as insecure
+        let ds_rrset = ds_rrset
+            .iter()
+            .filter_map(|record| match record {
+                Record::DS(ds)
+                    if digest_type(ds.digest_type).is_some()
+                        && is_supported_algorithm(ds.algorithm) =>
+                {
+                    Some(ds)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if ds_rrset.is_empty() {
+            return Err(Verdict::Insecure(Reason::UnsupportedAlgorithm {
+                zone: zone.clone(),
+            }));
+        }
+
+        let matching = dnskeys
+            .iter()
+            .filter_map(|record| match record {
+                Record::DNSKEY(dnskey) => Some(dnskey),
+                _ => None,
+            })
+            .filter(|dnskey| ds_rrset.iter().any(|ds| ds_matches(ds, dnskey)))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if matching.is_empty() {
+            Err(Verdict::Bogus(Reason::DsMismatch { zone: zone.clone() }))
+        } else {
+            Ok(matching)
+        }
+    }
+
+    // looks for a NSEC or NSEC3 record, in the parent zone, that proves that `zone` is an
+    // insecure delegation
+    fn verify_no_ds(&self, zone: &FQDN) -> Verdict {
+        let missing_ds = Verdict::Bogus(Reason::MissingDs { zone: zone.clone() });
+        let owner = wire::canonical_name(zone);
+
+        if let Some([nsec @ Record::NSEC(NSEC { record_types, .. })]) = self
+            .rrsets
+            .get(&(owner, RecordType::NSEC.code()))
+            .map(Vec::as_slice)
+        {
+            let proves = record_types.contains(&RecordType::NS)
+                && !record_types.contains(&RecordType::DS)
+                && !record_types.contains(&RecordType::SOA);
+            if !proves {
+                return missing_ds;
+            }
+
+            return match self.verify_rrset_signed_by(std::slice::from_ref(nsec), true) {
+                Verdict::Secure => Verdict::Insecure(Reason::NoDs { zone: zone.clone() }),
+                verdict => verdict,
+            };
+        }
+
+        let nsec3s = self
+            .rrsets
+            .iter()
+            .filter(|((_, code), _)| *code == RecordType::NSEC3.code())
+            .flat_map(|(_, rrset)| rrset)
+            .filter_map(|record| match record {
+                Record::NSEC3(nsec3) => Some(nsec3),
+                _ => None,
+            })
+            // NSEC3 records of the parent zone
+            .filter(|nsec3| {
+                nsec3
+                    .fqdn
+                    .parent()
+                    .is_some_and(|apex| is_ancestor(&apex, zone) && !is_ancestor(zone, &apex))
+            })
+            .collect::<Vec<_>>();
+
+        for nsec3 in &nsec3s {
+            let Some(hash) = hash_of(nsec3, zone) else {
+                continue;
+            };
+
+            let proves = if owner_hash(nsec3).as_deref() == Some(hash.as_slice()) {
+                nsec3.record_types.contains(&RecordType::NS)
+                    && !nsec3.record_types.contains(&RecordType::DS)
+            } else {
+                nsec3_covers(nsec3, &hash) && nsec3.flags & NSEC3_OPT_OUT_FLAG != 0
+            };
+
+            if proves {
+                return match self.verify_rrset(&[Record::NSEC3((*nsec3).clone())]) {
+                    Verdict::Secure => Verdict::Insecure(Reason::NoDs { zone: zone.clone() }),
+                    verdict => verdict,
+                };
+            }
+        }
+
+        missing_ds
+    }
+
+    // the closest ancestor of `fqdn`, including itself, that has DNSKEYs or a trust anchor
+    fn enclosing_zone(&self, fqdn: &FQDN) -> Option<FQDN> {
+        let mut current = Some(fqdn.clone());
+        while let Some(zone) = current {
+            let apex = wire::canonical_name(&zone);
+            let has_keys = self
+                .rrsets
+                .contains_key(&(apex.clone(), RecordType::DNSKEY.code()))
+                || self
+                    .trust_anchor
+                    .iter()
+                    .any(|key| wire::canonical_name(&key.zone) == apex);
+            if has_keys {
+                return Some(zone);
+            }
+            current = zone.parent();
+        }
+        None
+    }
+
+    // section 3.1.3.2 of RFC4035 and section 8.5 of RFC5155
+    fn verify_nodata(&self, authority: &[Record], qname: &FQDN, qtype: RecordType) -> Verdict {
+        let name = wire::canonical_name(qname);
+        let invalid = || {
+            Verdict::Bogus(Reason::InvalidDenialProof {
+                fqdn: qname.clone(),
+                record_type: qtype,
+            })
+        };
+
+        // the type bitmap of the record that matches `qname` must not contain `qtype` nor CNAME,
+        // and only the DS RRset can be proven absent by the parent side of a delegation
+        let proves = |record_types: &[RecordType]| {
+            let is_delegation =
+                record_types.contains(&RecordType::NS) && !record_types.contains(&RecordType::SOA);
+            !record_types.contains(&qtype)
+                && !record_types.contains(&RecordType::CNAME)
+                && (!is_delegation || qtype == RecordType::DS)
+        };
+
+        for record in authority {
+            match record {
+                Record::NSEC(nsec) if wire::canonical_name(&nsec.fqdn) == name => {
+                    if !proves(&nsec.record_types) {
+                        return invalid();
+                    }
+                    return self.verify_rrset(std::slice::from_ref(record));
+                }
+
+                Record::NSEC3(nsec3) => {
+                    let Some(hash) = hash_of(nsec3, qname) else {
+                        continue;
+                    };
+                    if owner_hash(nsec3).as_deref() == Some(hash.as_slice()) {
+                        if !proves(&nsec3.record_types) {
+                            return invalid();
+                        }
+                        return self.verify_rrset(std::slice::from_ref(record));
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        let nsec3s = authority
+            .iter()
+            .filter_map(|record| match record {
+                Record::NSEC3(nsec3) => Some(nsec3),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // section 8.6 of RFC5155: an insecure delegation may be left out of an opt-out NSEC3
+        // chain; its DS RRset is proven absent by the NSEC3 record that covers it
+        if qtype == RecordType::DS && !nsec3s.is_empty() {
+            let Some((_, matching, covering_next_closer)) = closest_encloser_proof(&nsec3s, qname)
+            else {
+                return invalid();
+            };
+
+            if covering_next_closer.flags & NSEC3_OPT_OUT_FLAG == 0 {
+                return invalid();
+            }
+
+            for nsec3 in [matching, covering_next_closer] {
+                let verdict = self.verify_rrset(&[Record::NSEC3(nsec3.clone())]);
+                if !verdict.is_secure() {
+                    return verdict;
+                }
+            }
+
+            return Verdict::Insecure(Reason::OptOut {
+                fqdn: qname.clone(),
+            });
+        }
+
+        self.missing_denial_proof(authority, qname, qtype)
+    }
+
+    // section 3.1.3.2 of RFC4035 and section 8.4 of RFC5155
+    fn verify_nxdomain(&self, authority: &[Record], qname: &FQDN, qtype: RecordType) -> Verdict {
+        let nsecs = authority
+            .iter()
+            .filter_map(|record| match record {
+                Record::NSEC(nsec) => Some(nsec),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let nsec3s = authority
+            .iter()
+            .filter_map(|record| match record {
+                Record::NSEC3(nsec3) => Some(nsec3),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let invalid = || {
+            Verdict::Bogus(Reason::InvalidDenialProof {
+                fqdn: qname.clone(),
+                record_type: qtype,
+            })
+        };
+
+        let proof = if !nsecs.is_empty() {
+            let name = wire::canonical_name(qname);
+            let Some(covering) = nsecs.iter().find(|nsec| nsec_covers(nsec, &name)) else {
+                return invalid();
+            };
+
+            let closest_encloser_len =
+                common_prefix_len(&name, &wire::canonical_name(&covering.fqdn)).max(
+                    common_prefix_len(&name, &wire::canonical_name(&covering.next_domain)),
+                );
+            let mut wildcard = name[..closest_encloser_len].to_vec();
+            wildcard.push(b"*".to_vec());
+
+            let Some(wildcard_covering) = nsecs.iter().find(|nsec| nsec_covers(nsec, &wildcard))
+            else {
+                return invalid();
+            };
+
+            vec![
+                (*covering).clone().into(),
+                (*wildcard_covering).clone().into(),
+            ]
+        } else if !nsec3s.is_empty() {
+            let Some((closest_encloser, matching, covering_next_closer)) =
+                closest_encloser_proof(&nsec3s, qname)
+            else {
+                return invalid();
+            };
+
+            let Ok(wildcard) = closest_encloser.prepend_label("*") else {
+                return invalid();
+            };
+            let Some(covering_wildcard) = nsec3s.iter().find(|nsec3| {
+                hash_of(nsec3, &wildcard).is_some_and(|hash| nsec3_covers(nsec3, &hash))
+            }) else {
+                return invalid();
+            };
+
+            let opt_out = covering_next_closer.flags & NSEC3_OPT_OUT_FLAG != 0;
+            let proof = [matching, covering_next_closer, covering_wildcard]
+                .into_iter()
+                .map(|nsec3| Record::NSEC3(nsec3.clone()))
+                .collect::<Vec<_>>();
+
+            if opt_out {
+                for record in &proof {
+                    let verdict = self.verify_rrset(std::slice::from_ref(record));
+                    if !verdict.is_secure() {
+                        return verdict;
+                    }
+                }
+
+                return Verdict::Insecure(Reason::OptOut {
+                    fqdn: qname.clone(),
+                });
+            }
+
+            proof
+        } else {
+            return self.missing_denial_proof(authority, qname, qtype);
+        };
+
+        for record in &proof {
+            let verdict = self.verify_rrset(std::slice::from_ref(record));
+            if !verdict.is_secure() {
+                return verdict;
+            }
+        }
+
+        Verdict::Secure
+    }
+
+    // no proof is fine as long as the zone is insecure
+    fn missing_denial_proof(
+        &self,
+        authority: &[Record],
+        qname: &FQDN,
+        qtype: RecordType,
+    ) -> Verdict {
+        let soa = authority.iter().find(|record| record.is_soa());
+        if let Some(soa) = soa {
+            let verdict = self.verify_rrset(std::slice::from_ref(soa));
+            if verdict.is_insecure() {
+                return verdict;
+            }
+        }
+
+        Verdict::Bogus(Reason::MissingDenialProof {
+            fqdn: qname.clone(),
+            record_type: qtype,
+        })
+    }
+}
+
+type CoreResult = core::result::Result<(), Reason>;
+
+// `None` if the algorithm is not supported
+fn verify_signature(dnskey: &DNSKEY, data: &[u8], signature: &[u8]) -> Option<bool> {
+    let public_key = wire::base64(&dnskey.public_key).ok()?;
+
+    let rsa = |params: &'static signature::RsaParameters| {
+        // section 2 of RFC3110
+        let (exponent_len, rest) = match public_key.split_first()? {
+            (0, rest) if rest.len() >= 2 => (
+                usize::from(u16::from_be_bytes([rest[0], rest[1]])),
+                &rest[2..],
+            ),
+            (len, rest) => (usize::from(*len), rest),
+        };
+        if rest.len() <= exponent_len {
+            return Some(false);
+        }
+        let (e, n) = rest.split_at(exponent_len);
+
+        Some(
+            RsaPublicKeyComponents { n, e }
+                .verify(params, data, signature)
+                .is_ok(),
+        )
+    };
+
+    let unparsed = |algorithm: &'static dyn signature::VerificationAlgorithm, key: Vec<u8>| {
+        Some(
+            UnparsedPublicKey::new(algorithm, key)
+                .verify(data, signature)
+                .is_ok(),
+        )
+    };
+
+    let uncompressed_point = || {
+        let mut key = vec![0x04];
+        key.extend(&public_key);
+        key
+    };
+
+    match dnskey.algorithm {
+        5 | 7 => rsa(&signature::RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY),
+        8 => rsa(&signature::RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY),
+        10 => rsa(&signature::RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY),
+        13 => unparsed(&signature::ECDSA_P256_SHA256_FIXED, uncompressed_point()),
+        14 => unparsed(&signature::ECDSA_P384_SHA384_FIXED, uncompressed_point()),
+        15 => unparsed(&signature::ED25519, public_key.clone()),
+        _ => None,
+    }
+}
+
+// the DNSKEY algorithms that `verify_signature` can verify
+fn is_supported_algorithm(code: u8) -> bool {
+    matches!(code, 5 | 7 | 8 | 10 | 13 | 14 | 15)
+}
+
+fn digest_type(code: u8) -> Option<DigestType> {
+    [DigestType::SHA1, DigestType::SHA256, DigestType::SHA384]
+        .into_iter()
+        .find(|digest_type| digest_type.code() == code)
+}
+
+fn ds_matches(ds: &DS, dnskey: &DNSKEY) -> bool {
+    let Some(digest_type) = digest_type(ds.digest_type) else {
+        return false;
+    };
+
+    let Ok(expected) = dnskey.ds(digest_type) else {
+        return false;
+    };
+
+    let digest = HEXUPPER_PERMISSIVE.decode(ds.digest.as_bytes());
+    let expected_digest = HEXUPPER_PERMISSIVE.decode(expected.digest.as_bytes());
+
+    ds.key_tag == expected.key_tag
+        && ds.algorithm == dnskey.algorithm
+        && digest.is_ok()
+        && digest.ok() == expected_digest.ok()
+}
+
+fn is_ancestor(ancestor: &FQDN, fqdn: &FQDN) -> bool {
+    wire::canonical_name(fqdn).starts_with(&wire::canonical_name(ancestor))
+}
+
+// `*.` followed by the rightmost `labels` labels of `fqdn`
+fn wildcard_owner(fqdn: &FQDN, labels: u8) -> Option<FQDN> {
+    let mut closest_encloser = fqdn.clone();
+    while closest_encloser.num_labels() > usize::from(labels) {
+        closest_encloser = closest_encloser.parent()?;
+    }
+
+    closest_encloser.prepend_label("*").ok()
+}
+
+fn common_prefix_len(a: &CanonicalName, b: &CanonicalName) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+// whether `name` falls strictly between the owner name and the next domain name of `nsec`
+fn nsec_covers(nsec: &NSEC, name: &CanonicalName) -> bool {
+    let owner = wire::canonical_name(&nsec.fqdn);
+    let next = wire::canonical_name(&nsec.next_domain);
+
+    if owner < next {
+        owner < *name && *name < next
+    } else {
+        // the last NSEC record in the chain
+        owner < *name
+    }
+}
+
+fn owner_hash(nsec3: &NSEC3) -> Option<Vec<u8>> {
+    let label = nsec3.fqdn.labels().next()?;
+    BASE32HEX_NOPAD
+        .decode(label.to_ascii_uppercase().as_bytes())
+        .ok()
+}
+
+// hash of `fqdn` using the parameters of `nsec3`
+fn hash_of(nsec3: &NSEC3, fqdn: &FQDN) -> Option<Vec<u8>> {
+    let salt = if nsec3.salt == "-" {
+        vec![]
+    } else {
+        HEXUPPER_PERMISSIVE.decode(nsec3.salt.as_bytes()).ok()?
+    };
+
+    Some(signer::nsec3_hash(fqdn, &salt, nsec3.iterations))
+}
+
+fn nsec3_covers(nsec3: &NSEC3, hash: &[u8]) -> bool {
+    let (Some(owner), Ok(next)) = (
+        owner_hash(nsec3),
+        BASE32HEX_NOPAD.decode(nsec3.next_hashed_owner_name.to_ascii_uppercase().as_bytes()),
+    ) else {
+        return false;
+    };
+
+    if owner < next {
+        owner.as_slice() < hash && hash < next.as_slice()
+    } else {
+        owner.as_slice() < hash || hash < next.as_slice()
+    }
+}
+
+// section 8.3 of RFC5155. returns the closest encloser, the NSEC3 record that matches it and the
+// NSEC3 record that covers the next closer name
+fn closest_encloser_proof<'a>(
+    nsec3s: &[&'a NSEC3],
+    qname: &FQDN,
+) -> Option<(FQDN, &'a NSEC3, &'a NSEC3)> {
+    let zone = nsec3s.first()?.fqdn.parent()?;
+
+    let mut next_closer = qname.clone();
+    let mut candidate = qname.parent()?;
+    loop {
+        if !is_ancestor(&zone, &candidate) {
+            return None;
+        }
+
+        let matching = nsec3s.iter().find(|nsec3| {
+            hash_of(nsec3, &candidate).is_some_and(|hash| owner_hash(nsec3) == Some(hash))
+        });
+
+        if let Some(matching) = matching {
+            let covering_next_closer = nsec3s.iter().find(|nsec3| {
+                hash_of(nsec3, &next_closer).is_some_and(|hash| nsec3_covers(nsec3, &hash))
+            })?;
+
+            return Some((candidate, matching, covering_next_closer));
+        }
+
+        next_closer = candidate.clone();
+        candidate = candidate.parent()?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::name_server::Nsec;
+    use crate::record::{Algorithm, SOA};
+    use crate::signer::{Signer, SigningKey};
+    use crate::DEFAULT_TTL;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn zone_signed_in_process_is_secure() -> Result<()> {
+        for nsec in [Nsec::_1, nsec3(false)] {
+            let signer = signer(nsec)?;
+            let signed = signer.sign_zone(&zone_file()?)?;
+
+            let verdict = Validator::new(&trust_anchor(&signer)).validate_zone_file(&signed);
+            assert_eq!(Verdict::Secure, verdict);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn zone_signed_with_combined_key_is_secure() -> Result<()> {
+        let csk = SigningKey::combined_signing_key(FQDN("example.")?, Algorithm::ECDSAP256SHA256)?;
+        let signer = Signer::with_keys(vec![csk], Nsec::_1)?;
+        let signed = signer.sign_zone(&zone_file()?)?;
+
+        let verdict = Validator::new(&trust_anchor(&signer)).validate_zone_file(&signed);
+        assert_eq!(Verdict::Secure, verdict);
+
+        Ok(())
+    }
+
+    // `ldns-signzone` output that uses RSA keys
+    #[test]
+    fn zone_signed_by_ldns_is_secure() -> Result<()> {
+        let mut zone_file: ZoneFile = include_str!("zone_file/muster.zone").parse()?;
+        // the serial number of the fixture was changed after the zone was signed
+        zone_file.soa.settings.serial = 2024010101;
+
+        let ksk = zone_file
+            .records
+            .iter()
+            .find_map(|record| match record {
+                Record::DNSKEY(dnskey) if dnskey.flags == 257 => Some(dnskey.clone()),
+                _ => None,
+            })
+            .expect("KSK not found");
+
+        let mut trust_anchor = TrustAnchor::empty();
+        trust_anchor.add(ksk);
+        let verdict = Validator::new(&trust_anchor)
+            .at_time(wire::timestamp(20240301000000)?.into())
+            .validate_zone_file(&zone_file);
+        assert_eq!(Verdict::Secure, verdict);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bad_signature_is_bogus() -> Result<()> {
+        let signer = signer(Nsec::_1)?;
+        let mut signed = signer.sign_zone(&zone_file()?)?;
+
+        let needle = FQDN("a.example.")?;
+        for record in &mut signed.records {
+            if let Record::RRSIG(rrsig) = record {
+                if rrsig.fqdn == needle && rrsig.type_covered == RecordType::A {
+                    let mut signature = wire::base64(&rrsig.signature)?;
+                    *signature.last_mut().unwrap() ^= 1;
+                    rrsig.signature = data_encoding::BASE64.encode(&signature);
+                }
+            }
+        }
+
+        let mut validator = Validator::new(&trust_anchor(&signer));
+        validator.add_zone_file(&signed);
+
+        assert_eq!(
+            Verdict::Bogus(Reason::BadSignature {
+                fqdn: needle.clone(),
+                record_type: RecordType::A,
+            }),
+            validator.validate_rrset(&needle, RecordType::A)
+        );
+        assert!(validator.validate_zone_file(&signed).is_bogus());
+
+        Ok(())
+    }
+
+    #[test]
+    fn expired_signature_is_bogus() -> Result<()> {
+        let signer = signer(Nsec::_1)?;
+        let signed = signer.sign_zone(&zone_file()?)?;
+
+        let mut validator = Validator::new(&trust_anchor(&signer));
+        validator.add_zone_file(&signed);
+
+        let needle = FQDN("a.example.")?;
+        validator.at_time(signer.inception - 1);
+        assert!(matches!(
+            validator.validate_rrset(&needle, RecordType::A),
+            Verdict::Bogus(Reason::SignatureNotYetValid { .. })
+        ));
+
+        validator.at_time(signer.expiration + 1);
+        assert!(matches!(
+            validator.validate_rrset(&needle, RecordType::A),
+            Verdict::Bogus(Reason::SignatureExpired { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_trust_anchor_is_bogus() -> Result<()> {
+        let signed = signer(Nsec::_1)?.sign_zone(&zone_file()?)?;
+        let other_signer = signer(Nsec::_1)?;
+
+        let verdict = Validator::new(&trust_anchor(&other_signer)).validate_zone_file(&signed);
+        assert!(verdict.is_bogus());
+
+        Ok(())
+    }
+
+    #[test]
+    fn denial_of_existence() -> Result<()> {
+        let nxdomain = FQDN("b.example.")?;
+        let nodata = FQDN("a.example.")?;
+
+        for nsec in [Nsec::_1, nsec3(false)] {
+            let signer = signer(nsec)?;
+            let signed = signer.sign_zone(&zone_file()?)?;
+            let mut validator = Validator::new(&trust_anchor(&signer));
+            validator.add_zone_file(&signed);
+
+            let output = negative_response(&signed, DigStatus::NXDOMAIN);
+            let verdict = validator.validate_response(&output, &nxdomain, RecordType::A)?;
+            assert_eq!(Verdict::Secure, verdict);
+
+            let output = negative_response(&signed, DigStatus::NOERROR);
+            let verdict = validator.validate_response(&output, &nodata, RecordType::MX)?;
+            assert_eq!(Verdict::Secure, verdict);
+
+            // the type bitmap says there's an A RRset
+            let verdict = validator.validate_response(&output, &nodata, RecordType::A)?;
+            assert!(matches!(
+                verdict,
+                Verdict::Bogus(Reason::InvalidDenialProof { .. })
+            ));
+
+            // no proof at all
+            let mut output = negative_response(&signed, DigStatus::NXDOMAIN);
+            output.authority.retain(|record| record.is_soa());
+            let verdict = validator.validate_response(&output, &nxdomain, RecordType::A)?;
+            assert!(matches!(
+                verdict,
+                Verdict::Bogus(Reason::MissingDenialProof { .. })
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn opt_out_nxdomain_is_insecure() -> Result<()> {
+        let signer = signer(nsec3(true))?;
+        let signed = signer.sign_zone(&zone_file()?)?;
+        let mut validator = Validator::new(&trust_anchor(&signer));
+        validator.add_zone_file(&signed);
+
+        let needle = FQDN("b.example.")?;
+        let output = negative_response(&signed, DigStatus::NXDOMAIN);
+        let verdict = validator.validate_response(&output, &needle, RecordType::A)?;
+        assert_eq!(Verdict::Insecure(Reason::OptOut { fqdn: needle }), verdict);
+
+        Ok(())
+    }
+
+    #[test]
+    fn nodata_proof_must_not_contain_cname_or_delegation() -> Result<()> {
+        let alias = FQDN("alias.example.")?;
+        let delegation = FQDN("child.example.")?;
+
+        for nsec in [Nsec::_1, nsec3(false)] {
+            let signer = signer(nsec)?;
+            let mut zone_file = zone_file()?;
+            zone_file.add(Record::cname(alias.clone(), FQDN("a.example.")?));
+            let signed = signer.sign_zone(&zone_file)?;
+            let mut validator = Validator::new(&trust_anchor(&signer));
+            validator.add_zone_file(&signed);
+
+            let output = negative_response(&signed, DigStatus::NOERROR);
+            let verdict = validator.validate_response(&output, &alias, RecordType::A)?;
+            assert!(matches!(
+                verdict,
+                Verdict::Bogus(Reason::InvalidDenialProof { .. })
+            ));
+
+            // the parent side of a delegation only proves the absence of the DS RRset
+            let verdict = validator.validate_response(&output, &delegation, RecordType::A)?;
+            assert!(matches!(
+                verdict,
+                Verdict::Bogus(Reason::InvalidDenialProof { .. })
+            ));
+
+            let verdict = validator.validate_response(&output, &delegation, RecordType::DS)?;
+            assert_eq!(Verdict::Secure, verdict);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn opt_out_ds_nodata_is_insecure() -> Result<()> {
+        let signer = signer(nsec3(true))?;
+        let signed = signer.sign_zone(&zone_file()?)?;
+        let mut validator = Validator::new(&trust_anchor(&signer));
+        validator.add_zone_file(&signed);
+
+        // the insecure delegation has no NSEC3 record of its own
+        let needle = FQDN("child.example.")?;
+        let output = negative_response(&signed, DigStatus::NOERROR);
+        let verdict = validator.validate_response(&output, &needle, RecordType::DS)?;
+        assert_eq!(Verdict::Insecure(Reason::OptOut { fqdn: needle }), verdict);
+
+        Ok(())
+    }
+
+    #[test]
+    fn wildcard_expansion() -> Result<()> {
+        let qname = FQDN("foo.example.")?;
+        let wildcard = FQDN("*.example.")?;
+
+        for nsec in [Nsec::_1, nsec3(false)] {
+            let signer = signer(nsec)?;
+            let mut zone_file = zone_file()?;
+            zone_file.add(Record::a(wildcard.clone(), [9, 9, 9, 9].into()));
+            let signed = signer.sign_zone(&zone_file)?;
+
+            // only the keys; the denial of existence records must come from the response
+            let mut validator = Validator::new(&trust_anchor(&signer));
+            validator.add_records(
+                signed
+                    .records
+                    .iter()
+                    .filter(|record| match record {
+                        Record::RRSIG(rrsig) => rrsig.type_covered == RecordType::DNSKEY,
+                        record => record.record_type() == RecordType::DNSKEY,
+                    })
+                    .cloned(),
+            );
+
+            // the answer that a name server synthesizes from the wildcard
+            let answer = signed
+                .records
+                .iter()
+                .filter(|record| record.fqdn() == &wildcard)
+                .filter_map(|record| match record.clone() {
+                    Record::A(mut a) => {
+                        a.fqdn = qname.clone();
+                        Some(Record::A(a))
+                    }
+                    Record::RRSIG(mut rrsig) if rrsig.type_covered == RecordType::A => {
+                        rrsig.fqdn = qname.clone();
+                        Some(Record::RRSIG(rrsig))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            let mut output = negative_response(&signed, DigStatus::NOERROR);
+            output.answer = answer.clone();
+            let verdict = validator.validate_response(&output, &qname, RecordType::A)?;
+            assert_eq!(Verdict::Secure, verdict);
+
+            // no proof that `qname` does not exist
+            output.authority.clear();
+            let verdict = validator.validate_response(&output, &qname, RecordType::A)?;
+            assert!(matches!(
+                verdict,
+                Verdict::Bogus(Reason::MissingDenialProof { .. })
+            ));
+
+            // more labels than the owner name has
+            output.answer = answer
+                .into_iter()
+                .map(|record| match record {
+                    Record::RRSIG(mut rrsig) => {
+                        rrsig.labels = 3;
+                        Record::RRSIG(rrsig)
+                    }
+                    record => record,
+                })
+                .collect();
+            let verdict = validator.validate_response(&output, &qname, RecordType::A)?;
+            assert!(matches!(
+                verdict,
+                Verdict::Bogus(Reason::InvalidLabels { .. })
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_dnskey_algorithm_is_insecure() -> Result<()> {
+        let child = FQDN("child.example.")?;
+        let child_ksk = SigningKey::key_signing_key(child.clone(), Algorithm::ECDSAP256SHA256)?;
+        let child_zsk = SigningKey::zone_signing_key(child.clone(), Algorithm::ECDSAP256SHA256)?;
+        let child_signer = Signer::new(child_ksk, child_zsk, Nsec::_1)?;
+        let mut child_zone_file = ZoneFile::new(SOA {
+            zone: child.clone(),
+            ttl: DEFAULT_TTL,
+            nameserver: FQDN("ns.child.example.")?,
+            admin: FQDN("admin.child.example.")?,
+            settings: Default::default(),
+        });
+        child_zone_file.add(Record::a(FQDN("a.child.example.")?, [1, 2, 3, 4].into()));
+        let mut child_signed = child_signer.sign_zone(&child_zone_file)?;
+
+        // a KSK that uses ED448, which can't be verified, is the only secure entry point
+        let mut ed448 = child_signer.key_signing_key().dnskey().clone();
+        ed448.algorithm = 16;
+        child_signed.add(ed448.clone());
+
+        let signer = signer(Nsec::_1)?;
+        let mut zone_file = zone_file()?;
+        zone_file.add(ed448.ds(DigestType::SHA256)?);
+        let signed = signer.sign_zone(&zone_file)?;
+
+        let mut validator = Validator::new(&trust_anchor(&signer));
+        validator.add_zone_file(&signed);
+        validator.add_zone_file(&child_signed);
+
+        assert_eq!(
+            Verdict::Insecure(Reason::UnsupportedAlgorithm { zone: child }),
+            validator.validate_rrset(&FQDN("a.child.example.")?, RecordType::A)
+        );
+
+        Ok(())
+    }
+
+    fn nsec3(opt_out: bool) -> Nsec {
+        Nsec::_3 {
+            iterations: 0,
+            opt_out,
+            salt: None,
+        }
+    }
+
+    fn signer(nsec: Nsec) -> Result<Signer> {
+        let zone = FQDN("example.")?;
+        let ksk = SigningKey::key_signing_key(zone.clone(), Algorithm::ECDSAP256SHA256)?;
+        let zsk = SigningKey::zone_signing_key(zone, Algorithm::ECDSAP256SHA256)?;
+        Signer::new(ksk, zsk, nsec)
+    }
+
+    fn trust_anchor(signer: &Signer) -> TrustAnchor {
+        let mut trust_anchor = TrustAnchor::empty();
+        trust_anchor.add(signer.key_signing_key().dnskey().clone());
+        trust_anchor
+    }
+
+    fn zone_file() -> Result<ZoneFile> {
+        let zone = FQDN("example.")?;
+        let mut zone_file = ZoneFile::new(SOA {
+            zone: zone.clone(),
+            ttl: DEFAULT_TTL,
+            nameserver: FQDN("ns.example.")?,
+            admin: FQDN("admin.example.")?,
+            settings: Default::default(),
+        });
+        zone_file.add(Record::ns(zone, FQDN("a.example.")?));
+        zone_file.add(Record::a(FQDN("a.example.")?, [1, 2, 3, 4].into()));
+        zone_file.referral(
+            FQDN("child.example.")?,
+            FQDN("ns.child.example.")?,
+            [5, 6, 7, 8].into(),
+        );
+
+        Ok(zone_file)
+    }
+
+    // a response whose authority section contains the SOA record and all the NSEC(3) records of
+    // `signed` zone, plus their RRSIGs
+    fn negative_response(signed: &ZoneFile, status: DigStatus) -> DigOutput {
+        let is_denial = |record_type| {
+            [RecordType::SOA, RecordType::NSEC, RecordType::NSEC3].contains(&record_type)
+        };
+
+        let authority = [signed.soa.clone().into()]
+            .into_iter()
+            .chain(signed.records.iter().cloned())
+            .filter(|record| match record {
+                Record::RRSIG(rrsig) => is_denial(rrsig.type_covered),
+                record => is_denial(record.record_type()),
+            })
+            .collect();
+
+        DigOutput {
+            ede: None,
+            flags: Default::default(),
+            status,
+            answer: vec![],
+            authority,
+            tsig: None,
+        }
+    }
+}
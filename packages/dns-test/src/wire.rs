@@ -0,0 +1,841 @@
+//! Wire format encoding and decoding of domain names, records and messages
+//!
+//! Three encodings are produced: the canonical form described in section 6 of RFC4034, where
+//! domain names are lowercased and never compressed, which is used for signing and validation;
+//! the uncompressed form, which preserves the case of domain names; and the message form, where
+//! domain names are compressed as described in section 4.1.4 of RFC1035
+
+mod decode;
+mod message;
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use data_encoding::{BASE32HEX_NOPAD, BASE64, HEXUPPER_PERMISSIVE};
+
+use self::decode::Decoder;
+pub use self::message::{Edns, EdnsOption, Header, Message, Question};
+use crate::fqdn;
+use crate::record::{Record, RecordType, SvcParam, UnknownRdata, RRSIG};
+use crate::{Error, Result, FQDN};
+
+const CLASS_IN: u16 = 1;
+
+// the two most significant bits of a compression pointer are set (section 4.1.4 of RFC1035)
+const POINTER_MASK: u16 = 0xC000;
+const MAX_POINTER_OFFSET: u16 = !POINTER_MASK;
+
+/// Appends the canonical wire form of `fqdn` to `buf`
+pub(crate) fn name(fqdn: &FQDN, buf: &mut Vec<u8>) {
+    let mut encoder = Encoder::canonical();
+    encoder.name(fqdn, NameKind::Compressible);
+    buf.extend(encoder.buf);
+}
+
+// owner names as reversed sequences of lowercased labels; their `Ord` implementation matches the
+// canonical ordering described in section 6.1 of RFC4034
+pub(crate) type CanonicalName = Vec<Vec<u8>>;
+
+pub(crate) fn canonical_name(fqdn: &FQDN) -> CanonicalName {
+    fqdn.wire_labels()
+        .into_iter()
+        .rev()
+        .map(|label| label.to_ascii_lowercase())
+        .collect()
+}
+
+/// Inverse of `canonical_name`; the labels are lowercased
+pub(crate) fn fqdn_from_canonical(key: &CanonicalName) -> Result<FQDN> {
+    if key.is_empty() {
+        return Ok(FQDN::ROOT);
+    }
+
+    let mut fqdn = String::new();
+    for label in key.iter().rev() {
+        fqdn::escape_label(label, &mut fqdn);
+        fqdn.push('.');
+    }
+
+    FQDN(fqdn)
+}
+
+/// Appends the wire form of the whole record, using `owner` and `ttl` in place of the record's
+/// owner name and TTL
+pub(crate) fn record(record: &Record, owner: &FQDN, ttl: u32, buf: &mut Vec<u8>) -> Result<()> {
+    let mut encoder = Encoder::canonical();
+    encoder.record(owner, record, ttl)?;
+    buf.extend(encoder.buf);
+
+    Ok(())
+}
+
+/// The RDATA section of `record` in canonical form
+pub(crate) fn rdata(record: &Record) -> Result<Vec<u8>> {
+    let mut encoder = Encoder::canonical();
+    encode_rdata(record, &mut encoder)?;
+
+    Ok(encoder.buf)
+}
+
+/// The RRSIG RDATA fields that precede the signature; they are part of the signed data
+pub(crate) fn rrsig_rdata_without_signature(rrsig: &RRSIG, buf: &mut Vec<u8>) -> Result<()> {
+    let mut encoder = Encoder::canonical();
+    encode_rrsig_fields(rrsig, &mut encoder)?;
+    buf.extend(encoder.buf);
+
+    Ok(())
+}
+
+impl FQDN {
+    /// The uncompressed wire form of this domain name
+    pub fn to_wire(&self) -> Vec<u8> {
+        let mut encoder = Encoder::uncompressed();
+        encoder.name(self, NameKind::Compressible);
+        encoder.buf
+    }
+
+    /// The wire form of this domain name with all its labels lowercased
+    pub fn to_canonical_wire(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        name(self, &mut buf);
+        buf
+    }
+
+    /// Decodes an uncompressed domain name that spans all of `bytes`
+    pub fn from_wire(bytes: &[u8]) -> Result<Self> {
+        let mut decoder = Decoder::new(bytes);
+        let fqdn = decoder.name()?;
+        decoder.finish()?;
+
+        Ok(fqdn)
+    }
+}
+
+impl Record {
+    /// The uncompressed wire form of the whole record, including its owner name and TTL
+    pub fn to_wire(&self) -> Result<Vec<u8>> {
+        let mut encoder = Encoder::uncompressed();
+        encoder.record(self.fqdn(), self, self.ttl())?;
+
+        Ok(encoder.buf)
+    }
+
+    /// The canonical form of the record described in section 6.2 of RFC4034
+    pub fn to_canonical_wire(&self) -> Result<Vec<u8>> {
+        let mut buf = vec![];
+        record(self, self.fqdn(), self.ttl(), &mut buf)?;
+
+        Ok(buf)
+    }
+
+    /// Decodes an uncompressed record that spans all of `bytes`
+    pub fn from_wire(bytes: &[u8]) -> Result<Self> {
+        let mut decoder = Decoder::new(bytes);
+        let record = decoder.record()?;
+        decoder.finish()?;
+
+        Ok(record)
+    }
+}
+
+/// How a domain name is treated by the different encodings
+#[derive(Clone, Copy)]
+enum NameKind {
+    /// May be compressed in messages and is lowercased in the canonical form, e.g. owner names
+    /// and the NS target (section 3.3 of RFC1035)
+    Compressible,
+    /// Never compressed but lowercased in the canonical form, e.g. the RRSIG signer name
+    Uncompressed,
+    /// Neither compressed nor lowercased, e.g. the NSEC next domain name (section 5.1 of RFC6840)
+    Verbatim,
+}
+
+/// Writes DNS data in wire format
+struct Encoder {
+    buf: Vec<u8>,
+    /// Lowercase the names, as in the canonical form
+    canonical: bool,
+    /// Offsets of the names written so far, keyed by their presentation form; `None` if names
+    /// must not be compressed
+    compression: Option<HashMap<String, u16>>,
+}
+
+impl Encoder {
+    fn canonical() -> Self {
+        Self {
+            buf: vec![],
+            canonical: true,
+            compression: None,
+        }
+    }
+
+    fn uncompressed() -> Self {
+        Self {
+            buf: vec![],
+            canonical: false,
+            compression: None,
+        }
+    }
+
+    fn compressed() -> Self {
+        Self {
+            buf: vec![],
+            canonical: false,
+            compression: Some(HashMap::new()),
+        }
+    }
+
+    fn name(&mut self, fqdn: &FQDN, kind: NameKind) {
+        let labels = fqdn.labels().collect::<Vec<_>>();
+        let wire_labels = fqdn.wire_labels();
+
+        for index in 0..labels.len() {
+            if let Some(compression) = &mut self.compression {
+                let suffix = labels[index..].join(".");
+                if let Some(offset) = compression.get(&suffix) {
+                    if matches!(kind, NameKind::Compressible) {
+                        self.buf.extend((POINTER_MASK | offset).to_be_bytes());
+                        return;
+                    }
+                } else if let Ok(offset) = u16::try_from(self.buf.len()) {
+                    // names in any part of the message can be the target of a pointer
+                    if offset <= MAX_POINTER_OFFSET {
+                        compression.insert(suffix, offset);
+                    }
+                }
+            }
+
+            let label = &wire_labels[index];
+            self.buf.push(label.len() as u8);
+            if self.canonical && !matches!(kind, NameKind::Verbatim) {
+                self.buf
+                    .extend(label.iter().map(|byte| byte.to_ascii_lowercase()));
+            } else {
+                self.buf.extend(label.iter());
+            }
+        }
+        self.buf.push(0);
+    }
+
+    fn record(&mut self, owner: &FQDN, record: &Record, ttl: u32) -> Result<()> {
+        self.name(owner, NameKind::Compressible);
+        self.buf.extend(record.record_type().code().to_be_bytes());
+        self.buf.extend(CLASS_IN.to_be_bytes());
+        self.buf.extend(ttl.to_be_bytes());
+
+        let rdlength = self.buf.len();
+        self.buf.extend([0, 0]);
+        encode_rdata(record, self)?;
+        let len = u16::try_from(self.buf.len() - rdlength - 2)?;
+        self.buf[rdlength..rdlength + 2].copy_from_slice(&len.to_be_bytes());
+
+        Ok(())
+    }
+}
+
+fn encode_rdata(record: &Record, encoder: &mut Encoder) -> Result<()> {
+    match record {
+        Record::A(a) => encoder.buf.extend(a.ipv4_addr.octets()),
+
+        Record::AAAA(aaaa) => encoder.buf.extend(aaaa.ipv6_addr.octets()),
+
+        Record::CAA(caa) => {
+            encoder.buf.push(caa.flags);
+            encoder.buf.push(u8::try_from(caa.tag.len())?);
+            encoder.buf.extend(caa.tag.bytes());
+            encoder.buf.extend(unescape(&caa.value)?);
+        }
+
+        Record::CDNSKEY(cdnskey) => {
+            encoder.buf.extend(cdnskey.flags.to_be_bytes());
+            encoder.buf.push(cdnskey.protocol);
+            encoder.buf.push(cdnskey.algorithm);
+            encoder.buf.extend(base64(&cdnskey.public_key)?);
+        }
+
+        Record::CDS(cds) => {
+            encoder.buf.extend(cds.key_tag.to_be_bytes());
+            encoder.buf.push(cds.algorithm);
+            encoder.buf.push(cds.digest_type);
+            encoder
+                .buf
+                .extend(HEXUPPER_PERMISSIVE.decode(cds.digest.as_bytes())?);
+        }
+
+        Record::CNAME(cname) => encoder.name(&cname.target, NameKind::Compressible),
+
+        // RFC6672 section 2.5: the target is lowercased in the canonical form even though
+        // RFC4034 does not list DNAME
+        Record::DNAME(dname) => encoder.name(&dname.target, NameKind::Uncompressed),
+
+        Record::DNSKEY(dnskey) => {
+            encoder.buf.extend(dnskey.flags.to_be_bytes());
+            encoder.buf.push(dnskey.protocol);
+            encoder.buf.push(dnskey.algorithm);
+            encoder.buf.extend(base64(&dnskey.public_key)?);
+        }
+
+        Record::DS(ds) => {
+            encoder.buf.extend(ds.key_tag.to_be_bytes());
+            encoder.buf.push(ds.algorithm);
+            encoder.buf.push(ds.digest_type);
+            encoder
+                .buf
+                .extend(HEXUPPER_PERMISSIVE.decode(ds.digest.as_bytes())?);
+        }
+
+        Record::HTTPS(https) => {
+            encoder.buf.extend(https.priority.to_be_bytes());
+            encoder.name(&https.target, NameKind::Verbatim);
+            svc_params(&https.params, &mut encoder.buf)?;
+        }
+
+        Record::MX(mx) => {
+            encoder.buf.extend(mx.preference.to_be_bytes());
+            encoder.name(&mx.exchange, NameKind::Compressible);
+        }
+
+        Record::NS(ns) => encoder.name(&ns.nameserver, NameKind::Compressible),
+
+        // RFC6840 section 5.1: the next domain name is not lowercased
+        Record::NSEC(nsec) => {
+            encoder.name(&nsec.next_domain, NameKind::Verbatim);
+            type_bitmaps(&nsec.record_types, &mut encoder.buf);
+        }
+
+        Record::NSEC3(nsec3) => {
+            encoder.buf.push(nsec3.hash_alg);
+            encoder.buf.push(nsec3.flags);
+            encoder.buf.extend(nsec3.iterations.to_be_bytes());
+            salt(&nsec3.salt, &mut encoder.buf)?;
+            let hash =
+                BASE32HEX_NOPAD.decode(nsec3.next_hashed_owner_name.to_uppercase().as_bytes())?;
+            encoder.buf.push(u8::try_from(hash.len())?);
+            encoder.buf.extend(hash);
+            type_bitmaps(&nsec3.record_types, &mut encoder.buf);
+        }
+
+        Record::NSEC3PARAM(nsec3param) => {
+            encoder.buf.push(nsec3param.hash_alg);
+            encoder.buf.push(nsec3param.flags);
+            encoder.buf.extend(nsec3param.iterations.to_be_bytes());
+            salt(&nsec3param.salt, &mut encoder.buf)?;
+        }
+
+        Record::PTR(ptr) => encoder.name(&ptr.target, NameKind::Compressible),
+
+        Record::RRSIG(rrsig) => {
+            encode_rrsig_fields(rrsig, encoder)?;
+            encoder.buf.extend(base64(&rrsig.signature)?);
+        }
+
+        Record::SOA(soa) => {
+            encoder.name(&soa.nameserver, NameKind::Compressible);
+            encoder.name(&soa.admin, NameKind::Compressible);
+            let settings = &soa.settings;
+            for field in [
+                settings.serial,
+                settings.refresh,
+                settings.retry,
+                settings.expire,
+                settings.minimum,
+            ] {
+                encoder.buf.extend(field.to_be_bytes());
+            }
+        }
+
+        Record::SRV(srv) => {
+            encoder.buf.extend(srv.priority.to_be_bytes());
+            encoder.buf.extend(srv.weight.to_be_bytes());
+            encoder.buf.extend(srv.port.to_be_bytes());
+            encoder.name(&srv.target, NameKind::Uncompressed);
+        }
+
+        // RFC9460 section 2.2: the target name is neither compressed nor lowercased
+        Record::SVCB(svcb) => {
+            encoder.buf.extend(svcb.priority.to_be_bytes());
+            encoder.name(&svcb.target, NameKind::Verbatim);
+            svc_params(&svcb.params, &mut encoder.buf)?;
+        }
+
+        Record::TLSA(tlsa) => {
+            encoder.buf.push(tlsa.usage);
+            encoder.buf.push(tlsa.selector);
+            encoder.buf.push(tlsa.matching_type);
+            encoder
+                .buf
+                .extend(HEXUPPER_PERMISSIVE.decode(tlsa.data.as_bytes())?);
+        }
+
+        Record::TXT(txt) => {
+            for character_string in &txt.character_strings {
+                let bytes = unescape(character_string)?;
+                encoder.buf.push(u8::try_from(bytes.len())?);
+                encoder.buf.extend(bytes);
+            }
+        }
+
+        Record::ZONEMD(zonemd) => {
+            encoder.buf.extend(zonemd.serial.to_be_bytes());
+            encoder.buf.push(zonemd.scheme);
+            encoder.buf.push(zonemd.hash_algorithm);
+            encoder
+                .buf
+                .extend(HEXUPPER_PERMISSIVE.decode(zonemd.digest.as_bytes())?);
+        }
+
+        Record::Unknown(unknown) => match &unknown.rdata {
+            UnknownRdata::Generic(rdata) => encoder.buf.extend(rdata),
+            UnknownRdata::Presentation(_) => {
+                return Err(Error::InvalidInput(format!(
+                    "the RDATA of {} records is only known in presentation format",
+                    RecordType::from_code(unknown.type_code)
+                )))
+            }
+        },
+    }
+
+    Ok(())
+}
+
+fn encode_rrsig_fields(rrsig: &RRSIG, encoder: &mut Encoder) -> Result<()> {
+    let buf = &mut encoder.buf;
+    buf.extend(rrsig.type_covered.code().to_be_bytes());
+    buf.push(rrsig.algorithm);
+    buf.push(rrsig.labels);
+    buf.extend(rrsig.original_ttl.to_be_bytes());
+    buf.extend(timestamp(rrsig.signature_expiration)?.to_be_bytes());
+    buf.extend(timestamp(rrsig.signature_inception)?.to_be_bytes());
+    buf.extend(rrsig.key_tag.to_be_bytes());
+    encoder.name(&rrsig.signer_name, NameKind::Uncompressed);
+
+    Ok(())
+}
+
+/// Decodes a base64 field, like a public key or a signature, that may have been split into
+/// whitespace separated chunks
+pub(crate) fn base64(text: &str) -> Result<Vec<u8>> {
+    let text = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+
+    Ok(BASE64.decode(text.as_bytes())?)
+}
+
+/// Decodes the escape sequences, `\X` and `\DDD`, of a presentation format string
+fn unescape(text: &str) -> Result<Vec<u8>> {
+    let mut bytes = vec![];
+    let mut input = text.bytes();
+    while let Some(byte) = input.next() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+
+        let Some(escaped) = input.next() else {
+            return Err(Error::parse(format!("incomplete escape sequence: {text}")));
+        };
+
+        if escaped.is_ascii_digit() {
+            let digits = [Some(escaped), input.next(), input.next()];
+            let mut value = 0u16;
+            for digit in digits {
+                match digit {
+                    Some(digit) if digit.is_ascii_digit() => {
+                        value = value * 10 + u16::from(digit - b'0');
+                    }
+                    _ => return Err(Error::parse(format!("invalid escape sequence: {text}"))),
+                }
+            }
+            bytes.push(u8::try_from(value)?);
+        } else {
+            bytes.push(escaped);
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Splits a comma separated list, like the value of the `alpn` SvcParam, honoring escaped commas
+fn comma_separated(value: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ',' => {
+                items.push(&value[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&value[start..]);
+    items
+}
+
+// section 14.3.2 of RFC9460; the position of a key is its number
+const SVC_PARAM_KEYS: [&str; 7] = [
+    "mandatory",
+    "alpn",
+    "no-default-alpn",
+    "port",
+    "ipv4hint",
+    "ech",
+    "ipv6hint",
+];
+
+fn svc_param_key(key: &str) -> Result<u16> {
+    if let Some(number) = SVC_PARAM_KEYS.iter().position(|known| *known == key) {
+        return Ok(number as u16);
+    }
+
+    key.strip_prefix("key")
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| Error::parse(format!("unknown SvcParamKey: {key}")))
+}
+
+/// Inverse of `svc_param_key`
+fn svc_param_key_name(number: u16) -> String {
+    match SVC_PARAM_KEYS.get(usize::from(number)) {
+        Some(key) => key.to_string(),
+        None => format!("key{number}"),
+    }
+}
+
+// section 2.2 of RFC9460; the parameters are sorted by key number
+fn svc_params(params: &[SvcParam], buf: &mut Vec<u8>) -> Result<()> {
+    let mut params = params
+        .iter()
+        .map(|param| Ok((svc_param_key(&param.key)?, param.value.as_deref())))
+        .collect::<Result<Vec<_>>>()?;
+    params.sort_by_key(|(key, _)| *key);
+
+    for (key, value) in params {
+        let value = value.unwrap_or_default();
+        let mut encoded = vec![];
+        match key {
+            0 => {
+                for key in comma_separated(value) {
+                    encoded.extend(svc_param_key(key)?.to_be_bytes());
+                }
+            }
+
+            1 => {
+                for alpn_id in comma_separated(value) {
+                    let alpn_id = unescape(alpn_id)?;
+                    encoded.push(u8::try_from(alpn_id.len())?);
+                    encoded.extend(alpn_id);
+                }
+            }
+
+            2 => {}
+
+            3 => encoded.extend(value.parse::<u16>()?.to_be_bytes()),
+
+            4 => {
+                for addr in comma_separated(value) {
+                    encoded.extend(addr.parse::<Ipv4Addr>()?.octets());
+                }
+            }
+
+            5 => encoded.extend(base64(value)?),
+
+            6 => {
+                for addr in comma_separated(value) {
+                    encoded.extend(addr.parse::<Ipv6Addr>()?.octets());
+                }
+            }
+
+            _ => encoded.extend(unescape(value)?),
+        }
+
+        buf.extend(key.to_be_bytes());
+        buf.extend(u16::try_from(encoded.len())?.to_be_bytes());
+        buf.extend(encoded);
+    }
+
+    Ok(())
+}
+
+// section 4.1.2 of RFC4034
+fn type_bitmaps(record_types: &[RecordType], buf: &mut Vec<u8>) {
+    let mut codes = record_types
+        .iter()
+        .map(|record_type| record_type.code())
+        .collect::<Vec<_>>();
+    codes.sort_unstable();
+    codes.dedup();
+
+    let mut windows: Vec<(u8, Vec<u8>)> = vec![];
+    for code in codes {
+        let [window, low] = code.to_be_bytes();
+        if windows.last().is_none_or(|(last, _)| *last != window) {
+            windows.push((window, vec![]));
+        }
+
+        let (_, bitmap) = windows.last_mut().unwrap();
+        let index = usize::from(low / 8);
+        if bitmap.len() <= index {
+            bitmap.resize(index + 1, 0);
+        }
+        bitmap[index] |= 0x80 >> (low % 8);
+    }
+
+    for (window, bitmap) in windows {
+        buf.push(window);
+        buf.push(bitmap.len() as u8);
+        buf.extend(bitmap);
+    }
+}
+
+fn salt(salt: &str, buf: &mut Vec<u8>) -> Result<()> {
+    if salt == "-" {
+        buf.push(0);
+    } else {
+        let salt = HEXUPPER_PERMISSIVE.decode(salt.as_bytes())?;
+        buf.push(u8::try_from(salt.len())?);
+        buf.extend(salt);
+    }
+
+    Ok(())
+}
+
+/// Converts the `YYYYMMDDHHmmSS` text representation of RRSIG timestamps into seconds since the
+/// UNIX epoch
+pub(crate) fn timestamp(text: u64) -> Result<u32> {
+    let seconds = text % 100;
+    let minutes = text / 100 % 100;
+    let hours = text / 10_000 % 100;
+    let day = text / 1_000_000 % 100;
+    let month = text / 100_000_000 % 100;
+    let year = text / 10_000_000_000;
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hours > 23 || minutes > 59 {
+        return Err(Error::parse(format!("invalid RRSIG timestamp: {text}")));
+    }
+
+    let days = days_from_civil(year as i64, month as i64, day as i64);
+    let unix = days * 86_400 + (hours * 3_600 + minutes * 60 + seconds) as i64;
+
+    // serial number arithmetic (RFC4034 section 3.1.5); only the lower 32 bits are kept
+    Ok(unix as u32)
+}
+
+/// Inverse of `timestamp`
+pub(crate) fn text_timestamp(unix: u64) -> u64 {
+    let (days, seconds) = ((unix / 86_400) as i64, unix % 86_400);
+    let (year, month, day) = civil_from_days(days);
+
+    let date = year as u64 * 10_000 + month as u64 * 100 + day as u64;
+    let time = seconds / 3_600 * 10_000 + seconds / 60 % 60 * 100 + seconds % 60;
+    date * 1_000_000 + time
+}
+
+// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn timestamp_roundtrip() -> Result<()> {
+        assert_eq!(1709731621, timestamp(20240306132701)?);
+        assert_eq!(1707312421, timestamp(20240207132701)?);
+        assert_eq!(20240306132701, text_timestamp(1709731621));
+
+        Ok(())
+    }
+
+    #[test]
+    fn name_is_lowercased() -> Result<()> {
+        let mut buf = vec![];
+        name(&FQDN("Example.COM.")?, &mut buf);
+        assert_eq!(b"\x07example\x03com\x00", buf.as_slice());
+
+        let mut buf = vec![];
+        name(&FQDN::ROOT, &mut buf);
+        assert_eq!(b"\x00", buf.as_slice());
+
+        Ok(())
+    }
+
+    #[test]
+    fn txt_rdata_is_unescaped() -> Result<()> {
+        let txt = Record::txt(FQDN("example.com.")?, "a \"b\"");
+        assert_eq!(b"\x05a \"b\"", rdata(&txt)?.as_slice());
+
+        let txt: Record = "example.com.\t0\tIN\tTXT\t\"\\065\" \"\"".parse()?;
+        assert_eq!(b"\x01A\x00", rdata(&txt)?.as_slice());
+
+        Ok(())
+    }
+
+    // example from appendix D.2 of RFC9460; the parameters are sorted by key
+    #[test]
+    fn svcb_rdata() -> Result<()> {
+        let svcb: Record =
+            "example.com.\t0\tIN\tSVCB\t16 foo.example.org. port=53 mandatory=alpn,ipv4hint alpn=\"h2,h3-19\" ipv4hint=192.0.2.1"
+                .parse()?;
+
+        let expected = b"\x00\x10\
+            \x03foo\x07example\x03org\x00\
+            \x00\x00\x00\x04\x00\x01\x00\x04\
+            \x00\x01\x00\x09\x02h2\x05h3-19\
+            \x00\x03\x00\x02\x00\x35\
+            \x00\x04\x00\x04\xc0\x00\x02\x01";
+        assert_eq!(expected.as_slice(), rdata(&svcb)?.as_slice());
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_rdata_is_copied_verbatim() -> Result<()> {
+        let unknown: Record = "a.example.\t0\tIN\tTYPE731\t\\# 6 ABCDEF012345".parse()?;
+
+        let mut buf = vec![];
+        record(&unknown, unknown.fqdn(), 0, &mut buf)?;
+        let expected =
+            b"\x01a\x07example\x00\x02\xdb\x00\x01\x00\x00\x00\x00\x00\x06\xab\xcd\xef\x01\x23\x45";
+        assert_eq!(expected.as_slice(), buf.as_slice());
+
+        Ok(())
+    }
+
+    // example from section 4.3 of RFC4034
+    #[test]
+    fn nsec_type_bitmaps() {
+        let mut buf = vec![];
+        type_bitmaps(
+            &[
+                RecordType::A,
+                RecordType::MX,
+                RecordType::RRSIG,
+                RecordType::NSEC,
+            ],
+            &mut buf,
+        );
+
+        let expected = [
+            0x00, 0x06, 0x40, 0x01, 0x00, 0x00, 0x00, 0x03, //
+        ];
+        assert_eq!(expected.as_slice(), buf.as_slice());
+    }
+
+    #[test]
+    fn record_roundtrip() -> Result<()> {
+        let inputs = [
+            "a.example.\t3600\tIN\tA\t192.0.2.1",
+            "a.example.\t3600\tIN\tAAAA\t2001:db8::1",
+            "example.\t3600\tIN\tCAA\t128 issue \"ca.example; account=\\\"1\\\"\"",
+            ".\t1116\tIN\tCDNSKEY\t257 3 8 AwEAAaz/tAm8yTn4Mfeh5eyI96WSVexTBAvkMgJzkKTOiW1vkIbzxeF3",
+            "com.\t7612\tIN\tCDS\t19718 13 2 8ACBB0CD28F41250A80A491389424D341522D946B0DA0C0291F2D3D771D7805A",
+            "www.Example.\t3600\tIN\tCNAME\tExample.",
+            "example.\t3600\tIN\tDNAME\texample.net.",
+            ".\t1116\tIN\tDNSKEY\t256 3 8 AwEAAaz/tAm8yTn4Mfeh5eyI96WSVexTBAvkMgJzkKTOiW1vkIbzxeF3",
+            "com.\t7612\tIN\tDS\t19718 13 2 8ACBB0CD28F41250A80A491389424D341522D946B0DA0C0291F2D3D771D7805A",
+            "example.\t300\tIN\tHTTPS\t1 . alpn=\"h3,h2\" port=8443 ipv4hint=192.0.2.1,192.0.2.2 ech=AEX+DQ== ipv6hint=2001:db8::1 key65333=\"x\\\"y\"",
+            "example.\t300\tIN\tMX\t10 mail.example.",
+            ".\t86400\tIN\tNS\tf.root-servers.net.",
+            "example.\t86400\tIN\tNSEC\tA.example. NS SOA RRSIG NSEC DNSKEY TYPE65534",
+            "abhif1b25fhcda5amfk5hnrsh6jid2ki.example.com.\t3571\tIN\tNSEC3\t1 0 5 53BCBC5805D2B761 GVPMD82B8ER38VUEGP72I721LIH19RGR A NS SOA MX TXT AAAA RRSIG DNSKEY NSEC3PARAM",
+            "com.\t86238\tIN\tNSEC3PARAM\t1 0 0 -",
+            "1.2.0.192.in-addr.arpa.\t300\tIN\tPTR\ta.example.",
+            ".\t1800\tIN\tRRSIG\tSOA 7 0 1800 20240306132701 20240207132701 11264 . wXpRU4elJPGYm2kgVVsIwGf1IkYJcQ3UE4mwmItWdxj0XWSWY07MO4Ll",
+            ".\t15633\tIN\tSOA\ta.root-servers.net. nstld.verisign-grs.com. 2024020501 1800 900 604800 86400",
+            "_sip._udp.example.\t300\tIN\tSRV\t0 5 5060 sip.example.",
+            "example.\t300\tIN\tSVCB\t0 svc.example.",
+            "_25._tcp.mail.example.\t1800\tIN\tTLSA\t3 1 1 0C72AC70B745AC19998811B131D662C9AC69DBDBE7CB23E5B514B56664C5D3D6",
+            "example.\t300\tIN\tTXT\t\"v=spf1 -all\" \"\" \"\\\\ \\\"\\255\"",
+            "example.\t3600\tIN\tZONEMD\t2023102900 1 1 7C6CF7A8F9F3B91D0E1E3AEDEB2D16E87E0C0AAD7A1C2C7DFBD3ED0D40E3F5F9",
+            "a.example.\t3600\tIN\tTYPE731\t\\# 6 ABCDEF012345",
+        ];
+
+        for input in inputs {
+            let record: Record = input.parse()?;
+            let expected = record.to_string();
+
+            let wire = record.to_wire()?;
+            let decoded = Record::from_wire(&wire)?;
+            assert_eq!(expected, decoded.to_string());
+            assert_eq!(wire, decoded.to_wire()?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn canonical_form_lowercases_names() -> Result<()> {
+        let record: Record = "WWW.Example.\t3600\tIN\tCNAME\tExample.".parse()?;
+
+        let expected =
+            b"\x03www\x07example\x00\x00\x05\x00\x01\x00\x00\x0e\x10\x00\x09\x07example\x00";
+        assert_eq!(expected.as_slice(), record.to_canonical_wire()?.as_slice());
+
+        let decoded = Record::from_wire(&record.to_wire()?)?;
+        assert_eq!("WWW.Example.", decoded.fqdn().as_str());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fqdn_roundtrip() -> Result<()> {
+        let fqdn = FQDN("Example.COM.")?;
+        assert_eq!(b"\x07Example\x03COM\x00", fqdn.to_wire().as_slice());
+        assert_eq!(fqdn, FQDN::from_wire(&fqdn.to_wire())?);
+        assert_eq!(FQDN::ROOT, FQDN::from_wire(b"\x00")?);
+
+        assert!(FQDN::from_wire(b"\x03com").is_err());
+        assert!(FQDN::from_wire(b"\x03com\x00\x00").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn decoded_labels_are_escaped() -> Result<()> {
+        let fqdn = FQDN::from_wire(b"\x04a.b\\\x02\x00 \x00")?;
+        assert_eq!("a\\.b\\\\.\\000\\032.", fqdn.as_str());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_names_longer_than_255_bytes() {
+        let mut bytes = vec![];
+        for _ in 0..4 {
+            bytes.push(63);
+            bytes.extend([b'a'; 63]);
+        }
+        bytes.push(0);
+        // 4 * (1 + 63) + 1 = 257 bytes
+        assert!(FQDN::from_wire(&bytes).is_err());
+    }
+}
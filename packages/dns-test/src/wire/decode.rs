@@ -0,0 +1,602 @@
+//! Wire format decoding
+
+use core::fmt::Write;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use data_encoding::{BASE32HEX_NOPAD, BASE64, HEXUPPER};
+
+use super::{svc_param_key_name, text_timestamp, CLASS_IN, POINTER_MASK};
+use crate::fqdn::escape_label;
+use crate::record::{
+    Record, RecordType, SoaSettings, SvcParam, Unknown, UnknownRdata, A, AAAA, CAA, CDNSKEY, CDS,
+    CNAME, DNAME, DNSKEY, DS, HTTPS, MX, NS, NSEC, NSEC3, NSEC3PARAM, PTR, RRSIG, SOA, SRV, SVCB,
+    TLSA, TXT, ZONEMD,
+};
+use crate::{Error, Result, FQDN};
+
+// section 3.1 of RFC1035
+const MAX_NAME_LEN: usize = 255;
+
+/// The fixed size fields of a resource record
+pub(super) struct RecordHeader {
+    pub(super) owner: FQDN,
+    pub(super) type_code: u16,
+    pub(super) class: u16,
+    pub(super) ttl: u32,
+    pub(super) rdlength: u16,
+}
+
+/// Reads DNS data in wire format
+///
+/// Compression pointers are resolved against the whole `message` but the decoder does not read
+/// past `end`
+pub(super) struct Decoder<'a> {
+    message: &'a [u8],
+    position: usize,
+    end: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub(super) fn new(message: &'a [u8]) -> Self {
+        Self {
+            message,
+            position: 0,
+            end: message.len(),
+        }
+    }
+
+    /// Errors if there's data left to read
+    pub(super) fn finish(&self) -> Result<()> {
+        if self.position == self.end {
+            Ok(())
+        } else {
+            Err(Error::parse(format!(
+                "{} unexpected trailing bytes",
+                self.end - self.position
+            )))
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.position == self.end
+    }
+
+    pub(super) fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.position + len;
+        if end > self.end {
+            return Err(Error::parse("unexpected end of data"));
+        }
+
+        let bytes = &self.message[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        let rest = &self.message[self.position..self.end];
+        self.position = self.end;
+        rest
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub(super) fn u16(&mut self) -> Result<u16> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub(super) fn u32(&mut self) -> Result<u32> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads a possibly compressed domain name
+    pub(super) fn name(&mut self) -> Result<FQDN> {
+        let mut text = String::new();
+        let mut len = 1; // the root label
+        let mut position = self.position;
+        let mut end = self.end;
+        let mut jumped = false;
+
+        loop {
+            let out_of_bounds = || Error::parse("domain name extends past the end of data");
+            let label_len = *self.message[..end]
+                .get(position)
+                .ok_or_else(out_of_bounds)?;
+
+            match u16::from(label_len) << 8 & POINTER_MASK {
+                0 => {
+                    let start = position + 1;
+                    position = start + usize::from(label_len);
+                    let label = self.message[..end]
+                        .get(start..position)
+                        .ok_or_else(out_of_bounds)?;
+
+                    if label.is_empty() {
+                        break;
+                    }
+
+                    len += label.len() + 1;
+                    if len > MAX_NAME_LEN {
+                        return Err(Error::parse(format!(
+                            "domain name is longer than {MAX_NAME_LEN} bytes"
+                        )));
+                    }
+
+                    escape_label(label, &mut text);
+                    text.push('.');
+                }
+
+                POINTER_MASK => {
+                    let low = *self.message[..end]
+                        .get(position + 1)
+                        .ok_or_else(out_of_bounds)?;
+                    let target = usize::from(u16::from_be_bytes([label_len, low]) & !POINTER_MASK);
+
+                    // only pointing backwards rules out loops
+                    if target >= position {
+                        return Err(Error::parse(format!(
+                            "compression pointer at offset {position} does not point backwards"
+                        )));
+                    }
+
+                    if !jumped {
+                        self.position = position + 2;
+                        jumped = true;
+                    }
+                    position = target;
+                    // the target may lie outside the data that bounds this decoder, e.g. the
+                    // RDATA of a record
+                    end = self.message.len();
+                }
+
+                _ => {
+                    return Err(Error::parse(format!(
+                        "unsupported label type: {label_len:#04x}"
+                    )))
+                }
+            }
+        }
+
+        if !jumped {
+            self.position = position;
+        }
+
+        if text.is_empty() {
+            Ok(FQDN::ROOT)
+        } else {
+            FQDN(text)
+        }
+    }
+
+    pub(super) fn record_header(&mut self) -> Result<RecordHeader> {
+        Ok(RecordHeader {
+            owner: self.name()?,
+            type_code: self.u16()?,
+            class: self.u16()?,
+            ttl: self.u32()?,
+            rdlength: self.u16()?,
+        })
+    }
+
+    /// Reads a whole resource record of class IN
+    pub(super) fn record(&mut self) -> Result<Record> {
+        let header = self.record_header()?;
+        self.record_data(header)
+    }
+
+    /// Reads the RDATA that follows `header`
+    pub(super) fn record_data(&mut self, header: RecordHeader) -> Result<Record> {
+        let RecordHeader {
+            owner,
+            type_code,
+            class,
+            ttl,
+            rdlength,
+        } = header;
+
+        if class != CLASS_IN {
+            return Err(Error::parse(format!("unsupported class: {class}")));
+        }
+
+        let end = self.position + usize::from(rdlength);
+        if end > self.end {
+            return Err(Error::parse("RDATA extends past the end of data"));
+        }
+
+        let mut rdata = Decoder {
+            message: self.message,
+            position: self.position,
+            end,
+        };
+        let record = rdata.rdata(owner, ttl, type_code).map_err(|e| {
+            Error::parse(format!(
+                "invalid {} RDATA: {e}",
+                RecordType::from_code(type_code)
+            ))
+        })?;
+        rdata.finish()?;
+        self.position = end;
+
+        Ok(record)
+    }
+
+    fn rdata(&mut self, fqdn: FQDN, ttl: u32, type_code: u16) -> Result<Record> {
+        let record = match RecordType::from_code(type_code) {
+            RecordType::A => A {
+                fqdn,
+                ttl,
+                ipv4_addr: Ipv4Addr::from(self.u32()?),
+            }
+            .into(),
+
+            RecordType::AAAA => AAAA {
+                fqdn,
+                ttl,
+                ipv6_addr: Ipv6Addr::from(<[u8; 16]>::try_from(self.bytes(16)?).unwrap()),
+            }
+            .into(),
+
+            RecordType::CAA => {
+                let flags = self.u8()?;
+                let tag_len = self.u8()?;
+                let tag = String::from_utf8(self.bytes(usize::from(tag_len))?.to_vec())?;
+
+                CAA {
+                    fqdn,
+                    ttl,
+                    flags,
+                    tag,
+                    value: escape_character_string(self.rest()),
+                }
+                .into()
+            }
+
+            RecordType::CDNSKEY => CDNSKEY {
+                zone: fqdn,
+                ttl,
+                flags: self.u16()?,
+                protocol: self.u8()?,
+                algorithm: self.u8()?,
+                public_key: BASE64.encode(self.rest()),
+            }
+            .into(),
+
+            RecordType::CDS => CDS {
+                zone: fqdn,
+                ttl,
+                key_tag: self.u16()?,
+                algorithm: self.u8()?,
+                digest_type: self.u8()?,
+                digest: HEXUPPER.encode(self.rest()),
+            }
+            .into(),
+
+            RecordType::CNAME => CNAME {
+                fqdn,
+                ttl,
+                target: self.name()?,
+            }
+            .into(),
+
+            RecordType::DNAME => DNAME {
+                fqdn,
+                ttl,
+                target: self.name()?,
+            }
+            .into(),
+
+            RecordType::DNSKEY => DNSKEY {
+                zone: fqdn,
+                ttl,
+                flags: self.u16()?,
+                protocol: self.u8()?,
+                algorithm: self.u8()?,
+                public_key: BASE64.encode(self.rest()),
+            }
+            .into(),
+
+            RecordType::DS => DS {
+                zone: fqdn,
+                ttl,
+                key_tag: self.u16()?,
+                algorithm: self.u8()?,
+                digest_type: self.u8()?,
+                digest: HEXUPPER.encode(self.rest()),
+            }
+            .into(),
+
+            RecordType::HTTPS => HTTPS {
+                fqdn,
+                ttl,
+                priority: self.u16()?,
+                target: self.name()?,
+                params: self.svc_params()?,
+            }
+            .into(),
+
+            RecordType::MX => MX {
+                fqdn,
+                ttl,
+                preference: self.u16()?,
+                exchange: self.name()?,
+            }
+            .into(),
+
+            RecordType::NS => NS {
+                zone: fqdn,
+                ttl,
+                nameserver: self.name()?,
+            }
+            .into(),
+
+            RecordType::NSEC => NSEC {
+                fqdn,
+                ttl,
+                next_domain: self.name()?,
+                record_types: self.type_bitmaps()?,
+            }
+            .into(),
+
+            RecordType::NSEC3 => {
+                let hash_alg = self.u8()?;
+                let flags = self.u8()?;
+                let iterations = self.u16()?;
+                let salt = self.salt()?;
+                let hash_len = self.u8()?;
+                let next_hashed_owner_name = BASE32HEX_NOPAD.encode(self.bytes(hash_len.into())?);
+
+                NSEC3 {
+                    fqdn,
+                    ttl,
+                    hash_alg,
+                    flags,
+                    iterations,
+                    salt,
+                    next_hashed_owner_name,
+                    record_types: self.type_bitmaps()?,
+                }
+                .into()
+            }
+
+            RecordType::NSEC3PARAM => NSEC3PARAM {
+                zone: fqdn,
+                ttl,
+                hash_alg: self.u8()?,
+                flags: self.u8()?,
+                iterations: self.u16()?,
+                salt: self.salt()?,
+            }
+            .into(),
+
+            RecordType::PTR => PTR {
+                fqdn,
+                ttl,
+                target: self.name()?,
+            }
+            .into(),
+
+            RecordType::RRSIG => RRSIG {
+                fqdn,
+                ttl,
+                type_covered: RecordType::from_code(self.u16()?),
+                algorithm: self.u8()?,
+                labels: self.u8()?,
+                original_ttl: self.u32()?,
+                signature_expiration: text_timestamp(self.u32()?.into()),
+                signature_inception: text_timestamp(self.u32()?.into()),
+                key_tag: self.u16()?,
+                signer_name: self.name()?,
+                signature: BASE64.encode(self.rest()),
+            }
+            .into(),
+
+            RecordType::SOA => SOA {
+                zone: fqdn,
+                ttl,
+                nameserver: self.name()?,
+                admin: self.name()?,
+                settings: SoaSettings {
+                    serial: self.u32()?,
+                    refresh: self.u32()?,
+                    retry: self.u32()?,
+                    expire: self.u32()?,
+                    minimum: self.u32()?,
+                },
+            }
+            .into(),
+
+            RecordType::SRV => SRV {
+                fqdn,
+                ttl,
+                priority: self.u16()?,
+                weight: self.u16()?,
+                port: self.u16()?,
+                target: self.name()?,
+            }
+            .into(),
+
+            RecordType::SVCB => SVCB {
+                fqdn,
+                ttl,
+                priority: self.u16()?,
+                target: self.name()?,
+                params: self.svc_params()?,
+            }
+            .into(),
+
+            RecordType::TLSA => TLSA {
+                fqdn,
+                ttl,
+                usage: self.u8()?,
+                selector: self.u8()?,
+                matching_type: self.u8()?,
+                data: HEXUPPER.encode(self.rest()),
+            }
+            .into(),
+
+            RecordType::TXT => {
+                let mut character_strings = vec![];
+                while !self.is_empty() {
+                    character_strings.push(escape_character_string(self.character_string()?));
+                }
+
+                if character_strings.is_empty() {
+                    return Err(Error::parse("no character strings"));
+                }
+
+                TXT {
+                    fqdn,
+                    ttl,
+                    character_strings,
+                }
+                .into()
+            }
+
+            RecordType::ZONEMD => ZONEMD {
+                zone: fqdn,
+                ttl,
+                serial: self.u32()?,
+                scheme: self.u8()?,
+                hash_algorithm: self.u8()?,
+                digest: HEXUPPER.encode(self.rest()),
+            }
+            .into(),
+
+            RecordType::Unknown(type_code) => Unknown {
+                fqdn,
+                ttl,
+                type_code,
+                rdata: UnknownRdata::Generic(self.rest().to_vec()),
+            }
+            .into(),
+        };
+
+        Ok(record)
+    }
+
+    fn character_string(&mut self) -> Result<&'a [u8]> {
+        let len = self.u8()?;
+        self.bytes(len.into())
+    }
+
+    fn salt(&mut self) -> Result<String> {
+        let salt = self.character_string()?;
+        if salt.is_empty() {
+            Ok("-".to_string())
+        } else {
+            Ok(HEXUPPER.encode(salt))
+        }
+    }
+
+    // section 4.1.2 of RFC4034
+    fn type_bitmaps(&mut self) -> Result<Vec<RecordType>> {
+        let mut record_types = vec![];
+        while !self.is_empty() {
+            let window = self.u8()?;
+            let len = self.u8()?;
+            if !(1..=32).contains(&len) {
+                return Err(Error::parse(format!("invalid bitmap length: {len}")));
+            }
+
+            for (index, byte) in self.bytes(len.into())?.iter().enumerate() {
+                for bit in 0..8 {
+                    if byte & (0x80 >> bit) != 0 {
+                        let low = index as u16 * 8 + bit;
+                        let code = u16::from(window) << 8 | low;
+                        record_types.push(RecordType::from_code(code));
+                    }
+                }
+            }
+        }
+
+        Ok(record_types)
+    }
+
+    // section 2.2 of RFC9460
+    fn svc_params(&mut self) -> Result<Vec<SvcParam>> {
+        let mut params = vec![];
+        while !self.is_empty() {
+            let key = self.u16()?;
+            let len = self.u16()?;
+            let mut value = Decoder {
+                message: self.message,
+                position: self.position,
+                end: self.position + usize::from(len),
+            };
+            self.bytes(len.into())?;
+
+            let value = match key {
+                0 => {
+                    let mut keys = vec![];
+                    while !value.is_empty() {
+                        keys.push(svc_param_key_name(value.u16()?));
+                    }
+                    Some(keys.join(","))
+                }
+
+                1 => {
+                    let mut alpn_ids = vec![];
+                    while !value.is_empty() {
+                        let alpn_id = escape_character_string(value.character_string()?);
+                        alpn_ids.push(alpn_id.replace(',', "\\,"));
+                    }
+                    Some(alpn_ids.join(","))
+                }
+
+                2 => None,
+
+                3 => Some(value.u16()?.to_string()),
+
+                4 => {
+                    let mut addrs = vec![];
+                    while !value.is_empty() {
+                        addrs.push(Ipv4Addr::from(value.u32()?).to_string());
+                    }
+                    Some(addrs.join(","))
+                }
+
+                5 => Some(BASE64.encode(value.rest())),
+
+                6 => {
+                    let mut addrs = vec![];
+                    while !value.is_empty() {
+                        let octets = <[u8; 16]>::try_from(value.bytes(16)?).unwrap();
+                        addrs.push(Ipv6Addr::from(octets).to_string());
+                    }
+                    Some(addrs.join(","))
+                }
+
+                _ => {
+                    let value = value.rest();
+                    (!value.is_empty()).then(|| escape_character_string(value))
+                }
+            };
+
+            params.push(SvcParam {
+                key: svc_param_key_name(key),
+                value,
+            });
+        }
+
+        Ok(params)
+    }
+}
+
+/// The presentation form of a character string, without the surrounding quotes
+fn escape_character_string(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len());
+    for byte in bytes {
+        match byte {
+            b'"' | b'\\' => {
+                text.push('\\');
+                text.push(char::from(*byte));
+            }
+            0x20..=0x7E => text.push(char::from(*byte)),
+            _ => write!(text, "\\{byte:03}").unwrap(),
+        }
+    }
+    text
+}
@@ -0,0 +1,436 @@
+//! DNS messages (section 4 of RFC1035)
+
+use super::decode::{Decoder, RecordHeader};
+use super::{Encoder, NameKind, CLASS_IN};
+use crate::record::{Record, RecordType};
+use crate::{Error, Result, FQDN};
+
+// section 6.1.1 of RFC6891
+const OPT: u16 = 41;
+
+/// A DNS message
+///
+/// `to_wire` compresses the domain names of the message; the section counts of the header are
+/// derived from the length of the sections
+#[derive(Clone, Debug)]
+pub struct Message {
+    pub header: Header,
+    pub questions: Vec<Question>,
+    pub answer: Vec<Record>,
+    pub authority: Vec<Record>,
+    /// The additional section, without the OPT pseudo-record
+    pub additional: Vec<Record>,
+    /// The OPT pseudo-record; `None` if the message does not use EDNS
+    pub edns: Option<Edns>,
+}
+
+impl Message {
+    /// A query for the `qtype` records of `qname` that advertises EDNS support
+    pub fn query(id: u16, qname: FQDN, qtype: RecordType) -> Self {
+        Self {
+            header: Header {
+                id,
+                ..Header::default()
+            },
+            questions: vec![Question { qname, qtype }],
+            answer: vec![],
+            authority: vec![],
+            additional: vec![],
+            edns: Some(Edns::default()),
+        }
+    }
+
+    /// The 12-bit response code; its upper 8 bits are carried in the OPT pseudo-record
+    pub fn rcode(&self) -> u16 {
+        let extended = self.edns.as_ref().map_or(0, |edns| edns.extended_rcode);
+        u16::from(extended) << 4 | u16::from(self.header.rcode)
+    }
+
+    /// The wire form of the message, with compressed domain names
+    pub fn to_wire(&self) -> Result<Vec<u8>> {
+        let Self {
+            header,
+            questions,
+            answer,
+            authority,
+            additional,
+            edns,
+        } = self;
+
+        let mut encoder = Encoder::compressed();
+        let buf = &mut encoder.buf;
+        buf.extend(header.id.to_be_bytes());
+        buf.extend(header.flags()?.to_be_bytes());
+        for count in [
+            questions.len(),
+            answer.len(),
+            authority.len(),
+            additional.len() + usize::from(edns.is_some()),
+        ] {
+            buf.extend(u16::try_from(count)?.to_be_bytes());
+        }
+
+        for Question { qname, qtype } in questions {
+            encoder.name(qname, NameKind::Compressible);
+            encoder.buf.extend(qtype.code().to_be_bytes());
+            encoder.buf.extend(CLASS_IN.to_be_bytes());
+        }
+
+        for record in answer.iter().chain(authority).chain(additional) {
+            encoder.record(record.fqdn(), record, record.ttl())?;
+        }
+
+        if let Some(edns) = edns {
+            edns.encode(&mut encoder)?;
+        }
+
+        Ok(encoder.buf)
+    }
+
+    /// Decodes a message that spans all of `bytes`
+    pub fn from_wire(bytes: &[u8]) -> Result<Self> {
+        let mut decoder = Decoder::new(bytes);
+        let id = decoder.u16()?;
+        let header = Header::from_flags(id, decoder.u16()?);
+        let qdcount = decoder.u16()?;
+        let ancount = decoder.u16()?;
+        let nscount = decoder.u16()?;
+        let arcount = decoder.u16()?;
+
+        let mut questions = vec![];
+        for _ in 0..qdcount {
+            let qname = decoder.name()?;
+            let qtype = RecordType::from_code(decoder.u16()?);
+            let qclass = decoder.u16()?;
+            if qclass != CLASS_IN {
+                return Err(Error::parse(format!("unsupported class: {qclass}")));
+            }
+
+            questions.push(Question { qname, qtype });
+        }
+
+        let answer = (0..ancount)
+            .map(|_| decoder.record())
+            .collect::<Result<_>>()?;
+        let authority = (0..nscount)
+            .map(|_| decoder.record())
+            .collect::<Result<_>>()?;
+
+        let mut additional = vec![];
+        let mut edns = None;
+        for _ in 0..arcount {
+            let header = decoder.record_header()?;
+            if header.type_code == OPT {
+                if edns.is_some() {
+                    return Err(Error::parse("more than one OPT record"));
+                }
+                edns = Some(Edns::decode(header, &mut decoder)?);
+            } else {
+                additional.push(decoder.record_data(header)?);
+            }
+        }
+
+        decoder.finish()?;
+
+        Ok(Self {
+            header,
+            questions,
+            answer,
+            authority,
+            additional,
+            edns,
+        })
+    }
+}
+
+/// The header of a message, without the section counts (section 4.1.1 of RFC1035)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Header {
+    pub id: u16,
+    pub qr: bool,
+    /// 4-bit value
+    pub opcode: u8,
+    pub authoritative_answer: bool,
+    pub truncated: bool,
+    pub recursion_desired: bool,
+    pub recursion_available: bool,
+    pub authenticated_data: bool,
+    pub checking_disabled: bool,
+    /// The lower 4 bits of the response code; see `Message::rcode`
+    pub rcode: u8,
+}
+
+impl Header {
+    fn flags(&self) -> Result<u16> {
+        let Self {
+            id: _,
+            qr,
+            opcode,
+            authoritative_answer,
+            truncated,
+            recursion_desired,
+            recursion_available,
+            authenticated_data,
+            checking_disabled,
+            rcode,
+        } = *self;
+
+        if opcode > 0xF || rcode > 0xF {
+            return Err(Error::Other(format!(
+                "opcode ({opcode}) and rcode ({rcode}) must fit in 4 bits"
+            )));
+        }
+
+        let bit = |flag: bool, position: u16| u16::from(flag) << position;
+        Ok(bit(qr, 15)
+            | u16::from(opcode) << 11
+            | bit(authoritative_answer, 10)
+            | bit(truncated, 9)
+            | bit(recursion_desired, 8)
+            | bit(recursion_available, 7)
+            | bit(authenticated_data, 5)
+            | bit(checking_disabled, 4)
+            | u16::from(rcode))
+    }
+
+    fn from_flags(id: u16, flags: u16) -> Self {
+        let bit = |position: u16| flags >> position & 1 == 1;
+        Self {
+            id,
+            qr: bit(15),
+            opcode: (flags >> 11 & 0xF) as u8,
+            authoritative_answer: bit(10),
+            truncated: bit(9),
+            recursion_desired: bit(8),
+            recursion_available: bit(7),
+            authenticated_data: bit(5),
+            checking_disabled: bit(4),
+            rcode: (flags & 0xF) as u8,
+        }
+    }
+}
+
+/// An entry of the question section; its class is always IN
+#[derive(Clone, Debug)]
+pub struct Question {
+    pub qname: FQDN,
+    pub qtype: RecordType,
+}
+
+/// The contents of the OPT pseudo-record (section 6.1 of RFC6891)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Edns {
+    pub udp_payload_size: u16,
+    /// The upper 8 bits of the response code; see `Message::rcode`
+    pub extended_rcode: u8,
+    pub version: u8,
+    /// The DO bit (RFC3225)
+    pub dnssec_ok: bool,
+    pub options: Vec<EdnsOption>,
+}
+
+impl Default for Edns {
+    fn default() -> Self {
+        Self {
+            // recommended by the DNS flag day 2020
+            udp_payload_size: 1232,
+            extended_rcode: 0,
+            version: 0,
+            dnssec_ok: false,
+            options: vec![],
+        }
+    }
+}
+
+impl Edns {
+    fn encode(&self, encoder: &mut Encoder) -> Result<()> {
+        let Self {
+            udp_payload_size,
+            extended_rcode,
+            version,
+            dnssec_ok,
+            options,
+        } = self;
+
+        encoder.name(&FQDN::ROOT, NameKind::Compressible);
+        let buf = &mut encoder.buf;
+        buf.extend(OPT.to_be_bytes());
+        buf.extend(udp_payload_size.to_be_bytes());
+        buf.push(*extended_rcode);
+        buf.push(*version);
+        buf.extend((u16::from(*dnssec_ok) << 15).to_be_bytes());
+
+        // code (2 bytes), length (2 bytes) and data of each option
+        let rdata = options
+            .iter()
+            .try_fold(0u16, |rdlength, option| {
+                u16::try_from(option.data.len())
+                    .ok()
+                    .and_then(|len| len.checked_add(4))
+                    .and_then(|len| rdlength.checked_add(len))
+            })
+            .ok_or_else(|| {
+                Error::InvalidInput("the EDNS options do not fit in an OPT record".to_string())
+            })?;
+        buf.extend(rdata.to_be_bytes());
+        for EdnsOption { code, data } in options {
+            buf.extend(code.to_be_bytes());
+            buf.extend(u16::try_from(data.len())?.to_be_bytes());
+            buf.extend(data);
+        }
+
+        Ok(())
+    }
+
+    fn decode(header: RecordHeader, decoder: &mut Decoder) -> Result<Self> {
+        let RecordHeader {
+            owner,
+            type_code: _,
+            class,
+            ttl,
+            rdlength,
+        } = header;
+
+        if !owner.is_root() {
+            return Err(Error::parse("the owner of the OPT record is not the root"));
+        }
+
+        let [extended_rcode, version, flags @ ..] = ttl.to_be_bytes();
+
+        let mut options = vec![];
+        let mut rdata = decoder.bytes(rdlength.into())?;
+        while !rdata.is_empty() {
+            let mut option = Decoder::new(rdata);
+            let code = option.u16()?;
+            let len = option.u16()?;
+            let data = option.bytes(len.into())?.to_vec();
+            rdata = &rdata[4 + data.len()..];
+
+            options.push(EdnsOption { code, data });
+        }
+
+        Ok(Self {
+            udp_payload_size: class,
+            extended_rcode,
+            version,
+            dnssec_ok: u16::from_be_bytes(flags) >> 15 == 1,
+            options,
+        })
+    }
+}
+
+/// An EDNS option, e.g. an Extended DNS Error (RFC8914) or a cookie (RFC7873)
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdnsOption {
+    pub code: u16,
+    pub data: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    // `dig +adflag +dnssec A example.com.` with a fixed ID
+    const QUERY: &[u8] = b"\x12\x34\x01\x20\x00\x01\x00\x00\x00\x00\x00\x01\
+        \x07example\x03com\x00\x00\x01\x00\x01\
+        \x00\x00\x29\x04\xd0\x00\x00\x80\x00\x00\x00";
+
+    #[test]
+    fn query() -> Result<()> {
+        let mut query = Message::query(0x1234, FQDN("example.com.")?, RecordType::A);
+        query.header.recursion_desired = true;
+        query.header.authenticated_data = true;
+        query.edns.as_mut().unwrap().dnssec_ok = true;
+
+        assert_eq!(QUERY, query.to_wire()?.as_slice());
+
+        let decoded = Message::from_wire(QUERY)?;
+        assert_eq!(query.header, decoded.header);
+        assert_eq!(query.edns, decoded.edns);
+        let [question] = decoded.questions.try_into().expect("exactly one question");
+        assert_eq!("example.com.", question.qname.as_str());
+        assert_eq!(RecordType::A, question.qtype);
+
+        Ok(())
+    }
+
+    #[test]
+    fn names_are_compressed() -> Result<()> {
+        let mut response = Message::query(1, FQDN("www.example.com.")?, RecordType::A);
+        response.header.qr = true;
+        response.answer = vec![
+            "www.example.com.\t300\tIN\tCNAME\texample.com.".parse()?,
+            "example.com.\t300\tIN\tA\t192.0.2.1".parse()?,
+        ];
+        response.authority = vec!["example.com.\t300\tIN\tNS\tns.example.com.".parse()?];
+        response.additional = vec!["ns.example.com.\t300\tIN\tA\t192.0.2.2".parse()?];
+
+        let bytes = response.to_wire()?;
+
+        // the owner of the first answer points to the question
+        let answer = 12 + 17 + 4;
+        assert_eq!([0xC0, 12], bytes[answer..answer + 2]);
+        // the CNAME target points to the `example.com.` suffix of the question
+        let target = answer + 2 + 10;
+        assert_eq!([0xC0, 12 + 4], bytes[target..target + 2]);
+
+        let decoded = Message::from_wire(&bytes)?;
+        let sections = |message: &Message| {
+            [&message.answer, &message.authority, &message.additional]
+                .map(|section| section.iter().map(Record::to_string).collect::<Vec<_>>())
+        };
+        assert_eq!(sections(&response), sections(&decoded));
+
+        Ok(())
+    }
+
+    #[test]
+    fn extended_rcode() -> Result<()> {
+        let mut response = Message::query(1, FQDN::ROOT, RecordType::SOA);
+        // BADVERS (RFC6891)
+        response.header.rcode = 0;
+        response.edns.as_mut().unwrap().extended_rcode = 1;
+        response.edns.as_mut().unwrap().options = vec![EdnsOption {
+            code: 15,
+            data: vec![0, 9],
+        }];
+
+        let decoded = Message::from_wire(&response.to_wire()?)?;
+        assert_eq!(16, decoded.rcode());
+        assert_eq!(response.edns, decoded.edns);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_oversized_edns_options() -> Result<()> {
+        let mut query = Message::query(1, FQDN("example.com.")?, RecordType::A);
+        let option = EdnsOption {
+            code: 65001,
+            data: vec![0; 40_000],
+        };
+        query.edns.as_mut().unwrap().options = vec![option.clone(), option];
+
+        assert!(matches!(query.to_wire(), Err(Error::InvalidInput(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_compression_loops() {
+        let mut bytes = QUERY[..12].to_vec();
+        bytes[11] = 0; // no additional records
+                       // the question name points to itself
+        bytes.extend(b"\xC0\x0C\x00\x01\x00\x01");
+
+        assert!(Message::from_wire(&bytes).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_message() {
+        assert!(Message::from_wire(&QUERY[..QUERY.len() - 1]).is_err());
+        assert!(Message::from_wire(&QUERY[..5]).is_err());
+    }
+}
@@ -5,13 +5,15 @@
 //! - relative domain names are not used; all domain names must be in fully-qualified form
 
 use core::fmt;
-use std::array;
 use std::net::Ipv4Addr;
 use std::str::FromStr;
+use std::{array, iter};
 
 use crate::record::{self, Record, SOA};
+use crate::rrset::RRset;
 use crate::{Error, Result, DEFAULT_TTL, FQDN};
 
+#[derive(Clone, Debug)]
 pub struct ZoneFile {
     origin: FQDN,
     pub soa: SOA,
@@ -39,6 +41,12 @@ impl ZoneFile {
         self.add(Record::a(nameserver, ipv4_addr));
     }
 
+    /// All the records of the zone, including its SOA record, grouped into RRsets in canonical
+    /// order
+    pub fn rrsets(&self) -> Result<Vec<RRset>> {
+        RRset::group(iter::once(self.soa.clone().into()).chain(self.records.iter().cloned()))
+    }
+
     pub(crate) fn origin(&self) -> &FQDN {
         &self.origin
     }
@@ -63,17 +71,21 @@ impl FromStr for ZoneFile {
     fn from_str(input: &str) -> Result<Self> {
         let mut records = vec![];
         let mut maybe_soa = None;
-        for line in input.lines() {
+        for (index, line) in input.lines().enumerate() {
             let line = line.trim();
 
             if line.is_empty() {
                 continue;
             }
 
-            let record: Record = line.parse()?;
+            let record: Record = line
+                .parse()
+                .map_err(|e: Error| e.at_line(index + 1, line))?;
             if let Record::SOA(soa) = record {
                 if maybe_soa.is_some() {
-                    return Err("found more than one SOA record".into());
+                    return Err(
+                        Error::parse("found more than one SOA record").at_line(index + 1, line)
+                    );
                 }
 
                 maybe_soa = Some(soa);
@@ -82,7 +94,7 @@ impl FromStr for ZoneFile {
             }
         }
 
-        let soa = maybe_soa.ok_or("no SOA record found in zone file")?;
+        let soa = maybe_soa.ok_or_else(|| Error::parse("no SOA record found in zone file"))?;
         Ok(Self {
             origin: soa.zone.clone(),
             soa,
@@ -164,15 +176,17 @@ impl FromStr for DNSKEY {
         let [Some(zone), Some(class), Some(record_type), Some(flags), Some(protocol), Some(algorithm), Some(public_key), None] =
             array::from_fn(|_| columns.next())
         else {
-            return Err("expected 7 columns".into());
+            return Err(Error::parse("expected 7 columns"));
         };
 
         if record_type != "DNSKEY" {
-            return Err(format!("tried to parse `{record_type}` record as a DNSKEY record").into());
+            return Err(Error::parse(format!(
+                "tried to parse `{record_type}` record as a DNSKEY record"
+            )));
         }
 
         if class != "IN" {
-            return Err(format!("unknown class: {class}").into());
+            return Err(Error::parse(format!("unknown class: {class}")));
         }
 
         Ok(Self {
@@ -188,6 +202,7 @@ impl FromStr for DNSKEY {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::record::{DigestType, RecordType, SoaSettings, DS};
 
     use pretty_assertions::assert_eq;
 
@@ -213,6 +228,40 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn rrsets_include_the_soa_record() -> Result<()> {
+        let mut zone_file = ZoneFile::new(SOA {
+            zone: FQDN::COM,
+            ttl: 86400,
+            nameserver: FQDN("primary.com.")?,
+            admin: FQDN("admin.com.")?,
+            settings: SoaSettings::default(),
+        });
+        zone_file.referral(
+            FQDN::NAMESERVERS,
+            FQDN("primary.nameservers.com.")?,
+            Ipv4Addr::new(192, 168, 1, 2),
+        );
+        zone_file.add(Record::ns(FQDN::COM, FQDN("primary.com.")?));
+
+        let rrsets = zone_file.rrsets()?;
+        let summary = rrsets
+            .iter()
+            .map(|rrset| (rrset.fqdn().as_str(), rrset.record_type()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            [
+                ("com.", RecordType::NS),
+                ("com.", RecordType::SOA),
+                ("nameservers.com.", RecordType::NS),
+                ("primary.nameservers.com.", RecordType::A),
+            ],
+            summary.as_slice()
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn roundtrip() -> Result<()> {
         // `ldns-signzone`'s output minus trailing comments; long trailing fields have been split as well
@@ -223,4 +272,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn key_tags_match_rrsigs() -> Result<()> {
+        let zone: ZoneFile = include_str!("muster.zone").parse()?;
+
+        let mut key_tags = vec![];
+        for record in &zone.records {
+            if let Record::DNSKEY(dnskey) = record {
+                key_tags.push((dnskey.is_key_signing_key(), dnskey.key_tag()?));
+            }
+        }
+        key_tags.sort();
+        // `ldns-signzone` reports these in its trailing comments
+        assert_eq!(vec![(false, 11387), (true, 11245)], key_tags);
+
+        // every RRSIG refers to one of the keys
+        for record in &zone.records {
+            if let Record::RRSIG(rrsig) = record {
+                assert!(key_tags.iter().any(|(_, tag)| *tag == rrsig.key_tag));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn ds_roundtrip() -> Result<()> {
+        let zone: ZoneFile = include_str!("muster.zone").parse()?;
+
+        for record in &zone.records {
+            if let Record::DNSKEY(dnskey) = record {
+                for digest_type in [DigestType::SHA1, DigestType::SHA256, DigestType::SHA384] {
+                    let ds = dnskey.ds(digest_type)?;
+                    let output: DS = ds.to_string().parse()?;
+
+                    assert_eq!(ds.key_tag, output.key_tag);
+                    assert_eq!(dnskey.key_tag()?, output.key_tag);
+                    assert_eq!(dnskey.algorithm, output.algorithm);
+                    assert_eq!(digest_type.code(), output.digest_type);
+                    assert_eq!(ds.digest, output.digest);
+                }
+            }
+        }
+
+        Ok(())
+    }
 }